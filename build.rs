@@ -1,15 +1,29 @@
-//! Embed version from git tag (release) or fall back to Cargo.toml
+//! Embed version from git tag (release) or fall back to Cargo.toml, plus build info
+//! (git commit, rustc version, target triple) for `ratlog --version --verbose`.
+
+use std::process::Command;
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let s = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if s.is_empty() { None } else { Some(s) }
+}
 
 fn main() {
-    if let Ok(output) = std::process::Command::new("git")
-        .args(["describe", "--tags", "--abbrev=0"])
-        .output()
-    {
-        if output.status.success() {
-            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !version.is_empty() {
-                println!("cargo:rustc-env=RATLOG_VERSION={}", version);
-            }
-        }
+    if let Some(version) = command_output("git", &["describe", "--tags", "--abbrev=0"]) {
+        println!("cargo:rustc-env=RATLOG_VERSION={}", version);
+    }
+    if let Some(hash) = command_output("git", &["rev-parse", "--short", "HEAD"]) {
+        println!("cargo:rustc-env=RATLOG_GIT_HASH={}", hash);
+    }
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    if let Some(rustc_version) = command_output(&rustc, &["--version"]) {
+        println!("cargo:rustc-env=RATLOG_RUSTC_VERSION={}", rustc_version);
+    }
+    if let Ok(target) = std::env::var("TARGET") {
+        println!("cargo:rustc-env=RATLOG_BUILD_TARGET={}", target);
     }
 }
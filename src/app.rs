@@ -1,174 +1,941 @@
 //! Main TUI app: state, draw, event handling.
 
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
-use std::path::PathBuf;
-use std::time::Duration;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::{
+    Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
 use futures::FutureExt;
 use futures::StreamExt;
 use ratatui::{
     DefaultTerminal, Frame,
-    layout::{Constraint, Direction, Layout},
-    style::{Modifier, Style},
-    widgets::{Block, Clear, HighlightSpacing, List, ListItem, ListState, Paragraph, Wrap},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        Block, Clear, HighlightSpacing, List, ListItem, ListState, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Wrap,
+    },
 };
+use unicode_width::UnicodeWidthStr;
 
-use crate::constants::{MAX_LINES, POLL_READ_CAP};
-use crate::login;
-use crate::logs::apply_filter;
-use crate::settings::{load_settings, save_settings};
-use crate::theme::{self, AccentColor, BorderColor, Focus, StatusColor, TextColor, TextStyle};
-use crate::util::{centered_rect, current_process_memory};
+use crate::cli::RunOptions;
+use crate::constants::{
+    GLOB_SCAN_INTERVAL, JUMP_LIST_LEN, MAX_LINES, MESSAGE_DURATION, MIN_TERMINAL_HEIGHT,
+    MIN_TERMINAL_WIDTH, POLL_INTERVALS_MS, POLL_READ_CAP, SIZE_CHECK_INTERVAL, TAB_WIDTHS,
+};
+use crate::highlight::{find_pattern, find_tokens};
+use crate::i18n::{self, Language, fill, t};
+use crate::keymap::{self, Action, GLOBAL_BINDINGS, LOG_LIST_BINDINGS};
+use crate::login::{self, ShareExpiry};
+use crate::logs::{
+    FilterSpec, LineSep, LoadOptions, SourceKind, column_widths, columnize, decode_appended,
+    decode_appended_utf16, entry_filter_indices, entry_starts, entry_step, filter_indices,
+    is_blank, line_tokens, load_logs_with, newest_match, split_appended_with,
+    tail_numbers_relative, utf16_bom,
+};
+use crate::prefix::LinePrefix;
+use crate::redact::{RedactRule, Redactor};
+use crate::remote::{RemoteFollow, RemoteSource};
+use crate::settings::{
+    PerFileSettings, Settings, load_filter, load_hint_text, load_line_prefix, load_redact_rules,
+    load_settings, save_settings, settings_error, settings_key,
+};
+use crate::status::{StatusField, StatusLayout, fit_status};
+use crate::theme::{
+    self, AccentColor, BorderColor, Focus, GutterColor, StatusColor, TextColor, TextStyle,
+    ThemeColors, ThemePreset,
+};
+use crate::util::{
+    RateMeter, centered_rect, context_window, copy_to_clipboard, current_process_memory,
+    delete_word_before, display_column, expand_tabs, format_millis, format_rate, format_uptime,
+    next_grapheme, next_word_end, percent_index, poll_interval, prev_grapheme, prev_word_start,
+    scrollbar_index, truncate_chars, truncate_middle, visible_segments,
+};
 
 pub struct App {
     running: bool,
-    event_stream: EventStream,
+    /// Created on first use so an `App` can be built and drawn without a terminal.
+    event_stream: Option<EventStream>,
     all_lines: Vec<String>,
+    /// Lines kept in `all_lines`: MAX_LINES, or the loaded count with `--all`.
+    max_lines: usize,
     filter: String,
+    /// Parsed `filter`, updated on each edit.
+    filter_spec: FilterSpec,
+    /// Why the `re:` pattern of the filter doesn't compile, if it doesn't.
+    filter_error: Option<regex::Error>,
+    /// Byte offset into `filter`, always on a grapheme boundary.
     filter_cursor: usize,
     focus: Focus,
     list_state: ListState,
+    /// Rows inside the log pane borders, captured on each draw.
+    list_view_height: usize,
+    /// Log pane area (with borders) from the last draw; the scrollbar sits on its right border.
+    list_area: Rect,
+    /// Left button went down on the scrollbar and has not been released yet.
+    scrollbar_drag: bool,
     live: bool,
     live_file_path: Option<PathBuf>,
     live_file_offset: u64,
     live_partial: String,
+    /// Bytes of a UTF-8 character cut off by the end of the last poll read.
+    live_pending: Vec<u8>,
+    /// False between `FocusLost` and `FocusGained`; live polling pauses meanwhile.
+    focused: bool,
+    /// Consecutive live polls that read no new bytes; drives the poll backoff.
+    idle_polls: u32,
+    /// File size when last loaded or polled, to notice growth outside live mode.
+    seen_size: Option<u64>,
+    last_size_check: Instant,
+    /// Last `--glob` scan for a newer file.
+    last_glob_scan: Instant,
+    /// When the viewer started, for the uptime status field.
+    started_at: Instant,
+    /// The file grew since it was loaded and live mode is off; offers `r` to reload.
+    file_changed: bool,
+    /// Live mode could not open the file on the last poll (deleted, or mid-rotation); polling
+    /// goes on and reloads it once it is back.
+    file_unavailable: bool,
+    /// Live mode keeps the last line selected; cleared when the user moves away, set again by
+    /// G / End.
+    follow_tail: bool,
     file_line_start: usize,
+    /// Recent live appends, for the lines-per-second rate in the status bar.
+    live_rate: RateMeter,
+    /// Lines dropped from the front of the buffer by live appends since startup.
+    dropped_lines: usize,
     show_settings: bool,
     settings_list_state: ListState,
-    accent_color: AccentColor,
-    text_color: TextColor,
-    text_style: TextStyle,
-    border_color: BorderColor,
-    status_color: StatusColor,
+    show_help: bool,
+    /// Query typed into the command palette (Ctrl+P) while it is open.
+    palette: Option<String>,
+    palette_list_state: ListState,
+    help_list_state: ListState,
+    /// Digits typed after `:`; `Some` while the go-to-line prompt is open.
+    goto_input: Option<String>,
+    /// File lines selected before recent jumps, newest last (Ctrl+O pops them).
+    jump_back: Vec<usize>,
+    /// File lines left by Ctrl+O, for Ctrl+I.
+    jump_forward: Vec<usize>,
+    /// Gutter drawn before each line (`line_prefix` in settings.json).
+    line_prefix: LinePrefix,
+    /// Replaces the key hint line (`hint_text` in settings.json).
+    hint_text: Option<String>,
+    /// Digits typed in the log list before `%` (go to that percentage).
+    percent_input: String,
+    /// Transient message shown over the key hint line, with when it was set.
+    status_message: Option<(String, Instant)>,
+    /// `redact` rules from `settings.json`, applied to lines as they are loaded.
+    redactor: Redactor,
+    /// Words of the selected line and the highlighted one while picking a filter with `w`.
+    token_pick: Option<(Vec<String>, usize)>,
+    /// Text typed after `*`; `Some` while the find prompt is open.
+    find_input: Option<String>,
+    /// Text being found with `n` / `N` and its pattern; its matches in the shown lines are
+    /// highlighted until Esc clears it.
+    find: Option<(String, regex::Regex)>,
+    /// File line and occurrence within that line of the current find match.
+    find_current: Option<(usize, usize)>,
+    settings: Settings,
+    /// Entry in `settings.json` for the open file when per-file settings are on.
+    settings_key: Option<String>,
+    /// Setting changes are saved (see [`AppConfig::persist`]).
+    persist: bool,
+    /// Status bar badge: where the lines came from.
+    source: SourceKind,
+    /// Line numbers count from the first loaded line, not the file's first line (see
+    /// [`tail_numbers_relative`]); the log pane title says so.
+    relative_numbers: bool,
+    /// Line terminator of the open file for live appends (`--line-sep`, `Auto` resolved).
+    line_sep: LineSep,
+    /// Remote log being viewed (see [`App::with_remote`]); `live_file_offset` is its size.
+    remote: Option<RemoteSource>,
+    /// Appends to the remote log while live mode is on.
+    remote_follow: Option<RemoteFollow>,
+    /// Byte order of the open file when it is UTF-16 (`Some(true)` for big-endian), so live
+    /// appends are decoded the same way as the loaded lines.
+    utf16: Option<bool>,
+    /// `--theme`: the preset's colours and the saved ones they replace for this session.
+    theme_override: Option<(ThemeColors, ThemeColors)>,
     pending_share: bool,
     share_message: Option<String>,
     show_share_confirm: bool,
+    /// Warning before the share dialog that no `redact` rules will mask the upload.
+    show_redact_warning: bool,
     share_is_public: bool,
+    share_expiry: ShareExpiry,
+    options: RunOptions,
+}
+
+/// What the viewer reads from `settings.json` when it starts. [`AppConfig::load`] reads it;
+/// tests pass [`AppConfig::default`] so the developer's own config plays no part.
+#[derive(Debug, Clone, Default)]
+pub struct AppConfig {
+    /// Settings for the open file, or the global ones.
+    pub settings: Settings,
+    /// Entry in `settings.json` for the open file when per-file settings are on.
+    pub settings_key: Option<String>,
+    /// Filter remembered under `settings_key`.
+    pub filter: String,
+    pub redact: Vec<RedactRule>,
+    pub hint_text: Option<String>,
+    pub line_prefix: Option<String>,
+    /// Why `settings.json` could not be read, shown when the viewer opens.
+    pub error: Option<String>,
+    /// Write setting changes back to `settings.json`.
+    pub persist: bool,
+}
+
+impl AppConfig {
+    /// The saved configuration for `live_file_path` (its per-file entry when per-file settings
+    /// are on).
+    pub fn load(live_file_path: Option<&Path>) -> Self {
+        let global = load_settings(None);
+        let settings_key = live_file_path.and_then(|p| settings_key(p, global.per_file));
+        let (settings, filter) = match settings_key.as_deref() {
+            Some(key) => (
+                load_settings(Some(key)),
+                load_filter(Some(key)).unwrap_or_default(),
+            ),
+            None => (global, String::new()),
+        };
+        Self {
+            settings,
+            settings_key,
+            filter,
+            redact: load_redact_rules(),
+            hint_text: load_hint_text(),
+            line_prefix: load_line_prefix(),
+            error: settings_error(),
+            persist: true,
+        }
+    }
 }
 
 impl App {
     pub fn new(
+        all_lines: Vec<String>,
+        live_file_path: Option<PathBuf>,
+        live_file_offset: u64,
+        file_line_start: usize,
+    ) -> Self {
+        let config = AppConfig::load(live_file_path.as_deref());
+        Self::with_max_lines(
+            all_lines,
+            live_file_path,
+            live_file_offset,
+            file_line_start,
+            MAX_LINES,
+            config,
+        )
+    }
+
+    /// Like [`App::new`] followed by [`App::with_run_options`]. With `--all`, every loaded line
+    /// is kept (live appends then drop the oldest beyond that count) instead of MAX_LINES.
+    pub fn new_with_options(
+        all_lines: Vec<String>,
+        live_file_path: Option<PathBuf>,
+        live_file_offset: u64,
+        file_line_start: usize,
+        options: RunOptions,
+    ) -> Self {
+        let config = AppConfig::load(live_file_path.as_deref());
+        Self::new_with_config(
+            all_lines,
+            live_file_path,
+            live_file_offset,
+            file_line_start,
+            options,
+            config,
+        )
+    }
+
+    /// [`App::new_with_options`] with `config` instead of what `settings.json` holds.
+    pub fn new_with_config(
+        all_lines: Vec<String>,
+        live_file_path: Option<PathBuf>,
+        live_file_offset: u64,
+        file_line_start: usize,
+        options: RunOptions,
+        config: AppConfig,
+    ) -> Self {
+        let max_lines = if options.all {
+            all_lines.len().max(MAX_LINES)
+        } else {
+            MAX_LINES
+        };
+        Self::with_max_lines(
+            all_lines,
+            live_file_path,
+            live_file_offset,
+            file_line_start,
+            max_lines,
+            config,
+        )
+        .with_run_options(options)
+    }
+
+    fn with_max_lines(
         mut all_lines: Vec<String>,
         live_file_path: Option<PathBuf>,
         live_file_offset: u64,
         mut file_line_start: usize,
+        max_lines: usize,
+        config: AppConfig,
     ) -> Self {
-        if all_lines.len() > MAX_LINES {
-            let drop = all_lines.len() - MAX_LINES;
+        if all_lines.len() > max_lines {
+            let drop = all_lines.len() - max_lines;
             all_lines.drain(0..drop);
             file_line_start += drop;
         }
+        let (redactor, redact_errors) = Redactor::new(&config.redact);
+        redactor.apply_all(&mut all_lines);
         let mut list_state = ListState::default();
         if !all_lines.is_empty() {
             list_state.select(Some(0));
         }
-        let (accent_color, text_color, text_style, border_color, status_color) = load_settings();
+        let AppConfig {
+            settings,
+            settings_key,
+            mut filter,
+            ..
+        } = config;
+        truncate_chars(&mut filter, settings.max_filter_len);
+        let (filter_spec, filter_error) = parse_filter(&filter, settings.filter_message_only);
+        let source = if live_file_path.is_some() {
+            SourceKind::File
+        } else {
+            SourceKind::Sample
+        };
+        let relative_numbers = live_file_path
+            .as_deref()
+            .is_some_and(|p| tail_numbers_relative(p, LoadOptions::default()));
+        let utf16 = live_file_path
+            .as_deref()
+            .and_then(|p| utf16_bom(p).ok().flatten());
+        let seen_size = live_file_path
+            .as_deref()
+            .and_then(|p| std::fs::metadata(p).ok())
+            .map(|m| m.len());
         Self {
             running: true,
-            event_stream: EventStream::default(),
+            event_stream: None,
             all_lines,
-            filter: String::new(),
-            filter_cursor: 0,
+            max_lines,
+            filter_cursor: filter.len(),
+            filter_spec,
+            filter_error,
+            filter,
             focus: Focus::LogList,
             list_state,
+            list_view_height: 0,
+            list_area: Rect::default(),
+            scrollbar_drag: false,
             live: false,
             live_file_path,
             live_file_offset,
             live_partial: String::new(),
+            live_pending: Vec::new(),
+            focused: true,
+            idle_polls: 0,
+            seen_size,
+            last_size_check: Instant::now(),
+            last_glob_scan: Instant::now(),
+            started_at: Instant::now(),
+            file_changed: false,
+            file_unavailable: false,
+            follow_tail: false,
             file_line_start,
+            live_rate: RateMeter::default(),
+            dropped_lines: 0,
             show_settings: false,
             settings_list_state: ListState::default().with_selected(Some(0)),
-            accent_color,
-            text_color,
-            text_style,
-            border_color,
-            status_color,
+            show_help: false,
+            palette: None,
+            palette_list_state: ListState::default(),
+            help_list_state: ListState::default(),
+            goto_input: None,
+            find_input: None,
+            find: None,
+            find_current: None,
+            jump_back: Vec::new(),
+            jump_forward: Vec::new(),
+            hint_text: config.hint_text,
+            line_prefix: config
+                .line_prefix
+                .map(|t| LinePrefix::parse(&t))
+                .unwrap_or_default(),
+            percent_input: String::new(),
+            status_message: config
+                .error
+                .map(|e| fill(t().settings_invalid, &[&e]))
+                .or_else(|| {
+                    (!redact_errors.is_empty())
+                        .then(|| fill(t().redact_rule_invalid, &[&redact_errors.join("; ")]))
+                })
+                .map(|message| (message, Instant::now())),
+            redactor,
+            token_pick: None,
+            settings,
+            settings_key,
+            persist: config.persist,
+            source,
+            theme_override: None,
+            line_sep: LineSep::Auto,
+            utf16,
+            relative_numbers,
+            remote: None,
+            remote_follow: None,
             pending_share: false,
             share_message: None,
             show_share_confirm: false,
+            show_redact_warning: false,
             share_is_public: false,
+            share_expiry: ShareExpiry::default(),
+            options: RunOptions::default(),
         }
     }
 
-    fn save_settings_to_disk(&self) {
-        save_settings(
-            self.accent_color,
-            self.text_color,
-            self.text_style,
-            self.border_color,
-            self.status_color,
-        );
+    /// Name the kind of source the lines came from (shown as a status bar badge). Without this,
+    /// [`SourceKind::File`] is assumed when a file path was given and
+    /// [`SourceKind::Sample`] otherwise.
+    pub fn with_source(mut self, source: SourceKind) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// View a remote log, loaded up to `live_file_offset` bytes: live mode follows it from
+    /// there, and `--follow` turns it on. `relative_numbers` when its lines are numbered from
+    /// the first one received rather than the start of the log.
+    pub fn with_remote(mut self, remote: RemoteSource, relative_numbers: bool) -> Self {
+        self.source = SourceKind::Remote;
+        self.relative_numbers = relative_numbers;
+        self.remote = Some(remote);
+        if self.options.follow {
+            self.follow_tail = true;
+            self.list_state.select_last();
+            self.set_live(true);
+        }
+        self
+    }
+
+    /// Live mode can follow the source: a file or a remote log.
+    fn can_follow(&self) -> bool {
+        self.live_file_path.is_some() || self.remote.is_some()
+    }
+
+    /// Turn live mode on or off; for a remote log this starts or stops following it.
+    fn set_live(&mut self, live: bool) {
+        self.live = live;
+        let Some(remote) = self.remote.as_ref().filter(|_| live) else {
+            self.remote_follow = None;
+            return;
+        };
+        match remote.follow(self.live_file_offset) {
+            Ok(follow) => self.remote_follow = Some(follow),
+            Err(e) => {
+                self.live = false;
+                self.set_message(fill(t().remote_follow_failed, &[&e.to_string()]));
+            }
+        }
+    }
+
+    /// Apply command-line options such as `--no-mem`. `--filter` replaces any filter remembered
+    /// for the file; `--follow` (or `--glob`) turns live mode on and jumps to the last line, but
+    /// only when viewing a file.
+    pub fn with_run_options(mut self, options: RunOptions) -> Self {
+        if let Some(preset) = options.theme.as_deref().and_then(ThemePreset::find) {
+            self.theme_override = Some((preset.colors, self.settings.colors()));
+            self.settings.set_colors(preset.colors);
+        }
+        if let Some(filter) = &options.filter {
+            self.filter = filter.clone();
+            self.filter_cursor = self.filter.len();
+            self.on_filter_changed();
+        }
+        self.line_sep = options.line_sep();
+        if let Some(path) = &self.live_file_path {
+            self.line_sep = self.line_sep.detect_file(path);
+            let load = LoadOptions {
+                all: options.all,
+                ..LoadOptions::default()
+            };
+            self.relative_numbers = tail_numbers_relative(path, load);
+        }
+        if (options.follow || options.glob.is_some()) && self.live_file_path.is_some() {
+            self.live = true;
+            self.follow_tail = true;
+            self.list_state.select_last();
+        }
+        self.options = options;
+        self
+    }
+
+    /// Show `message` over the key hint line for `MESSAGE_DURATION` (replacing any current one).
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.status_message = Some((message.into(), Instant::now()));
+    }
+
+    /// Time left before the status message is cleared, if one is shown.
+    fn message_time_left(&self) -> Option<Duration> {
+        self.status_message
+            .as_ref()
+            .map(|(_, at)| MESSAGE_DURATION.saturating_sub(at.elapsed()))
+    }
+
+    fn save_settings_to_disk(&mut self) {
+        if !self.persist {
+            return;
+        }
+        let mut settings = self.settings;
+        // A `--theme` preset is not saved; once a colour is changed by hand, all of them are.
+        if let Some((preset, saved)) = self.theme_override
+            && settings.colors() == preset
+        {
+            settings.set_colors(saved);
+        }
+        if let Err(e) = save_settings(&settings, self.settings_key.as_deref(), &self.filter) {
+            self.set_message(fill(t().settings_invalid, &[&e]));
+        }
     }
 
     fn border_style(&self) -> Style {
-        theme::border_style(self.border_color)
+        theme::border_style(self.settings.border_color)
     }
 
     fn accent_style(&self) -> Style {
-        theme::accent_style(self.accent_color)
+        theme::accent_style(self.settings.accent_color)
     }
 
     fn log_text_style(&self) -> Style {
-        theme::log_text_style(self.text_color, self.text_style)
+        theme::log_text_style(self.settings.text_color, self.settings.text_style)
+    }
+
+    fn gutter_style(&self) -> Style {
+        theme::gutter_style(
+            self.settings.gutter_color,
+            self.settings.text_color,
+            self.settings.text_style,
+        )
+    }
+
+    /// Log text as spans, with recognised tokens coloured when highlighting is on (never in
+    /// `--raw`).
+    fn text_spans(&self, text: String) -> Vec<Span<'static>> {
+        if !self.settings.highlight_tokens || self.options.raw {
+            return vec![Span::raw(text)];
+        }
+        let mut spans = Vec::new();
+        let mut end = 0;
+        for (range, token) in find_tokens(&text) {
+            if range.start > end {
+                spans.push(Span::raw(text[end..range.start].to_string()));
+            }
+            spans.push(Span::styled(
+                text[range.clone()].to_string(),
+                theme::token_style(token),
+            ));
+            end = range.end;
+        }
+        if end < text.len() {
+            spans.push(Span::raw(text[end..].to_string()));
+        }
+        spans
+    }
+
+    /// Dense mode drops the gutter and narrows the selection marker to one column.
+    fn highlight_symbol(&self) -> &'static str {
+        if self.settings.dense { "▸" } else { " ▸ " }
+    }
+
+    /// Column view widths: timestamps and levels padded to the widest of `rows`.
+    fn row_columns(&self, rows: &[usize]) -> Option<(usize, usize)> {
+        (self.settings.columns && !self.options.raw)
+            .then(|| column_widths(rows.iter().map(|&idx| self.all_lines[idx].as_str())))
+    }
+
+    /// Columns left for a row's text beside `gutter`, after the borders and highlight symbol;
+    /// `None` until the log pane has been drawn.
+    fn content_width(&self, gutter: &str) -> Option<usize> {
+        (self.list_area.width > 0).then(|| {
+            (self.list_area.width as usize)
+                .saturating_sub(2 + self.highlight_symbol().width() + gutter.width())
+        })
+    }
+
+    /// Gutter and text of the row showing `all_lines[idx]` as the log pane draws them: columns
+    /// lined up, tabs expanded (left for [`visible_segments`] when invisibles are shown) and
+    /// the middle cut out to fit the pane.
+    fn row_parts(
+        &self,
+        idx: usize,
+        continuation: bool,
+        columns: Option<(usize, usize)>,
+    ) -> (String, String) {
+        let line = &self.all_lines[idx];
+        let gutter = if self.settings.dense {
+            String::new()
+        } else {
+            self.line_prefix
+                .render(self.file_line_start + idx, line, continuation)
+        };
+        let text = columns.map_or_else(|| line.clone(), |widths| columnize(line, widths));
+        let text = if self.settings.show_invisibles || self.options.raw {
+            text
+        } else {
+            expand_tabs(&text, self.settings.tab_width)
+        };
+        let text = match self.content_width(&gutter) {
+            Some(width) if self.settings.truncate_middle && !self.options.raw => {
+                truncate_middle(&text, width)
+            }
+            _ => text,
+        };
+        (gutter, text)
+    }
+
+    /// Find matches in the row showing `all_lines[idx]` that the log pane shows: counted on the
+    /// drawn text, the way [`Self::find_spans`] numbers them, leaving out any that start past
+    /// the right edge.
+    fn visible_find_count(
+        &self,
+        re: &regex::Regex,
+        idx: usize,
+        continuation: bool,
+        columns: Option<(usize, usize)>,
+    ) -> usize {
+        let (gutter, text) = self.row_parts(idx, continuation, columns);
+        let text = if self.settings.show_invisibles || self.options.raw {
+            visible_segments(&text, self.settings.tab_width)
+                .into_iter()
+                .map(|(segment, _)| segment)
+                .collect()
+        } else {
+            text
+        };
+        let width = self.content_width(&gutter);
+        re.find_iter(&text)
+            .filter(|m| width.is_none_or(|width| text[..m.start()].width() < width))
+            .count()
+    }
+
+    /// `spans` of the row showing `file_line` with the find matches in them highlighted, the
+    /// current one more strongly.
+    fn find_spans(&self, spans: Vec<Span<'static>>, file_line: usize) -> Vec<Span<'static>> {
+        let Some((_, re)) = &self.find else {
+            return spans;
+        };
+        let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+        let ranges: Vec<(Range<usize>, Style)> = re
+            .find_iter(&text)
+            .enumerate()
+            .map(|(occurrence, m)| {
+                let style = if self.find_current == Some((file_line, occurrence)) {
+                    theme::find_current_style()
+                } else {
+                    theme::find_match_style()
+                };
+                (m.range(), style)
+            })
+            .collect();
+        restyle_spans(spans, &ranges)
     }
 
     fn status_style(&self) -> Style {
-        theme::status_style(self.status_color)
+        theme::status_style(self.settings.status_color)
+    }
+
+    /// One live-mode poll as the event loop makes it between frames: only while live mode is
+    /// on and the window focused. Returns whether new lines arrived.
+    pub fn poll_live(&mut self) -> bool {
+        if !(self.live && self.focused) {
+            return false;
+        }
+        let arrived = self.poll_live_file();
+        self.idle_polls = if arrived {
+            0
+        } else {
+            self.idle_polls.saturating_add(1)
+        };
+        arrived
     }
 
-    fn poll_live_file(&mut self) {
+    /// Read bytes appended since the last poll; returns whether any arrived.
+    fn poll_live_file(&mut self) -> bool {
+        if self.remote_follow.is_some() {
+            return self.poll_remote();
+        }
         let path = match &self.live_file_path {
             Some(p) => p.clone(),
-            None => return,
+            None => return false,
         };
+        if self.check_glob() {
+            return true;
+        }
         let mut file = match File::open(&path) {
             Ok(f) => f,
-            Err(_) => return,
+            Err(_) => {
+                self.file_unavailable = true;
+                return false;
+            }
         };
+        if self.file_unavailable {
+            // Recreated (log rotation, redeploy): start over from the new file's lines.
+            self.file_unavailable = false;
+            self.reload_file();
+            if self.status_message.is_none() {
+                self.set_message(t().file_reappeared);
+            }
+            return true;
+        }
+        if let Ok(meta) = file.metadata() {
+            self.seen_size = Some(meta.len());
+        }
         let _ = file.seek(SeekFrom::Start(self.live_file_offset));
         let mut buf = Vec::with_capacity(POLL_READ_CAP);
         let mut limited = (&mut file).take(POLL_READ_CAP as u64);
         if limited.read_to_end(&mut buf).is_err() {
-            return;
+            return false;
         }
-        let new_len = self.live_file_offset + buf.len() as u64;
         if buf.is_empty() {
-            return;
+            return false;
         }
-        let s = match String::from_utf8(buf) {
-            Ok(x) => x,
-            Err(_) => return,
+        self.live_file_offset += buf.len() as u64;
+        self.append_bytes(&buf);
+        true
+    }
+
+    /// Take what arrived from the followed remote log; returns whether anything did. An error
+    /// is shown and, once the connection is gone, ends live mode.
+    fn poll_remote(&mut self) -> bool {
+        let Some(follow) = &mut self.remote_follow else {
+            return false;
         };
-        let mut full = std::mem::take(&mut self.live_partial);
-        full.push_str(&s);
-        let lines: Vec<&str> = full.split('\n').collect();
-        if full.ends_with('\n') {
-            self.live_partial.clear();
-            for line in lines {
-                if !line.is_empty() {
-                    self.all_lines.push(line.to_string());
-                }
+        let polled = follow.poll();
+        let finished = follow.is_finished();
+        let arrived = match polled {
+            Ok(bytes) if !bytes.is_empty() => {
+                self.live_file_offset += bytes.len() as u64;
+                self.append_bytes(&bytes);
+                true
             }
-        } else {
-            let (complete, last) = lines.split_at(lines.len().saturating_sub(1));
-            for line in complete {
-                self.all_lines.push(line.to_string());
+            Ok(_) => false,
+            Err(e) => {
+                self.set_message(fill(t().remote_follow_failed, &[&e.to_string()]));
+                false
             }
-            self.live_partial = last.first().copied().unwrap_or("").to_string();
+        };
+        if finished {
+            self.set_live(false);
         }
-        self.live_file_offset = new_len;
-        if self.all_lines.len() > MAX_LINES {
-            let drop = self.all_lines.len() - MAX_LINES;
+        arrived
+    }
+
+    /// Add the lines in `buf`, appended to the source, keeping the selection where it was
+    /// unless following the tail.
+    fn append_bytes(&mut self, buf: &[u8]) {
+        let s = match self.utf16 {
+            Some(big_endian) => decode_appended_utf16(&mut self.live_pending, buf, big_endian),
+            None => decode_appended(&mut self.live_pending, buf),
+        };
+        let mut lines = split_appended_with(&mut self.live_partial, &s, self.line_sep);
+        self.redactor.apply_all(&mut lines);
+        self.live_rate.record(Instant::now(), lines.len());
+        let anchor = if self.follow_tail {
+            None
+        } else {
+            self.selected_file_line()
+        };
+        self.all_lines.extend(lines);
+        if self.all_lines.len() > self.max_lines {
+            let drop = self.all_lines.len() - self.max_lines;
             self.all_lines.drain(0..drop);
             self.file_line_start += drop;
+            self.dropped_lines += drop;
         }
-        self.list_state.select_last();
+        match anchor {
+            Some(file_line) => self.select_file_line(file_line),
+            None => self.list_state.select_last(),
+        }
+    }
+
+    /// With `--glob`, look for a newer matching file (at most every GLOB_SCAN_INTERVAL) and
+    /// switch to its end; returns whether it switched.
+    fn check_glob(&mut self) -> bool {
+        let Some(pattern) = self.options.glob.as_deref() else {
+            return false;
+        };
+        if self.last_glob_scan.elapsed() < GLOB_SCAN_INTERVAL {
+            return false;
+        }
+        self.last_glob_scan = Instant::now();
+        let Ok(Some(newest)) = newest_match(pattern) else {
+            return false;
+        };
+        if self.live_file_path.as_deref() == Some(newest.as_path()) {
+            return false;
+        }
+        self.set_message(fill(t().glob_switched, &[&newest.display().to_string()]));
+        self.live_file_path = Some(newest);
+        self.file_unavailable = false;
+        self.follow_tail = true;
+        self.reload_file();
+        true
+    }
+
+    /// Outside live mode, compare the file size with the last seen one (at most every
+    /// SIZE_CHECK_INTERVAL) and flag growth so the hint line offers a reload.
+    fn check_file_size(&mut self) {
+        if self.live || self.file_changed || self.last_size_check.elapsed() < SIZE_CHECK_INTERVAL {
+            return;
+        }
+        self.last_size_check = Instant::now();
+        let size = self
+            .live_file_path
+            .as_deref()
+            .and_then(|p| std::fs::metadata(p).ok())
+            .map(|m| m.len());
+        if let (Some(size), Some(seen)) = (size, self.seen_size) {
+            self.file_changed = size > seen;
+        }
+    }
+
+    /// Load the file again with the startup options, keeping the selected line when it is
+    /// still in the buffer.
+    fn reload_file(&mut self) {
+        let Some(path) = self.live_file_path.clone() else {
+            return;
+        };
+        // A `--glob` switch can land on a file with other line endings or encoding.
+        self.line_sep = self.options.line_sep().detect_file(&path);
+        self.utf16 = utf16_bom(&path).ok().flatten();
+        let options = LoadOptions {
+            all: self.options.all,
+            raw: self.options.raw,
+            line_sep: self.line_sep,
+            ..LoadOptions::default()
+        };
+        self.relative_numbers = tail_numbers_relative(&path, options);
+        let anchor = self.selected_file_line();
+        let (mut lines, offset, mut file_line_start) =
+            match load_logs_with(Some(path.clone()), options) {
+                Ok((lines, _, offset, start)) => (lines, offset, start),
+                Err(e) => {
+                    self.set_message(fill(t().reload_failed, &[&e.to_string()]));
+                    return;
+                }
+            };
+        if options.all {
+            self.max_lines = self.max_lines.max(lines.len());
+        }
+        if lines.len() > self.max_lines {
+            let drop = lines.len() - self.max_lines;
+            lines.drain(0..drop);
+            file_line_start += drop;
+        }
+        self.redactor.apply_all(&mut lines);
+        self.all_lines = lines;
+        self.file_line_start = file_line_start;
+        self.live_file_offset = offset;
+        self.live_partial.clear();
+        self.live_pending.clear();
+        self.dropped_lines = 0;
+        self.seen_size = std::fs::metadata(&path).ok().map(|m| m.len());
+        self.file_changed = false;
+        match anchor {
+            Some(file_line) if !self.follow_tail => self.select_file_line(file_line),
+            _ => self.list_state.select_last(),
+        }
+    }
+
+    /// Indices into `all_lines` of the rows shown in the log pane: the matches, or every line
+    /// when the filter only tints.
+    fn filtered_rows(&self) -> Vec<usize> {
+        let mut rows = if self.settings.filter_tint {
+            filter_indices(&self.all_lines, &FilterSpec::default(), self.max_lines)
+        } else if self.settings.group_entries {
+            entry_filter_indices(&self.all_lines, &self.filter_spec, self.max_lines)
+        } else {
+            filter_indices(&self.all_lines, &self.filter_spec, self.max_lines)
+        };
+        if self.settings.hide_blank_lines {
+            rows.retain(|&idx| !is_blank(&self.all_lines[idx]));
+        }
+        rows
+    }
+
+    /// Entry id (first line index, see [`entry_starts`]) of each row when grouping entries.
+    fn row_entries(&self, rows: &[usize]) -> Option<Vec<usize>> {
+        if !self.settings.group_entries {
+            return None;
+        }
+        let entries = entry_starts(&self.all_lines);
+        Some(rows.iter().map(|&idx| entries[idx]).collect())
+    }
+
+    /// Move the selection to the previous or next entry when grouping, else by one row.
+    fn step_selection(&mut self, forward: bool) {
+        let rows = self.filtered_rows();
+        match (self.row_entries(&rows), self.list_state.selected()) {
+            (Some(entries), Some(selected)) if !entries.is_empty() => {
+                let selected = selected.min(entries.len() - 1);
+                self.list_state
+                    .select(Some(entry_step(&entries, selected, forward)));
+            }
+            (_, selected) => {
+                // `select_last` (G, live polls) leaves an index past the end until the next
+                // draw; step from the last row instead.
+                let last = rows.len().saturating_sub(1);
+                self.list_state.select(selected.map(|s| s.min(last)));
+                if forward {
+                    self.list_state.select_next();
+                } else {
+                    self.list_state.select_previous();
+                }
+            }
+        }
+    }
+
+    /// Text of the rows currently rendered in the log pane, one per line.
+    fn visible_text(&self) -> String {
+        let filtered = self.filtered_rows();
+        filtered
+            .iter()
+            .skip(self.list_state.offset())
+            .take(self.list_view_height)
+            .map(|&idx| self.all_lines[idx].as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
-    fn filtered_lines_with_indices(&self) -> Vec<(usize, String)> {
-        apply_filter(&self.all_lines, &self.filter, MAX_LINES)
+    /// Text in the filter field.
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// File line number of the selected row, if any.
+    pub fn selected_file_line(&self) -> Option<usize> {
+        let selected = self.list_state.selected()?;
+        let filtered = self.filtered_rows();
+        let idx = filtered.get(selected.min(filtered.len().checked_sub(1)?))?;
+        Some(self.file_line_start + idx)
+    }
+
+    /// Select the row showing `file_line`, or the nearest following row if it is filtered out.
+    fn select_file_line(&mut self, file_line: usize) {
+        let filtered = self.filtered_rows();
+        let pos = filtered
+            .iter()
+            .position(|idx| self.file_line_start + idx >= file_line)
+            .unwrap_or(filtered.len().saturating_sub(1));
+        self.list_state.select(Some(pos));
     }
 
     pub async fn run(mut self, mut terminal: DefaultTerminal) -> color_eyre::Result<()> {
@@ -177,30 +944,52 @@ impl App {
                 self.pending_share = false;
                 let content = self.all_lines.join("\n");
                 let is_public = self.share_is_public;
-                match login::share_log(&content, is_public).await {
+                match login::share_log(&content, is_public, self.share_expiry).await {
                     Ok(res) => {
                         let url = res.view_url.as_deref().unwrap_or(&res.url);
+                        let expires = match &res.expires_at {
+                            Some(at) => format!("{}\n\n", fill(t().share_expires, &[at])),
+                            None => String::new(),
+                        };
                         self.share_message = Some(format!(
-                            "Paylaşıldı!\n\n{}\n\n(Herhangi bir tuşa basın)",
-                            url
+                            "{}\n\n{}\n\n{}{}",
+                            t().shared,
+                            url,
+                            expires,
+                            t().press_any_key
                         ));
                     }
                     Err(e) => {
-                        self.share_message =
-                            Some(format!("Hata: {}\n\n(Herhangi bir tuşa basın)", e));
+                        self.share_message = Some(format!(
+                            "{}\n\n{}",
+                            fill(t().error, &[&e.to_string()]),
+                            t().press_any_key
+                        ));
                     }
                 }
             }
-            terminal.draw(|frame| self.draw(frame))?;
-            if self.live {
-                self.poll_live_file();
+            if self.message_time_left() == Some(Duration::ZERO) {
+                self.status_message = None;
             }
+            if self.focused {
+                self.check_file_size();
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+            self.poll_live();
             self.handle_crossterm_events().await?;
         }
+        if self.settings_key.is_some() {
+            self.save_settings_to_disk();
+        }
         Ok(())
     }
 
-    fn draw(&mut self, frame: &mut Frame) {
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        if area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT {
+            self.draw_too_small(frame);
+            return;
+        }
         if self.show_settings {
             self.draw_settings(frame);
             return;
@@ -209,43 +998,73 @@ impl App {
             self.draw_share_overlay(frame, &msg);
             return;
         }
+        if self.show_redact_warning {
+            self.draw_redact_warning(frame);
+            return;
+        }
         if self.show_share_confirm {
             self.draw_share_confirm(frame);
             return;
         }
-        let area = frame.area();
+        if self.show_help {
+            self.draw_help(frame);
+            return;
+        }
+        if self.palette.is_some() {
+            self.draw_palette(frame);
+            return;
+        }
+        // A hidden hint line gives its row to the log list until a prompt or message needs it.
+        let hint = self.hint_line();
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),
                 Constraint::Min(1),
                 Constraint::Length(1),
-                Constraint::Length(1),
+                Constraint::Length(u16::from(hint.is_some())),
             ])
             .split(area);
 
-        let filtered_with_idx = self.filtered_lines_with_indices();
-        self.ensure_list_selection_in_bounds(filtered_with_idx.len());
+        let rows = self.filtered_rows();
+        self.ensure_list_selection_in_bounds(rows.len());
 
         let border_style = self.border_style();
         let accent = self.accent_style();
         let log_style = self.log_text_style();
+        let gutter_style = self.gutter_style();
+        let tint_style = log_style.patch(theme::match_tint_style());
 
         let filter_label = if self.focus == Focus::Filter {
-            " Filter (focus) "
+            t().filter_title_focused
+        } else {
+            t().filter_title
+        };
+        // A `re:` filter gets an accent border while it compiles and a red one when it doesn't.
+        let (filter_label, filter_border) = if let Some(e) = &self.filter_error {
+            let reason = match e {
+                regex::Error::CompiledTooBig(_) => t().filter_regex_too_big,
+                _ => t().filter_invalid_regex,
+            };
+            (
+                format!("{}— {} ", filter_label, reason),
+                Style::default().fg(Color::Red),
+            )
+        } else if self.filter_spec.regex.is_some() {
+            (filter_label.to_string(), accent)
         } else {
-            " Filter "
+            (filter_label.to_string(), border_style)
         };
         let block = Block::bordered()
             .title(filter_label)
-            .border_style(border_style)
+            .border_style(filter_border)
             .style(if self.focus == Focus::Filter {
                 accent
             } else {
                 Style::default()
             });
         let filter_display = self.filter.to_string();
-        let cursor_pos = self.filter_cursor.min(filter_display.len());
+        let cursor_pos = display_column(&filter_display, self.filter_cursor);
         let para = Paragraph::new(filter_display.as_str())
             .block(block)
             .wrap(Wrap { trim: true });
@@ -257,58 +1076,292 @@ impl App {
             }
         }
 
-        let items: Vec<ListItem> = filtered_with_idx
+        // In split view the matches take the left half and the right half shows the selected
+        // line among its neighbours in the full buffer.
+        let (list_area, context_area) = if self.settings.split_view {
+            let halves = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(chunks[1]);
+            (halves[0], Some(halves[1]))
+        } else {
+            (chunks[1], None)
+        };
+
+        // Rows are fitted to this pane's width (see `row_parts`).
+        self.list_area = list_area;
+        // With entry grouping, continuation rows get a dashed gutter, the rest of the selected
+        // entry is drawn in the accent colour, and tinting covers whole matching entries.
+        let row_entries = self.row_entries(&rows);
+        let entry_of = |pos: usize| row_entries.as_ref().map(|e| e[pos]);
+        let selected_entry = self
+            .list_state
+            .selected()
+            .and_then(|sel| entry_of(sel.min(rows.len().saturating_sub(1))));
+        let row_matches: Vec<bool> = if self.settings.filter_tint {
+            let spec = &self.filter_spec;
+            let direct: Vec<bool> = rows
+                .iter()
+                .map(|&idx| !spec.is_empty() && spec.matches(&self.all_lines[idx]))
+                .collect();
+            let matched_entries: HashSet<usize> = (0..direct.len())
+                .filter(|&pos| direct[pos])
+                .filter_map(entry_of)
+                .collect();
+            direct
+                .iter()
+                .enumerate()
+                .map(|(pos, &hit)| {
+                    hit || entry_of(pos).is_some_and(|e| matched_entries.contains(&e))
+                })
+                .collect()
+        } else {
+            vec![false; rows.len()]
+        };
+        let columns = self.row_columns(&rows);
+        // Only the rows that fit in the pane become list items; the window scrolls just far
+        // enough to keep the selection in view, as `List` itself would.
+        let view_height = list_area.height.saturating_sub(2) as usize;
+        let selected = self.list_state.selected();
+        let mut offset = self.list_state.offset();
+        if let Some(sel) = selected {
+            if sel < offset {
+                offset = sel;
+            } else if sel >= offset + view_height {
+                offset = (sel + 1).saturating_sub(view_height);
+            }
+        }
+        let window = offset..rows.len().min(offset + view_height);
+        let items: Vec<ListItem> = rows[window.clone()]
             .iter()
-            .map(|(idx, s)| {
+            .zip(window)
+            .map(|(&idx, pos)| {
                 let file_line = self.file_line_start + idx;
-                let line = format!("{:>6} │ {}", file_line, s.as_str());
-                ListItem::new(line).style(log_style)
+                let continuation = entry_of(pos).is_some_and(|e| e != idx);
+                let (gutter, text) = self.row_parts(idx, continuation, columns);
+                let line = if self.settings.show_invisibles || self.options.raw {
+                    let segments = visible_segments(&text, self.settings.tab_width)
+                        .into_iter()
+                        .map(|(text, invisible)| {
+                            if invisible {
+                                Span::styled(text, Style::default().add_modifier(Modifier::DIM))
+                            } else {
+                                Span::raw(text)
+                            }
+                        })
+                        .collect();
+                    let mut spans = vec![Span::styled(gutter, gutter_style)];
+                    spans.extend(self.find_spans(segments, file_line));
+                    Line::from(spans)
+                } else {
+                    let mut spans = vec![Span::styled(gutter, gutter_style)];
+                    spans.extend(self.find_spans(self.text_spans(text), file_line));
+                    Line::from(spans)
+                };
+                let style = if row_matches[pos] {
+                    tint_style
+                } else {
+                    log_style
+                };
+                if selected_entry.is_some() && entry_of(pos) == selected_entry {
+                    ListItem::new(line).style(style.patch(accent))
+                } else {
+                    ListItem::new(line).style(style)
+                }
             })
             .collect();
         let list = List::new(items)
-            .block(Block::bordered().title(" Logs ").border_style(border_style))
-            .highlight_style(accent.add_modifier(Modifier::REVERSED))
-            .highlight_symbol(" ▸ ")
+            .block(
+                Block::bordered()
+                    .title(if self.relative_numbers {
+                        t().logs_title_relative
+                    } else {
+                        t().logs_title
+                    })
+                    .border_style(border_style),
+            )
+            // Reset the background so a tinted selected row reverses like any other.
+            .highlight_style(accent.add_modifier(Modifier::REVERSED).bg(Color::Reset))
+            .highlight_symbol(self.highlight_symbol())
             .highlight_spacing(HighlightSpacing::Always);
-        frame.render_stateful_widget(list, chunks[1], &mut self.list_state);
-
-        let live_tag = if self.live { " LIVE " } else { "" };
-        let mem = current_process_memory();
-        let status = format!(
-            " {} / {} lines {} |  RAM: {}  |  Filter: \"{}\"  |  Tab/ /: filter  |  L: live  |  S: settings  |  P: paylaş  |  q/Esc: quit ",
-            filtered_with_idx.len(),
-            self.all_lines.len(),
-            live_tag,
-            mem,
-            if self.filter.is_empty() {
-                "(none)"
-            } else {
-                self.filter.as_str()
-            }
-        );
+        let mut window_state = ListState::default().with_selected(selected.map(|sel| sel - offset));
+        frame.render_stateful_widget(list, list_area, &mut window_state);
+        *self.list_state.offset_mut() = offset;
+        self.list_view_height = view_height;
+        if rows.len() > self.list_view_height {
+            let scroll_len = rows.len() - self.list_view_height;
+            let mut scrollbar_state =
+                ScrollbarState::new(scroll_len).position(self.list_state.offset().min(scroll_len));
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(border_style);
+            frame.render_stateful_widget(
+                scrollbar,
+                list_area.inner(Margin::new(0, 1)),
+                &mut scrollbar_state,
+            );
+        }
+        if let Some(area) = context_area {
+            let selected_idx = self
+                .list_state
+                .selected()
+                .and_then(|sel| rows.get(sel.min(rows.len().checked_sub(1)?)))
+                .copied();
+            self.draw_context(frame, area, selected_idx);
+        }
+
+        let shown = if self.settings.filter_tint && !self.filter.trim().is_empty() {
+            row_matches.iter().filter(|&&hit| hit).count()
+        } else {
+            rows.len()
+        };
+        let can_follow = self.can_follow();
+        let segments: Vec<(StatusField, String)> = self
+            .settings
+            .status_layout
+            .fields()
+            .iter()
+            .map(|&field| {
+                let text = match field {
+                    StatusField::Count => {
+                        format!("{} / {} {}", shown, self.all_lines.len(), t().lines)
+                    }
+                    StatusField::Dropped if self.dropped_lines > 0 => {
+                        fill(t().older_lines_hidden, &[&self.dropped_lines.to_string()])
+                    }
+                    StatusField::Live if self.live && self.file_unavailable => {
+                        t().live_file_unavailable.to_string()
+                    }
+                    StatusField::Live if self.live && self.follow_tail => {
+                        t().status_live.to_string()
+                    }
+                    StatusField::Live if self.live => t().live_not_following.to_string(),
+                    StatusField::Rate if self.live => {
+                        format_rate(self.live_rate.rate(Instant::now()))
+                    }
+                    StatusField::Uptime => {
+                        fill(t().uptime, &[&format_uptime(self.started_at.elapsed())])
+                    }
+                    StatusField::Memory if !self.options.no_mem => {
+                        fill(t().status_memory, &[&current_process_memory()])
+                    }
+                    StatusField::Filter => fill(
+                        t().status_filter,
+                        &[if self.filter.is_empty() {
+                            t().filter_none
+                        } else {
+                            self.filter.as_str()
+                        }],
+                    ),
+                    StatusField::Source => format!("[{}]", self.source.name()),
+                    // L only does something when a file or remote log can be followed.
+                    StatusField::Keys if !can_follow => t().status_keys_no_live.to_string(),
+                    StatusField::Keys => t().status_keys.to_string(),
+                    _ => String::new(),
+                };
+                (field, text)
+            })
+            .collect();
+        let status = fit_status(&segments, chunks[2].width as usize);
         let status_para = Paragraph::new(status).style(self.status_style());
         frame.render_widget(status_para, chunks[2]);
 
-        let bottom_hint = " g: en üst  │  G: en alt  │  P: paylaş ";
-        let hint_para = Paragraph::new(bottom_hint).style(self.status_style());
-        frame.render_widget(hint_para, chunks[3]);
+        if let Some(hint) = hint {
+            frame.render_widget(hint, chunks[3]);
+        }
+    }
+
+    /// Bottom line: an open prompt or status message, otherwise the key hint (or the custom
+    /// `hint_text`). `None` when only the hint would show and the setting hides it.
+    fn hint_line(&self) -> Option<Paragraph<'static>> {
+        if let Some(input) = &self.find_input {
+            return Some(
+                Paragraph::new(format!(" {}: {}", t().find_prompt, input))
+                    .style(self.accent_style()),
+            );
+        }
+        let hint = match (&self.goto_input, &self.token_pick) {
+            (Some(input), _) => {
+                Paragraph::new(format!(" {}: {}", t().goto_line, input)).style(self.accent_style())
+            }
+            (None, Some((tokens, picked))) => {
+                let mut spans = vec![Span::raw(format!(" {}: ", t().pick_token))];
+                for (i, token) in tokens.iter().enumerate() {
+                    if i == *picked {
+                        spans.push(Span::styled(token.clone(), Style::default().reversed()));
+                    } else {
+                        spans.push(Span::raw(token.clone()));
+                    }
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(Span::raw(format!(" {}", t().pick_token_keys)));
+                Paragraph::new(Line::from(spans)).style(self.accent_style())
+            }
+            (None, None) if !self.percent_input.is_empty() => Paragraph::new(format!(
+                " {}",
+                fill(t().goto_percent, &[&self.percent_input])
+            ))
+            .style(self.accent_style()),
+            (None, None) => match &self.status_message {
+                Some((message, _)) => {
+                    Paragraph::new(format!(" {} ", message)).style(self.accent_style())
+                }
+                None if self.file_unavailable && self.live => {
+                    Paragraph::new(format!(" {} ", t().file_unavailable))
+                        .style(Style::default().fg(Color::Red))
+                }
+                None if self.file_changed && !self.live => {
+                    Paragraph::new(format!(" {} ", t().file_changed)).style(self.accent_style())
+                }
+                None if let Some((text, _)) = &self.find => {
+                    Paragraph::new(format!(" {} ", fill(t().find_active, &[text])))
+                        .style(self.accent_style())
+                }
+                None if self.settings.hide_hint_line => return None,
+                None => Paragraph::new(
+                    self.hint_text
+                        .clone()
+                        .unwrap_or_else(|| t().bottom_hint.to_string()),
+                )
+                .style(self.status_style()),
+            },
+        };
+        Some(hint)
+    }
+
+    /// Shown instead of everything else below MIN_TERMINAL_WIDTH × MIN_TERMINAL_HEIGHT, where
+    /// the layout would not fit.
+    fn draw_too_small(&mut self, frame: &mut Frame) {
+        // Nothing of the log pane is on screen, so mouse clicks must not hit its old area.
+        self.list_area = Rect::default();
+        self.list_view_height = 0;
+        let message = fill(
+            t().terminal_too_small,
+            &[
+                &MIN_TERMINAL_WIDTH.to_string(),
+                &MIN_TERMINAL_HEIGHT.to_string(),
+            ],
+        );
+        let para = Paragraph::new(message)
+            .style(self.accent_style())
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(para, frame.area());
     }
 
     fn draw_share_confirm(&mut self, frame: &mut Frame) {
         let area = frame.area();
-        let block_area = centered_rect(area, 56, 16);
+        let block_area = centered_rect(area, 56, 24);
         frame.render_widget(Clear, block_area);
         let visibility = if self.share_is_public {
-            "Public"
+            t().public
         } else {
-            "Private"
+            t().private
         };
-        let text = format!(
-            "Logları Ratlog Web'e paylaşmak istiyor musunuz?\n\n  Görünürlük: {}  (←/→ veya P/U)\n\n  [E]vet (Enter)   [H]ayır (Esc)",
-            visibility
-        );
+        let text = fill(t().share_confirm, &[visibility, self.share_expiry.label()]);
         let block = Block::bordered()
-            .title(" Paylaş ")
+            .title(t().share_title)
             .border_style(self.border_style())
             .style(self.accent_style());
         let inner = block.inner(block_area);
@@ -317,12 +1370,26 @@ impl App {
         frame.render_widget(para, inner);
     }
 
+    fn draw_redact_warning(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let block_area = centered_rect(area, 56, 30);
+        frame.render_widget(Clear, block_area);
+        let block = Block::bordered()
+            .title(t().share_title)
+            .border_style(Style::default().fg(Color::Red))
+            .style(self.accent_style());
+        let inner = block.inner(block_area);
+        frame.render_widget(block, block_area);
+        let para = Paragraph::new(t().share_unredacted).wrap(Wrap { trim: true });
+        frame.render_widget(para, inner);
+    }
+
     fn draw_share_overlay(&mut self, frame: &mut Frame, msg: &str) {
         let area = frame.area();
         let block_area = centered_rect(area, 70, 30);
         frame.render_widget(Clear, block_area);
         let block = Block::bordered()
-            .title(" Log Paylaş ")
+            .title(t().share_result_title)
             .border_style(self.border_style())
             .style(self.accent_style());
         let inner = block.inner(block_area);
@@ -331,29 +1398,70 @@ impl App {
         frame.render_widget(para, inner);
     }
 
-    fn draw_settings(&mut self, frame: &mut Frame) {
+    /// Right pane of the split view: lines of `all_lines` around `selected_idx`, which is
+    /// highlighted and kept in the middle where the buffer allows.
+    fn draw_context(&self, frame: &mut Frame, area: Rect, selected_idx: Option<usize>) {
+        let height = area.height.saturating_sub(2) as usize;
+        let lines: Vec<Line> = match selected_idx {
+            Some(selected) => {
+                let (start, end) = context_window(selected, self.all_lines.len(), height);
+                let gutter_style = self.gutter_style();
+                let highlight = self
+                    .accent_style()
+                    .add_modifier(Modifier::REVERSED)
+                    .bg(Color::Reset);
+                (start..end)
+                    .map(|idx| {
+                        let line = &self.all_lines[idx];
+                        let gutter =
+                            self.line_prefix
+                                .render(self.file_line_start + idx, line, false);
+                        let text = if self.options.raw {
+                            visible_segments(line, self.settings.tab_width)
+                                .into_iter()
+                                .map(|(text, _)| text)
+                                .collect()
+                        } else {
+                            expand_tabs(line, self.settings.tab_width)
+                        };
+                        let mut spans = vec![Span::styled(gutter, gutter_style)];
+                        spans.extend(self.text_spans(text));
+                        let line = Line::from(spans);
+                        if idx == selected {
+                            line.style(highlight)
+                        } else {
+                            line
+                        }
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        let para = Paragraph::new(lines).style(self.log_text_style()).block(
+            Block::bordered()
+                .title(t().context_title)
+                .border_style(self.border_style()),
+        );
+        frame.render_widget(para, area);
+    }
+
+    fn draw_settings(&mut self, frame: &mut Frame) {
         let area = frame.area();
-        let items = [
-            ListItem::new(format!(
-                " Accent (focus/highlight): {}  (←/→) ",
-                self.accent_color.name()
-            )),
-            ListItem::new(format!(" Text colour: {}  (←/→) ", self.text_color.name())),
-            ListItem::new(format!(" Text style: {}  (←/→) ", self.text_style.name())),
-            ListItem::new(format!(
-                " Border colour: {}  (←/→) ",
-                self.border_color.name()
-            )),
-            ListItem::new(format!(
-                " Status bar colour: {}  (←/→) ",
-                self.status_color.name()
-            )),
-            ListItem::new(" Back (Enter or Esc) "),
-        ];
+        let items: Vec<ListItem> = self
+            .settings_items()
+            .into_iter()
+            .map(|item| match item {
+                SettingsItem::Setting(row) => {
+                    ListItem::new(format!(" {}: {}  (←/→) ", row.label, row.value))
+                }
+                SettingsItem::Back => ListItem::new(format!(" {} ", t().settings_back)),
+            })
+            .collect();
+        let len = items.len();
         let list = List::new(items)
             .block(
                 Block::bordered()
-                    .title(" Settings ")
+                    .title(t().settings_title)
                     .style(self.accent_style()),
             )
             .highlight_style(Style::default().reversed())
@@ -362,6 +1470,315 @@ impl App {
         let settings_area = centered_rect(area, 56, 50);
         frame.render_widget(Clear, settings_area);
         frame.render_stateful_widget(list, settings_area, &mut self.settings_list_state);
+        // More rows than fit: the list scrolls with the selection, the scrollbar shows where.
+        let view_height = settings_area.height.saturating_sub(2) as usize;
+        if len > view_height {
+            let scroll_len = len - view_height;
+            let mut scrollbar_state = ScrollbarState::new(scroll_len)
+                .position(self.settings_list_state.offset().min(scroll_len));
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(self.accent_style());
+            frame.render_stateful_widget(
+                scrollbar,
+                settings_area.inner(Margin::new(0, 1)),
+                &mut scrollbar_state,
+            );
+        }
+    }
+
+    /// Everything listed in the settings panel, top to bottom.
+    fn settings_items(&self) -> Vec<SettingsItem> {
+        let mut items: Vec<SettingsItem> = self
+            .setting_rows()
+            .into_iter()
+            .map(SettingsItem::Setting)
+            .collect();
+        items.push(SettingsItem::Back);
+        items
+    }
+
+    /// Rows of the settings panel in order; the "Back" row follows them.
+    fn setting_rows(&self) -> Vec<SettingRow> {
+        let s = &self.settings;
+        let pick = |on: bool, yes: &'static str, no: &'static str| if on { yes } else { no };
+        vec![
+            SettingRow::new(
+                t().settings_accent,
+                s.accent_color.name(),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.accent_color = cycle(AccentColor::all(), s.accent_color, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_text_color,
+                s.text_color.name(),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.text_color = cycle(TextColor::all(), s.text_color, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_text_style,
+                s.text_style.name(),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.text_style = cycle(TextStyle::all(), s.text_style, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_border,
+                s.border_color.name(),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.border_color = cycle(BorderColor::all(), s.border_color, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_status,
+                s.status_color.name(),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.status_color = cycle(StatusColor::all(), s.status_color, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_gutter,
+                s.gutter_color.name(),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.gutter_color = cycle(GutterColor::all(), s.gutter_color, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_tab_width,
+                s.tab_width.to_string(),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.tab_width = cycle(&TAB_WIDTHS, s.tab_width, forward);
+                },
+            ),
+            SettingRow::new(t().settings_language, s.language.name(), |app, forward| {
+                let s = &mut app.settings;
+                s.language = cycle(Language::all(), s.language, forward);
+                i18n::set_language(s.language);
+            }),
+            SettingRow::new(
+                t().settings_filter_select,
+                pick(
+                    s.filter_select_last,
+                    t().filter_select_last,
+                    t().filter_select_current,
+                ),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.filter_select_last = cycle(&[false, true], s.filter_select_last, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_long_lines,
+                pick(
+                    s.truncate_middle,
+                    t().long_lines_middle,
+                    t().long_lines_clip,
+                ),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.truncate_middle = cycle(&[false, true], s.truncate_middle, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_max_poll,
+                format_millis(s.max_poll_ms),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.max_poll_ms = cycle(&POLL_INTERVALS_MS, s.max_poll_ms, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_status_fields,
+                status_layout_label(s.status_layout),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.status_layout = cycle(StatusLayout::presets(), s.status_layout, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_filter_mode,
+                pick(s.filter_tint, t().filter_mode_tint, t().filter_mode_hide),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.filter_tint = cycle(&[false, true], s.filter_tint, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_filter_scope,
+                pick(
+                    s.filter_message_only,
+                    t().filter_scope_message,
+                    t().filter_scope_line,
+                ),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.filter_message_only = cycle(&[false, true], s.filter_message_only, forward);
+                    app.filter_spec.message_only = s.filter_message_only;
+                },
+            ),
+            SettingRow::new(
+                t().settings_group_entries,
+                pick(s.group_entries, t().group_entries_on, t().group_entries_off),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.group_entries = cycle(&[false, true], s.group_entries, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_per_file,
+                per_file_label(s.per_file),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.per_file = cycle(PerFileSettings::all(), s.per_file, forward);
+                    app.settings_key = app
+                        .live_file_path
+                        .as_deref()
+                        .and_then(|p| settings_key(p, s.per_file));
+                },
+            ),
+            SettingRow::new(
+                t().settings_hint_line,
+                pick(s.hide_hint_line, t().hint_line_hidden, t().hint_line_shown),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.hide_hint_line = cycle(&[false, true], s.hide_hint_line, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_blank_lines,
+                pick(
+                    s.hide_blank_lines,
+                    t().blank_lines_hidden,
+                    t().blank_lines_shown,
+                ),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.hide_blank_lines = cycle(&[false, true], s.hide_blank_lines, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_quit_keys,
+                pick(
+                    s.quit_requires_modifier,
+                    t().quit_keys_ctrl,
+                    t().quit_keys_any,
+                ),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.quit_requires_modifier =
+                        cycle(&[false, true], s.quit_requires_modifier, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_highlight,
+                pick(s.highlight_tokens, t().highlight_on, t().highlight_off),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.highlight_tokens = cycle(&[false, true], s.highlight_tokens, forward);
+                },
+            ),
+            SettingRow::new(
+                t().settings_redact_warning,
+                pick(
+                    s.skip_redact_warning,
+                    t().redact_warning_off,
+                    t().redact_warning_on,
+                ),
+                |app, forward| {
+                    let s = &mut app.settings;
+                    s.skip_redact_warning = cycle(&[false, true], s.skip_redact_warning, forward);
+                },
+            ),
+        ]
+    }
+
+    fn draw_help(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let heading = self.accent_style().add_modifier(Modifier::BOLD);
+        let items: Vec<ListItem> = keymap::help_rows()
+            .into_iter()
+            .map(|(keys, desc)| {
+                if keys.is_empty() {
+                    ListItem::new(desc).style(heading)
+                } else {
+                    ListItem::new(format!("{:<18} {}", keys, desc))
+                }
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::bordered()
+                    .title(t().help_title)
+                    .border_style(self.border_style()),
+            )
+            .highlight_style(Style::default().reversed())
+            .highlight_spacing(HighlightSpacing::Always);
+        let help_area = centered_rect(area, 60, 70);
+        frame.render_widget(Clear, help_area);
+        frame.render_stateful_widget(list, help_area, &mut self.help_list_state);
+    }
+
+    fn draw_palette(&mut self, frame: &mut Frame) {
+        let query = self.palette.clone().unwrap_or_default();
+        let palette_area = centered_rect(frame.area(), 60, 50);
+        frame.render_widget(Clear, palette_area);
+        let block = Block::bordered()
+            .title(t().palette_title)
+            .border_style(self.border_style())
+            .style(self.accent_style());
+        let inner = block.inner(palette_area);
+        frame.render_widget(block, palette_area);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(inner);
+        let prompt = format!(" > {}", query);
+        let cursor_x = rows[0].x + prompt.width() as u16;
+        frame.render_widget(Paragraph::new(prompt), rows[0]);
+        if cursor_x < rows[0].x + rows[0].width {
+            frame.set_cursor_position((cursor_x, rows[0].y));
+        }
+        let matches = self.palette_matches();
+        if matches.is_empty() {
+            let none = Paragraph::new(format!("   {}", t().palette_no_match))
+                .style(Style::default().add_modifier(Modifier::DIM));
+            frame.render_widget(none, rows[1]);
+            return;
+        }
+        let items: Vec<ListItem> = matches
+            .iter()
+            .map(|b| {
+                let keys = keymap::binding_label(b);
+                ListItem::new(format!("{:<18} {}", keys, b.action.description()))
+            })
+            .collect();
+        let list = List::new(items)
+            .highlight_style(Style::default().reversed())
+            .highlight_symbol(" ▸ ")
+            .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(list, rows[1], &mut self.palette_list_state);
+    }
+
+    /// Palette entries whose keys or description match the query, read like the log filter.
+    fn palette_matches(&self) -> Vec<&'static keymap::Binding> {
+        let spec = FilterSpec::parse(self.palette.as_deref().unwrap_or(""));
+        keymap::palette_bindings()
+            .into_iter()
+            .filter(|b| {
+                let row = format!("{} {}", keymap::binding_label(b), b.action.description());
+                spec.matches(&row)
+            })
+            .collect()
     }
 
     fn ensure_list_selection_in_bounds(&mut self, len: usize) {
@@ -380,41 +1797,139 @@ impl App {
     }
 
     async fn handle_crossterm_events(&mut self) -> color_eyre::Result<()> {
-        let next_event = self.event_stream.next().fuse();
-        if self.live {
-            tokio::select! {
-                event = next_event => {
-                    if let Some(Ok(evt)) = event {
-                        match evt {
-                            Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
-                            Event::Resize(_, _) => {}
-                            _ => {}
-                        }
-                    }
-                }
-                _ = tokio::time::sleep(Duration::from_millis(400)) => {}
-            }
-        } else {
-            let event = next_event.await;
-            if let Some(Ok(evt)) = event {
-                match evt {
-                    Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
-                    Event::Resize(_, _) => {}
-                    _ => {}
+        // Wake up for the next live poll, file size check or to clear the status message,
+        // whichever is first.
+        // While the terminal is unfocused there is no polling, so nothing redraws until an event.
+        let live_poll = (self.live && self.focused)
+            .then(|| poll_interval(self.idle_polls, self.settings.max_poll_ms));
+        let size_check =
+            (!self.live && self.focused && !self.file_changed && self.live_file_path.is_some())
+                .then(|| SIZE_CHECK_INTERVAL.saturating_sub(self.last_size_check.elapsed()));
+        // The uptime field counts seconds; redraw when the next one starts.
+        let uptime_tick = (self.focused
+            && self
+                .settings
+                .status_layout
+                .fields()
+                .contains(&StatusField::Uptime))
+        .then(|| {
+            Duration::from_secs(1).saturating_sub(Duration::from_nanos(
+                self.started_at.elapsed().subsec_nanos().into(),
+            ))
+        });
+        let wake_after = [live_poll, self.message_time_left(), size_check, uptime_tick]
+            .into_iter()
+            .flatten()
+            .min();
+        let events = self.event_stream.get_or_insert_with(EventStream::default);
+        let next_event = events.next().fuse();
+        let event = match wake_after {
+            Some(wait) => tokio::select! {
+                event = next_event => event,
+                _ = tokio::time::sleep(wait) => None,
+            },
+            None => next_event.await,
+        };
+        if let Some(Ok(evt)) = event {
+            match evt {
+                Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
+                Event::Mouse(mouse) => self.on_mouse_event(mouse),
+                Event::FocusLost => self.focused = false,
+                Event::FocusGained => {
+                    self.focused = true;
+                    // Catch up right away instead of waiting out the idle backoff.
+                    self.idle_polls = 0;
                 }
+                Event::Resize(_, _) => {}
+                _ => {}
             }
         }
         Ok(())
     }
 
-    fn on_key_event(&mut self, key: KeyEvent) {
+    /// Mouse input (only reported with `--mouse`): click or drag on the log pane scrollbar to
+    /// jump through the list, wheel to move the selection.
+    fn on_mouse_event(&mut self, mouse: MouseEvent) {
+        if self.show_settings
+            || self.show_help
+            || self.show_share_confirm
+            || self.show_redact_warning
+            || self.palette.is_some()
+            || self.share_message.is_some()
+        {
+            return;
+        }
+        let area = self.list_area;
+        let track_top = area.y + 1;
+        let track_height = area.height.saturating_sub(2);
+        let on_scrollbar = area.width > 0
+            && mouse.column == area.x + area.width - 1
+            && (track_top..track_top + track_height).contains(&mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if on_scrollbar => {
+                self.scrollbar_drag = true;
+                self.follow_tail = false;
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.scrollbar_drag => {}
+            MouseEventKind::Up(MouseButton::Left) => {
+                self.scrollbar_drag = false;
+                return;
+            }
+            MouseEventKind::ScrollUp => {
+                self.follow_tail = false;
+                self.step_selection(false);
+                return;
+            }
+            MouseEventKind::ScrollDown => {
+                self.follow_tail = false;
+                self.step_selection(true);
+                return;
+            }
+            _ => return,
+        }
+        let len = self.filtered_rows().len();
+        if len > 0 {
+            let index = scrollbar_index(mouse.row, track_top, track_height, len);
+            self.list_state.select(Some(index));
+        }
+    }
+
+    /// Handle one key press, as the event loop does for each one read from the terminal.
+    pub fn on_key_event(&mut self, key: KeyEvent) {
         if self.share_message.is_some() {
             self.share_message = None;
             return;
         }
+        if self.show_redact_warning {
+            match key.code {
+                KeyCode::Enter
+                | KeyCode::Char('e')
+                | KeyCode::Char('E')
+                | KeyCode::Char('y')
+                | KeyCode::Char('Y') => {
+                    self.show_redact_warning = false;
+                    self.show_share_confirm = true;
+                }
+                KeyCode::Esc
+                | KeyCode::Char('q')
+                | KeyCode::Char('Q')
+                | KeyCode::Char('h')
+                | KeyCode::Char('H')
+                | KeyCode::Char('n')
+                | KeyCode::Char('N') => {
+                    self.show_redact_warning = false;
+                }
+                _ => {}
+            }
+            return;
+        }
         if self.show_share_confirm {
             match key.code {
-                KeyCode::Enter | KeyCode::Char('e') | KeyCode::Char('E') => {
+                KeyCode::Enter
+                | KeyCode::Char('e')
+                | KeyCode::Char('E')
+                | KeyCode::Char('y')
+                | KeyCode::Char('Y') => {
                     self.show_share_confirm = false;
                     self.pending_share = true;
                 }
@@ -424,11 +1939,26 @@ impl App {
                 KeyCode::Right | KeyCode::Char('u') | KeyCode::Char('U') => {
                     self.share_is_public = true;
                 }
+                KeyCode::Up | KeyCode::Down => {
+                    let all = ShareExpiry::all();
+                    let idx = all
+                        .iter()
+                        .position(|&e| e == self.share_expiry)
+                        .unwrap_or(0);
+                    let next = if key.code == KeyCode::Down {
+                        (idx + 1) % all.len()
+                    } else {
+                        (idx + all.len() - 1) % all.len()
+                    };
+                    self.share_expiry = all[next];
+                }
                 KeyCode::Esc
                 | KeyCode::Char('q')
                 | KeyCode::Char('Q')
                 | KeyCode::Char('h')
-                | KeyCode::Char('H') => {
+                | KeyCode::Char('H')
+                | KeyCode::Char('n')
+                | KeyCode::Char('N') => {
                     self.show_share_confirm = false;
                 }
                 _ => {}
@@ -439,17 +1969,31 @@ impl App {
             self.on_key_settings(key);
             return;
         }
-        match (key.modifiers, key.code) {
-            (_, KeyCode::Char('q'))
-            | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => {
-                self.quit();
-                return;
-            }
-            (_, KeyCode::Esc) if self.focus != Focus::Filter => {
-                self.quit();
-                return;
-            }
-            _ => {}
+        if self.show_help {
+            self.on_key_help(key);
+            return;
+        }
+        if self.palette.is_some() {
+            self.on_key_palette(key);
+            return;
+        }
+        if self.goto_input.is_some() {
+            self.on_key_goto(key);
+            return;
+        }
+        if self.token_pick.is_some() {
+            self.on_key_token_pick(key);
+            return;
+        }
+        if self.find_input.is_some() {
+            self.on_key_find(key);
+            return;
+        }
+        if keymap::action_for(GLOBAL_BINDINGS, &key) == Some(Action::Quit)
+            && (!self.settings.quit_requires_modifier || keymap::has_modifier(&key))
+        {
+            self.quit();
+            return;
         }
 
         if self.focus == Focus::Filter {
@@ -460,253 +2004,652 @@ impl App {
     }
 
     fn on_key_settings(&mut self, key: KeyEvent) {
-        const SETTINGS_LEN: usize = 6;
-        let cycle_next = |current: usize, len: usize| (current + 1) % len;
-        let cycle_prev = |current: usize, len: usize| (current + len - 1) % len;
-        match (key.modifiers, key.code) {
-            (_, KeyCode::Esc) => {
+        let mut items = self.settings_items();
+        let len = items.len();
+        let selected = self
+            .settings_list_state
+            .selected()
+            .unwrap_or(0)
+            .min(len - 1);
+        let item = items.swap_remove(selected);
+        match (key.modifiers, key.code, item) {
+            (_, KeyCode::Esc, _) | (_, KeyCode::Enter, SettingsItem::Back) => {
                 self.show_settings = false;
             }
-            (_, KeyCode::Enter) => {
-                let i = self.settings_list_state.selected().unwrap_or(0);
-                if i == 5 {
-                    self.show_settings = false;
-                } else {
-                    match i {
-                        0 => {
-                            let opts = AccentColor::all();
-                            let idx = opts
-                                .iter()
-                                .position(|&c| c == self.accent_color)
-                                .unwrap_or(0);
-                            self.accent_color = opts[cycle_next(idx, opts.len())];
-                        }
-                        1 => {
-                            let opts = TextColor::all();
-                            let idx = opts.iter().position(|&c| c == self.text_color).unwrap_or(0);
-                            self.text_color = opts[cycle_next(idx, opts.len())];
-                        }
-                        2 => {
-                            let opts = TextStyle::all();
-                            let idx = opts.iter().position(|&c| c == self.text_style).unwrap_or(0);
-                            self.text_style = opts[cycle_next(idx, opts.len())];
-                        }
-                        3 => {
-                            let opts = BorderColor::all();
-                            let idx = opts
-                                .iter()
-                                .position(|&c| c == self.border_color)
-                                .unwrap_or(0);
-                            self.border_color = opts[cycle_next(idx, opts.len())];
-                        }
-                        4 => {
-                            let opts = StatusColor::all();
-                            let idx = opts
-                                .iter()
-                                .position(|&c| c == self.status_color)
-                                .unwrap_or(0);
-                            self.status_color = opts[cycle_next(idx, opts.len())];
-                        }
-                        _ => {}
-                    }
-                    if (0..=4).contains(&i) {
-                        self.save_settings_to_disk();
-                    }
-                }
+            (_, KeyCode::Enter | KeyCode::Right, SettingsItem::Setting(row)) => {
+                self.change_setting(&row, true);
             }
-            (_, KeyCode::Up | KeyCode::Char('k')) => {
-                self.settings_list_state.select_previous();
-                if self.settings_list_state.selected().is_none() {
-                    self.settings_list_state.select(Some(SETTINGS_LEN - 1));
-                }
-            }
-            (_, KeyCode::Down | KeyCode::Char('j')) => {
-                self.settings_list_state.select_next();
-                if self.settings_list_state.selected().unwrap_or(0) >= SETTINGS_LEN {
-                    self.settings_list_state.select(Some(0));
-                }
-            }
-            (_, KeyCode::Left) => {
-                let i = self.settings_list_state.selected().unwrap_or(0);
-                match i {
-                    0 => {
-                        let opts = AccentColor::all();
-                        let idx = opts
-                            .iter()
-                            .position(|&c| c == self.accent_color)
-                            .unwrap_or(0);
-                        self.accent_color = opts[cycle_prev(idx, opts.len())];
-                    }
-                    1 => {
-                        let opts = TextColor::all();
-                        let idx = opts.iter().position(|&c| c == self.text_color).unwrap_or(0);
-                        self.text_color = opts[cycle_prev(idx, opts.len())];
-                    }
-                    2 => {
-                        let opts = TextStyle::all();
-                        let idx = opts.iter().position(|&c| c == self.text_style).unwrap_or(0);
-                        self.text_style = opts[cycle_prev(idx, opts.len())];
-                    }
-                    3 => {
-                        let opts = BorderColor::all();
-                        let idx = opts
-                            .iter()
-                            .position(|&c| c == self.border_color)
-                            .unwrap_or(0);
-                        self.border_color = opts[cycle_prev(idx, opts.len())];
-                    }
-                    4 => {
-                        let opts = StatusColor::all();
-                        let idx = opts
-                            .iter()
-                            .position(|&c| c == self.status_color)
-                            .unwrap_or(0);
-                        self.status_color = opts[cycle_prev(idx, opts.len())];
-                    }
-                    _ => {}
-                }
-                if (0..=4).contains(&i) {
-                    self.save_settings_to_disk();
-                }
+            (_, KeyCode::Left, SettingsItem::Setting(row)) => self.change_setting(&row, false),
+            (_, KeyCode::Up | KeyCode::Char('k'), _) => {
+                self.settings_list_state
+                    .select(Some((selected + len - 1) % len));
             }
-            (_, KeyCode::Right) => {
-                let i = self.settings_list_state.selected().unwrap_or(0);
-                match i {
-                    0 => {
-                        let opts = AccentColor::all();
-                        let idx = opts
-                            .iter()
-                            .position(|&c| c == self.accent_color)
-                            .unwrap_or(0);
-                        self.accent_color = opts[cycle_next(idx, opts.len())];
-                    }
-                    1 => {
-                        let opts = TextColor::all();
-                        let idx = opts.iter().position(|&c| c == self.text_color).unwrap_or(0);
-                        self.text_color = opts[cycle_next(idx, opts.len())];
-                    }
-                    2 => {
-                        let opts = TextStyle::all();
-                        let idx = opts.iter().position(|&c| c == self.text_style).unwrap_or(0);
-                        self.text_style = opts[cycle_next(idx, opts.len())];
-                    }
-                    3 => {
-                        let opts = BorderColor::all();
-                        let idx = opts
-                            .iter()
-                            .position(|&c| c == self.border_color)
-                            .unwrap_or(0);
-                        self.border_color = opts[cycle_next(idx, opts.len())];
-                    }
-                    4 => {
-                        let opts = StatusColor::all();
-                        let idx = opts
-                            .iter()
-                            .position(|&c| c == self.status_color)
-                            .unwrap_or(0);
-                        self.status_color = opts[cycle_next(idx, opts.len())];
-                    }
-                    _ => {}
-                }
-                if (0..=4).contains(&i) {
-                    self.save_settings_to_disk();
-                }
+            (_, KeyCode::Down | KeyCode::Char('j'), _) => {
+                self.settings_list_state.select(Some((selected + 1) % len));
             }
             _ => {}
         }
     }
 
+    /// Move `row` to its next (or previous) option and persist.
+    fn change_setting(&mut self, row: &SettingRow, forward: bool) {
+        (row.change)(self, forward);
+        self.save_settings_to_disk();
+    }
+
     fn on_key_filter(&mut self, key: KeyEvent) {
         match (key.modifiers, key.code) {
             (_, KeyCode::Esc) => {
                 if self.filter.is_empty() {
-                    self.quit();
+                    if !self.settings.quit_requires_modifier {
+                        self.quit();
+                    }
                 } else {
                     self.filter.clear();
                     self.filter_cursor = 0;
+                    self.on_filter_changed();
                 }
             }
             (_, KeyCode::Enter) | (_, KeyCode::Tab) => {
                 self.focus = Focus::LogList;
             }
-            (_, KeyCode::Backspace) => {
-                if self.filter_cursor > 0 {
-                    self.filter_cursor -= 1;
-                    self.filter.remove(self.filter_cursor);
-                }
-            }
-            (_, KeyCode::Char(c)) if !c.is_control() => {
-                self.filter.insert(self.filter_cursor, c);
-                self.filter_cursor += 1;
+            (_, KeyCode::Backspace) if self.filter_cursor > 0 => {
+                let start = prev_grapheme(&self.filter, self.filter_cursor);
+                self.filter.drain(start..self.filter_cursor);
+                self.filter_cursor = start;
+                self.on_filter_changed();
             }
-            (KeyModifiers::CONTROL, KeyCode::Char('a')) => {
+            (KeyModifiers::CONTROL, KeyCode::Char('a')) | (_, KeyCode::Home) => {
                 self.filter_cursor = 0;
             }
-            (KeyModifiers::CONTROL, KeyCode::Char('e')) => {
+            (KeyModifiers::CONTROL, KeyCode::Char('e')) | (_, KeyCode::End) => {
                 self.filter_cursor = self.filter.len();
             }
+            (KeyModifiers::CONTROL, KeyCode::Char('w')) if self.filter_cursor > 0 => {
+                self.filter_cursor = delete_word_before(&mut self.filter, self.filter_cursor);
+                self.on_filter_changed();
+            }
+            (KeyModifiers::CONTROL, KeyCode::Char('u')) if self.filter_cursor > 0 => {
+                self.filter.drain(..self.filter_cursor);
+                self.filter_cursor = 0;
+                self.on_filter_changed();
+            }
+            (KeyModifiers::ALT, KeyCode::Left) | (KeyModifiers::ALT, KeyCode::Char('b')) => {
+                self.filter_cursor = prev_word_start(&self.filter, self.filter_cursor);
+            }
+            (KeyModifiers::ALT, KeyCode::Right) | (KeyModifiers::ALT, KeyCode::Char('f')) => {
+                self.filter_cursor = next_word_end(&self.filter, self.filter_cursor);
+            }
+            (mods, KeyCode::Char(c))
+                if !c.is_control() && !mods.contains(KeyModifiers::CONTROL) =>
+            {
+                if self.filter.chars().count() >= self.settings.max_filter_len {
+                    let max = self.settings.max_filter_len.to_string();
+                    self.set_message(fill(t().filter_too_long, &[&max]));
+                    return;
+                }
+                self.filter.insert(self.filter_cursor, c);
+                self.filter_cursor += c.len_utf8();
+                self.on_filter_changed();
+            }
             (_, KeyCode::Left) => {
-                self.filter_cursor = self.filter_cursor.saturating_sub(1);
+                self.filter_cursor = prev_grapheme(&self.filter, self.filter_cursor);
             }
             (_, KeyCode::Right) => {
-                self.filter_cursor = (self.filter_cursor + 1).min(self.filter.len());
+                self.filter_cursor = next_grapheme(&self.filter, self.filter_cursor);
             }
             _ => {}
         }
     }
 
+    /// Re-parse the filter (compiling a `re:` pattern once per edit, not per frame) and apply
+    /// the *Filter selects* setting.
+    fn on_filter_changed(&mut self) {
+        if truncate_chars(&mut self.filter, self.settings.max_filter_len) {
+            self.filter_cursor = self.filter_cursor.min(self.filter.len());
+            let max = self.settings.max_filter_len.to_string();
+            self.set_message(fill(t().filter_too_long, &[&max]));
+        }
+        (self.filter_spec, self.filter_error) =
+            parse_filter(&self.filter, self.settings.filter_message_only);
+        if !self.settings.filter_select_last {
+            return;
+        }
+        if self.settings.filter_tint {
+            let filtered = self.filtered_rows();
+            let spec = &self.filter_spec;
+            if let Some(pos) = filtered
+                .iter()
+                .rposition(|&idx| !spec.is_empty() && spec.matches(&self.all_lines[idx]))
+            {
+                self.list_state.select(Some(pos));
+            }
+        } else {
+            self.list_state.select_last();
+        }
+    }
+
+    /// Remember the selected line before a jump, keeping at most JUMP_LIST_LEN of them.
+    fn record_jump(&mut self) {
+        let Some(file_line) = self.selected_file_line() else {
+            return;
+        };
+        if self.jump_back.last() != Some(&file_line) {
+            self.jump_back.push(file_line);
+        }
+        if self.jump_back.len() > JUMP_LIST_LEN {
+            self.jump_back.remove(0);
+        }
+        self.jump_forward.clear();
+    }
+
+    /// Go back to the line before the last jump (or forward again), like an editor's jump list.
+    fn jump(&mut self, back: bool) {
+        let current = self.selected_file_line();
+        let (from, to) = if back {
+            (&mut self.jump_back, &mut self.jump_forward)
+        } else {
+            (&mut self.jump_forward, &mut self.jump_back)
+        };
+        let Some(target) = from.pop() else {
+            return;
+        };
+        to.extend(current);
+        self.select_file_line(target);
+    }
+
     fn on_key_log_list(&mut self, key: KeyEvent) {
-        match (key.modifiers, key.code) {
-            (_, KeyCode::Char('s') | KeyCode::Char('S')) => {
+        if let KeyCode::Char(c) = key.code
+            && c.is_ascii_digit()
+        {
+            if self.percent_input.len() < 3 {
+                self.percent_input.push(c);
+            }
+            return;
+        }
+        let percent = std::mem::take(&mut self.percent_input);
+        if !percent.is_empty() && key.code == KeyCode::Esc {
+            return;
+        }
+        let Some(action) = keymap::action_for(LOG_LIST_BINDINGS, &key) else {
+            return;
+        };
+        self.run_action(action, &percent);
+    }
+
+    /// Carry out `action` from a key or the command palette; `percent` is the number typed
+    /// before `%`.
+    fn run_action(&mut self, action: Action, percent: &str) {
+        if matches!(action, Action::First | Action::Last)
+            || (action == Action::GotoPercent && !percent.is_empty())
+        {
+            self.record_jump();
+        }
+        match action {
+            // Esc first dismisses an active find.
+            Action::Quit if self.find.is_some() => {
+                self.find = None;
+                self.find_current = None;
+            }
+            Action::Quit => {
+                if !self.settings.quit_requires_modifier {
+                    self.quit();
+                }
+            }
+            Action::FocusFilter => {
+                self.focus = Focus::Filter;
+            }
+            Action::OpenSettings => {
                 self.show_settings = true;
                 self.settings_list_state.select(Some(0));
             }
-            (_, KeyCode::Char('p') | KeyCode::Char('P')) => {
+            Action::Share => {
                 if login::load_token().is_none() {
-                    self.share_message = Some(
-                        "Önce giriş yapın: ratlog login\n\n(Herhangi bir tuşa basın)".to_string(),
-                    );
+                    self.share_message =
+                        Some(format!("{}\n\n{}", t().login_first, t().press_any_key));
+                } else if self.redactor.is_empty() && !self.settings.skip_redact_warning {
+                    self.show_redact_warning = true;
                 } else {
                     self.show_share_confirm = true;
                 }
             }
-            (_, KeyCode::Char('/')) | (KeyModifiers::CONTROL, KeyCode::Char('f')) => {
-                self.focus = Focus::Filter;
+            Action::ToggleLive => {
+                if self.can_follow() {
+                    let anchor = self.selected_file_line();
+                    self.set_live(!self.live);
+                    self.idle_polls = 0;
+                    self.live_rate.clear();
+                    if let Some(file_line) = anchor {
+                        self.select_file_line(file_line);
+                    }
+                    // Follow new lines only when already at the bottom, like `tail -f`.
+                    let len = self.filtered_rows().len();
+                    self.follow_tail = self.list_state.selected().is_none_or(|sel| sel + 1 >= len);
+                } else {
+                    self.set_message(fill(t().live_unavailable, &[self.source.name()]));
+                }
             }
-            (_, KeyCode::Tab) => {
-                self.focus = Focus::Filter;
+            Action::Reload => self.reload_file(),
+            // Saved like the panel settings, so the next start looks the same.
+            Action::SplitView => {
+                self.settings.split_view = !self.settings.split_view;
+                self.save_settings_to_disk();
             }
-            (
-                _,
-                KeyCode::Char('l') | KeyCode::Char('L') | KeyCode::Char('f') | KeyCode::Char('F'),
-            ) => {
-                if self.live_file_path.is_some() {
-                    self.live = !self.live;
+            Action::ToggleDense => {
+                self.settings.dense = !self.settings.dense;
+                self.save_settings_to_disk();
+            }
+            Action::ToggleColumns => {
+                self.settings.columns = !self.settings.columns;
+                self.save_settings_to_disk();
+            }
+            Action::ToggleInvisibles => {
+                self.settings.show_invisibles = !self.settings.show_invisibles;
+                self.save_settings_to_disk();
+            }
+            Action::CycleAccent => {
+                let s = &mut self.settings;
+                s.accent_color = cycle(AccentColor::all(), s.accent_color, true);
+                self.save_settings_to_disk();
+                self.set_message(fill(
+                    t().accent_changed,
+                    &[self.settings.accent_color.name()],
+                ));
+            }
+            Action::CopyScreen => {
+                let text = self.visible_text();
+                let message = match copy_to_clipboard(&text) {
+                    Ok(()) => fill(t().copied_lines, &[&text.lines().count().to_string()]),
+                    Err(e) => fill(t().copy_failed, &[&e.to_string()]),
+                };
+                self.set_message(message);
+            }
+            // Only a file has a path; for stdin, sample and remote logs the message says why
+            // nothing was copied.
+            Action::CopyPath => {
+                let message = match &self.live_file_path {
+                    Some(path) => {
+                        let path = std::path::absolute(path).unwrap_or_else(|_| path.clone());
+                        let path = path.display().to_string();
+                        match copy_to_clipboard(&path) {
+                            Ok(()) => fill(t().copied_path, &[&path]),
+                            Err(e) => fill(t().copy_failed, &[&e.to_string()]),
+                        }
+                    }
+                    None => fill(t().no_file_path, &[self.source.name()]),
+                };
+                self.set_message(message);
+            }
+            Action::GotoLine => {
+                self.goto_input = Some(String::new());
+            }
+            Action::GotoPercent => {
+                if let Ok(percent) = percent.parse::<usize>() {
+                    let len = self.filtered_rows().len();
+                    self.list_state.select(Some(percent_index(percent, len)));
+                }
+            }
+            Action::JumpBack => self.jump(true),
+            Action::JumpForward => self.jump(false),
+            Action::PickToken => {
+                let filtered = self.filtered_rows();
+                let line = self
+                    .list_state
+                    .selected()
+                    .and_then(|i| filtered.get(i.min(filtered.len().checked_sub(1)?)))
+                    .map(|&idx| &self.all_lines[idx]);
+                if let Some(line) = line {
+                    let tokens: Vec<String> =
+                        line_tokens(line).into_iter().map(String::from).collect();
+                    if !tokens.is_empty() {
+                        self.token_pick = Some((tokens, 0));
+                    }
                 }
             }
-            (_, KeyCode::Up | KeyCode::Char('k')) => {
-                self.list_state.select_previous();
+            Action::Find => self.find_input = Some(String::new()),
+            Action::FindNext | Action::FindPrevious if self.find.is_none() => {
+                self.find_input = Some(String::new());
             }
-            (_, KeyCode::Down | KeyCode::Char('j')) => {
-                self.list_state.select_next();
+            Action::FindNext => self.find_step(true),
+            Action::FindPrevious => self.find_step(false),
+            Action::Help => {
+                self.show_help = true;
+                self.help_list_state.select(Some(0));
             }
-            (_, KeyCode::PageUp) => {
-                self.list_state.scroll_up_by(10);
+            Action::CommandPalette => {
+                self.palette = Some(String::new());
+                self.palette_list_state.select(Some(0));
             }
-            (_, KeyCode::PageDown) => {
-                self.list_state.scroll_down_by(10);
+            Action::Up => self.step_selection(false),
+            Action::Down => self.step_selection(true),
+            Action::PageUp => self.list_state.scroll_up_by(10),
+            Action::PageDown => self.list_state.scroll_down_by(10),
+            Action::First => self.list_state.select_first(),
+            Action::Last => self.list_state.select_last(),
+        }
+        match action {
+            Action::Last => self.follow_tail = true,
+            Action::Up
+            | Action::Down
+            | Action::PageUp
+            | Action::PageDown
+            | Action::First
+            | Action::GotoPercent
+            | Action::JumpBack
+            | Action::JumpForward
+            | Action::FindNext
+            | Action::FindPrevious => self.follow_tail = false,
+            _ => {}
+        }
+    }
+
+    fn on_key_palette(&mut self, key: KeyEvent) {
+        let Some(query) = self.palette.as_mut() else {
+            return;
+        };
+        match (key.modifiers, key.code) {
+            (_, KeyCode::Esc) | (KeyModifiers::CONTROL, KeyCode::Char('p' | 'P')) => {
+                self.palette = None;
+            }
+            (_, KeyCode::Char(c)) => {
+                query.push(c);
+                self.palette_list_state.select(Some(0));
             }
-            (_, KeyCode::Home) | (_, KeyCode::Char('g')) => {
-                self.list_state.select_first();
+            (_, KeyCode::Backspace) => {
+                query.pop();
+                self.palette_list_state.select(Some(0));
+            }
+            (_, KeyCode::Up) => {
+                let i = self.palette_list_state.selected().unwrap_or(0);
+                self.palette_list_state.select(Some(i.saturating_sub(1)));
+            }
+            (_, KeyCode::Down) => {
+                let last = self.palette_matches().len().saturating_sub(1);
+                let i = self.palette_list_state.selected().unwrap_or(0);
+                self.palette_list_state.select(Some((i + 1).min(last)));
             }
-            (_, KeyCode::End) | (_, KeyCode::Char('G')) => {
-                self.list_state.select_last();
+            (_, KeyCode::Enter) => {
+                let matches = self.palette_matches();
+                let picked = self
+                    .palette_list_state
+                    .selected()
+                    .and_then(|i| matches.get(i))
+                    .map(|b| b.action);
+                self.palette = None;
+                match picked {
+                    // Picking Quit by name is deliberate, so the Ctrl+C-only setting doesn't apply.
+                    Some(Action::Quit) => self.quit(),
+                    Some(action) => self.run_action(action, ""),
+                    None => {}
+                }
             }
             _ => {}
         }
     }
 
+    fn on_key_goto(&mut self, key: KeyEvent) {
+        let Some(input) = self.goto_input.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char(c) if c.is_ascii_digit() => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => {
+                if let Ok(file_line) = input.parse::<usize>() {
+                    self.record_jump();
+                    self.select_file_line(file_line);
+                    self.follow_tail = false;
+                }
+                self.goto_input = None;
+            }
+            KeyCode::Esc => self.goto_input = None,
+            _ => {}
+        }
+    }
+
+    fn on_key_find(&mut self, key: KeyEvent) {
+        let Some(input) = self.find_input.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => {
+                let text = self.find_input.take().unwrap_or_default();
+                self.find = find_pattern(&text).map(|re| (text, re));
+                self.find_current = None;
+                if self.find.is_some() {
+                    self.find_step(true);
+                    self.follow_tail = false;
+                }
+            }
+            KeyCode::Esc => self.find_input = None,
+            _ => {}
+        }
+    }
+
+    /// Select the next (or previous) find match in the shown lines, starting from the selected
+    /// row and wrapping around at the ends like a browser's find in page.
+    fn find_step(&mut self, forward: bool) {
+        let Some((text, re)) = &self.find else {
+            return;
+        };
+        let rows = self.filtered_rows();
+        let columns = self.row_columns(&rows);
+        let entries = self.row_entries(&rows);
+        let counts: Vec<usize> = rows
+            .iter()
+            .enumerate()
+            .map(|(pos, &idx)| {
+                let continuation = entries.as_ref().is_some_and(|e| e[pos] != idx);
+                self.visible_find_count(re, idx, continuation, columns)
+            })
+            .collect();
+        let Some(last) = rows.len().checked_sub(1) else {
+            return;
+        };
+        let selected = self.list_state.selected().unwrap_or(0).min(last);
+        let selected_line = self.file_line_start + rows[selected];
+        // Only step past the current match while it is on the selected row; after moving
+        // elsewhere, the search starts from there.
+        let occurrence = self
+            .find_current
+            .filter(|&(file_line, _)| file_line == selected_line)
+            .map(|(_, occurrence)| occurrence);
+        let len = rows.len();
+        let found = if forward {
+            let next = occurrence.map_or(0, |o| o + 1);
+            if next < counts[selected] {
+                Some((selected, next, false))
+            } else {
+                (1..=len)
+                    .map(|i| (selected + i) % len)
+                    .find(|&row| counts[row] > 0)
+                    .map(|row| (row, 0, row <= selected))
+            }
+        } else {
+            match occurrence {
+                Some(o) if o > 0 => Some((selected, o - 1, false)),
+                None if counts[selected] > 0 => Some((selected, counts[selected] - 1, false)),
+                _ => (1..=len)
+                    .map(|i| (selected + len - i) % len)
+                    .find(|&row| counts[row] > 0)
+                    .map(|row| (row, counts[row] - 1, row >= selected)),
+            }
+        };
+        match found {
+            Some((row, occurrence, wrapped)) => {
+                self.find_current = Some((self.file_line_start + rows[row], occurrence));
+                self.list_state.select(Some(row));
+                if wrapped {
+                    self.set_message(t().find_wrapped);
+                }
+            }
+            None => {
+                let message = fill(t().find_not_found, &[text]);
+                self.set_message(message);
+            }
+        }
+    }
+
+    fn on_key_token_pick(&mut self, key: KeyEvent) {
+        let Some((tokens, picked)) = self.token_pick.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => *picked = picked.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => *picked = (*picked + 1).min(tokens.len() - 1),
+            KeyCode::Home => *picked = 0,
+            KeyCode::End => *picked = tokens.len() - 1,
+            KeyCode::Enter => {
+                let token = tokens[*picked].clone();
+                self.token_pick = None;
+                let anchor = self.selected_file_line();
+                // Escape `|` so a token like `a|b` is matched literally, not as alternatives.
+                self.filter = token.replace('|', r"\|");
+                self.filter_cursor = self.filter.len();
+                self.on_filter_changed();
+                if let Some(file_line) = anchor {
+                    self.select_file_line(file_line);
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.token_pick = None,
+            _ => {}
+        }
+    }
+
+    fn on_key_help(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('q') | KeyCode::Enter => {
+                self.show_help = false;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.help_list_state.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.help_list_state.select_next(),
+            KeyCode::PageUp => self.help_list_state.scroll_up_by(10),
+            KeyCode::PageDown => self.help_list_state.scroll_down_by(10),
+            KeyCode::Home | KeyCode::Char('g') => self.help_list_state.select_first(),
+            KeyCode::End | KeyCode::Char('G') => self.help_list_state.select_last(),
+            _ => {}
+        }
+    }
+
     fn quit(&mut self) {
         self.running = false;
     }
 }
+
+/// One row of the settings panel: its label, the current value and what ←/→ do to it.
+struct SettingRow {
+    label: &'static str,
+    value: String,
+    /// Move the setting to its next (`true`) or previous option; the caller saves.
+    change: fn(&mut App, bool),
+}
+
+impl SettingRow {
+    fn new(label: &'static str, value: impl Into<String>, change: fn(&mut App, bool)) -> Self {
+        SettingRow {
+            label,
+            value: value.into(),
+            change,
+        }
+    }
+}
+
+/// What a line of the settings panel stands for, so keys dispatch on the row itself rather
+/// than on its position.
+enum SettingsItem {
+    Setting(SettingRow),
+    /// Closes the panel.
+    Back,
+}
+
+/// `spans` restyled over the sorted byte `ranges` of their joined text, split where needed.
+fn restyle_spans(
+    spans: Vec<Span<'static>>,
+    ranges: &[(Range<usize>, Style)],
+) -> Vec<Span<'static>> {
+    if ranges.is_empty() {
+        return spans;
+    }
+    let mut out = Vec::new();
+    let mut pos = 0;
+    for span in spans {
+        let end = pos + span.content.len();
+        let mut at = pos;
+        for (range, style) in ranges.iter().filter(|(r, _)| r.start < end && r.end > pos) {
+            let start = range.start.max(pos);
+            if start > at {
+                out.push(Span::styled(
+                    span.content[at - pos..start - pos].to_string(),
+                    span.style,
+                ));
+            }
+            let stop = range.end.min(end);
+            out.push(Span::styled(
+                span.content[start - pos..stop - pos].to_string(),
+                span.style.patch(*style),
+            ));
+            at = stop;
+        }
+        if at < end {
+            out.push(Span::styled(
+                span.content[at - pos..].to_string(),
+                span.style,
+            ));
+        }
+        pos = end;
+    }
+    out
+}
+
+/// The option after (or before) `current` in `opts`, wrapping around.
+fn cycle<T: Copy + PartialEq>(opts: &[T], current: T, forward: bool) -> T {
+    let idx = opts.iter().position(|&c| c == current).unwrap_or(0);
+    let len = opts.len();
+    if forward {
+        opts[(idx + 1) % len]
+    } else {
+        opts[(idx + len - 1) % len]
+    }
+}
+
+fn status_layout_label(layout: StatusLayout) -> &'static str {
+    if layout == StatusLayout::FULL {
+        t().status_layout_full
+    } else if layout == StatusLayout::COMPACT {
+        t().status_layout_compact
+    } else if layout == StatusLayout::MINIMAL {
+        t().status_layout_minimal
+    } else {
+        t().status_layout_custom
+    }
+}
+
+fn per_file_label(mode: PerFileSettings) -> &'static str {
+    match mode {
+        PerFileSettings::Off => t().per_file_off,
+        PerFileSettings::Path => t().per_file_path,
+        PerFileSettings::Name => t().per_file_name,
+    }
+}
+
+/// Parsed filter and the error of its `re:` pattern, if it failed to compile. With
+/// `message_only`, terms skip each line's leading timestamp.
+fn parse_filter(filter: &str, message_only: bool) -> (FilterSpec, Option<regex::Error>) {
+    let (spec, error) = match FilterSpec::try_parse(filter) {
+        Ok(spec) => (spec, None),
+        Err(e) => (FilterSpec::parse(filter), Some(e)),
+    };
+    (
+        FilterSpec {
+            message_only,
+            ..spec
+        },
+        error,
+    )
+}
@@ -2,21 +2,100 @@
 
 use std::path::PathBuf;
 
-use crate::constants::MAX_LINES;
+use crate::constants::{ALL_WARN_BYTES, MAX_LINES};
+use crate::logs::{LineSep, LogSource};
+use crate::remote::RemoteSource;
+use crate::theme::ThemePreset;
+use crate::util::format_bytes;
 
 const VERSION: &str = match option_env!("RATLOG_VERSION") {
     Some(v) => v,
     None => env!("CARGO_PKG_VERSION"),
 };
 
+/// Viewer options given on the command line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    /// `--no-mem`: leave RAM out of the status bar and never query sysinfo.
+    pub no_mem: bool,
+    /// `--follow` / `-f`: start in live mode, like `tail -f`. Ignored without a file or remote log.
+    pub follow: bool,
+    /// `--filter STR`: open with this filter applied; wins over a filter remembered per file.
+    pub filter: Option<String>,
+    /// `--all`: load every line of the file or stdin instead of the last MAX_LINES.
+    pub all: bool,
+    /// `--raw`: no line cutting, tab expansion or middle ellipsis; control characters are always
+    /// shown in caret notation.
+    pub raw: bool,
+    /// `--mouse`: capture the mouse for the scrollbar and wheel (disables terminal selection).
+    pub mouse: bool,
+    /// `--output-json`: print the lines matching `filter` as JSON objects and exit, no TUI.
+    pub output_json: bool,
+    /// `--inline`: draw in the normal screen instead of the alternate one, leaving the last
+    /// frame in the scrollback.
+    pub inline: bool,
+    /// `--cat`: print the last lines (matching `filter`) to stdout and exit, no TUI.
+    pub cat: bool,
+    /// `-n N` / `--lines N`: how many lines `--cat` and `--output-json` load (default MAX_LINES).
+    pub lines: Option<usize>,
+    /// `--line-numbers`: prefix lines printed by `--cat` with their file line number.
+    pub line_numbers: bool,
+    /// `--glob PATTERN`: open the newest file matching PATTERN and, in live mode, switch to a
+    /// newer one when it appears.
+    pub glob: Option<String>,
+    /// `--theme NAME`: colour preset for this session only; saved settings are left as they are.
+    pub theme: Option<String>,
+    /// `--line-sep lf|crlf|cr|auto`: what ends a line; main rejects other values before loading.
+    pub line_sep: Option<String>,
+}
+
+impl RunOptions {
+    /// The `--line-sep` value; `Auto` when none (or an unknown one) was given.
+    pub fn line_sep(&self) -> LineSep {
+        self.line_sep
+            .as_deref()
+            .and_then(LineSep::from_name)
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug)]
 pub enum CliAction {
-    Run(Option<PathBuf>),
-    Login,
+    Run(LogSource, RunOptions),
+    /// `ratlog login [--check]`; `--check` only verifies the saved token.
+    Login {
+        check: bool,
+    },
+    /// `ratlog unshare [URL_OR_ID]`; without an argument, lists recent shares.
+    Unshare(Option<String>),
+    /// `ratlog shares [--json]`: list the user's shared logs.
+    Shares {
+        json: bool,
+    },
+    /// `ratlog config export|import FILE`; `None` when the subcommand is missing or unknown.
+    Config(Option<ConfigAction>),
 }
 
-pub fn print_version() {
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConfigAction {
+    /// Print the effective settings as JSON.
+    Export,
+    /// Replace the settings with a file printed by `export` (`-` reads stdin).
+    Import(String),
+}
+
+const GIT_HASH: Option<&str> = option_env!("RATLOG_GIT_HASH");
+const RUSTC_VERSION: Option<&str> = option_env!("RATLOG_RUSTC_VERSION");
+const BUILD_TARGET: Option<&str> = option_env!("RATLOG_BUILD_TARGET");
+
+/// Print the version; with `verbose`, also the git commit, rustc version and target triple.
+pub fn print_version(verbose: bool) {
     println!("ratlog {}", VERSION);
+    if verbose {
+        println!("commit: {}", GIT_HASH.unwrap_or("unknown"));
+        println!("rustc:  {}", RUSTC_VERSION.unwrap_or("unknown"));
+        println!("target: {}", BUILD_TARGET.unwrap_or("unknown"));
+    }
 }
 
 pub fn print_help() {
@@ -25,29 +104,93 @@ pub fn print_help() {
 
 USAGE:
     ratlog [OPTIONS] [LOG_FILE]
-    ratlog login
+    <command> | ratlog [OPTIONS] -
+    ratlog login [--check]
+    ratlog unshare [URL_OR_ID]
+    ratlog shares [--json]
+    ratlog config export | import FILE
 
 ARGUMENTS:
     LOG_FILE    Log file to open (last {} lines shown). If omitted, sample logs are used.
+                `~` and `$VAR` / `${{VAR}}` are expanded. Use `-` to read from stdin.
+                http(s)://… and ssh://[user@]host[:port]/path are read once (no live mode).
 
 COMMANDS:
-    login       Log in to Ratlog Web (opens browser, saves token for log sharing)
+    login       Log in to Ratlog Web (opens browser, saves token for log sharing); with
+                --check, verify the saved token and print its email (exit 1 if invalid)
+    unshare     Delete a log shared from this machine by URL or id (no argument: list them)
+    shares      List your shared logs on Ratlog Web (--json for machine-readable output)
+    config      `export` prints the effective settings as JSON; `import FILE` replaces them
+                with such a file (`-` reads stdin)
 
 OPTIONS:
     -h, --help      Show this message and exit
-    -V, --version   Show version and exit
+    -V, --version   Show version and exit (add --verbose for commit, rustc and target)
+    -f, --follow    Start with live mode on, like `tail -f` (LOG_FILE or URL)
+    --filter STR    Open with STR as the filter, focus on the log list. Replaces a filter
+                    remembered for the file ("Remember per file" setting)
+    --all           Load the whole file (or stdin) instead of the last {} lines; warns
+                    before loading files over {} into memory
+    --raw           Show lines exactly as stored: no cutting of very long lines, no tab
+                    expansion or middle ellipsis; control characters (including
+                    escape sequences) always appear as ^X
+    --output-json   Print the loaded lines matching --filter to stdout, one JSON object per
+                    line ({{"line_no": N, "text": "..."}}), and exit without the TUI. Add
+                    --all for the whole file
+    --inline        Draw in the normal screen below the prompt instead of the alternate
+                    screen: the last view stays in the scrollback after quitting, but
+                    output above it scrolls away and --mouse is ignored
+    --cat           Print the last lines (-n, default {}) to stdout and exit without the
+                    TUI, like a bounded `tail`; --filter then keeps only matching ones.
+                    With --cat or --output-json, the exit status is 1 if nothing matched
+    -n, --lines N   Number of lines --cat and --output-json load
+    --glob PATTERN  Open the newest file matching PATTERN (e.g. 'logs/app.log.*') and follow
+                    it; live mode switches to a newer match when one appears. * and ? match
+                    in the file name only
+    --theme NAME    Use a colour preset for this session without saving it ({})
+    --line-sep SEP  What ends a line: lf, crlf, cr (old Mac files) or auto (default: cr when
+                    the start of the file has a carriage return but no newline, else crlf)
+    --profile NAME  Use the settings, login token and recent shares of profile NAME, kept in
+                    profiles/NAME.json and profiles/NAME.token in the config directory;
+                    works with every command (e.g. ratlog --profile work login)
+    --line-numbers  Prefix lines printed by --cat with their line number in the file
+    --no-mem        Hide the RAM usage in the status bar (skips reading process info)
+    --mouse         Drag the scrollbar and use the wheel in the log list (the terminal's
+                    own text selection then needs Shift in most terminals)
 
 CONTROLS (in app):
-    / or Tab or Ctrl+F   Focus filter
+    / or Tab or Ctrl+F   Focus filter (matches log text only, not line numbers)
+                         colN:term matches only the N-th field, e.g. col3:ERROR
+                         a|b matches either term (\| for a literal |)
+                         re:pattern matches a regular expression (red border if invalid)
     S                    Settings (colours)
+    C                    Next accent colour (saved)
     L or F               Toggle live mode (when viewing a file)
-    P                    Share logs to Ratlog Web (requires login)
+    R                    Reload the file (offered when it grew while live mode is off)
+    I                    Show invisible characters (tabs, \r, control chars)
+    P                    Share logs to Ratlog Web (pick visibility and expiry; requires login)
     g / G                Go to first / last line
-    q or Ctrl+C          Quit
+    :                    Go to a file line number
+    N%                   Go to N% of the list (e.g. 50%)
+    Ctrl+O / Ctrl+I      Jump back to the line before the last jump / forward again
+    Y                    Copy the lines on screen to the clipboard
+    Ctrl+Y               Copy the path of the open file to the clipboard
+    W                    Filter by a word of the selected line (←/→ to pick, Enter)
+    V                    Split view: matches on the left, selected line in context on the right
+    D                    Dense mode: no line numbers, one-column selection marker
+    T                    Column view: timestamps and levels aligned, messages after them
+    ?                    Show all keybindings
+    Ctrl+P               Command palette: type to find an action, Enter to run it
+    q or Ctrl+C          Quit (Ctrl+C only if set in Settings)
 
 https://github.com/ahmetbarut/ratlog
 "#,
-        VERSION, MAX_LINES
+        VERSION,
+        MAX_LINES,
+        MAX_LINES,
+        format_bytes(ALL_WARN_BYTES),
+        MAX_LINES,
+        ThemePreset::names()
     );
 }
 
@@ -58,17 +201,98 @@ pub fn parse_args(args: &[String]) -> CliAction {
         std::process::exit(0);
     }
     if args.iter().skip(1).any(|a| a == "-V" || a == "--version") {
-        print_version();
+        let verbose = args.iter().skip(1).any(|a| a == "-v" || a == "--verbose");
+        print_version(verbose);
         std::process::exit(0);
     }
-    let positional: Vec<&String> = args
-        .iter()
-        .skip(1)
-        .filter(|a| !a.starts_with('-'))
-        .collect();
-    if positional.first().map(|s| s.as_str()) == Some("login") {
-        return CliAction::Login;
+    let mut positional: Vec<&String> = Vec::new();
+    let mut rest = args.iter().skip(1);
+    while let Some(a) = rest.next() {
+        if matches!(
+            a.as_str(),
+            "--filter" | "-n" | "--lines" | "--glob" | "--theme" | "--line-sep" | "--profile"
+        ) {
+            // The value belongs to the flag, even when it looks like a file name.
+            rest.next();
+        } else if a == "-" || !a.starts_with('-') {
+            positional.push(a);
+        }
+    }
+    match positional.first().map(|s| s.as_str()) {
+        Some("login") => CliAction::Login {
+            check: args.iter().skip(1).any(|a| a == "--check"),
+        },
+        Some("shares") => CliAction::Shares {
+            json: args.iter().skip(1).any(|a| a == "--json"),
+        },
+        Some("unshare") => CliAction::Unshare(positional.get(1).map(|s| s.to_string())),
+        Some("config") => CliAction::Config(
+            match (positional.get(1).map(|s| s.as_str()), positional.get(2)) {
+                (Some("export"), _) => Some(ConfigAction::Export),
+                (Some("import"), Some(file)) => Some(ConfigAction::Import(file.to_string())),
+                _ => None,
+            },
+        ),
+        // `--glob` names the file itself; it wins over a LOG_FILE argument.
+        _ if option_value(args, &["--glob"]).is_some() => {
+            let options = run_options(args);
+            let pattern = options.glob.clone().unwrap_or_default();
+            CliAction::Run(LogSource::Glob(pattern), options)
+        }
+        Some("-") => CliAction::Run(LogSource::Stdin, run_options(args)),
+        Some(arg) => {
+            let source = match RemoteSource::parse(arg) {
+                Some(remote) => LogSource::Remote(remote),
+                None => LogSource::File(PathBuf::from(arg)),
+            };
+            CliAction::Run(source, run_options(args))
+        }
+        None => CliAction::Run(LogSource::Sample, run_options(args)),
     }
-    let file_arg = positional.first().map(|s| PathBuf::from(s.as_str()));
-    CliAction::Run(file_arg)
+}
+
+fn run_options(args: &[String]) -> RunOptions {
+    RunOptions {
+        no_mem: args.iter().skip(1).any(|a| a == "--no-mem"),
+        follow: args.iter().skip(1).any(|a| a == "--follow" || a == "-f"),
+        filter: filter_arg(args),
+        mouse: args.iter().skip(1).any(|a| a == "--mouse"),
+        all: args.iter().skip(1).any(|a| a == "--all"),
+        raw: args.iter().skip(1).any(|a| a == "--raw"),
+        output_json: args.iter().skip(1).any(|a| a == "--output-json"),
+        inline: args.iter().skip(1).any(|a| a == "--inline"),
+        cat: args.iter().skip(1).any(|a| a == "--cat"),
+        lines: option_value(args, &["-n", "--lines"]).and_then(|n| n.parse().ok()),
+        line_numbers: args.iter().skip(1).any(|a| a == "--line-numbers"),
+        glob: option_value(args, &["--glob"]),
+        theme: option_value(args, &["--theme"]),
+        line_sep: option_value(args, &["--line-sep"]),
+    }
+}
+
+/// Value of the last `FLAG VALUE` or `FLAG=VALUE` among `flags`.
+fn option_value(args: &[String], flags: &[&str]) -> Option<String> {
+    let mut value = None;
+    let mut rest = args.iter().skip(1);
+    while let Some(a) = rest.next() {
+        if flags.contains(&a.as_str()) {
+            value = rest.next().cloned();
+        } else if let Some((flag, v)) = a.split_once('=')
+            && flags.contains(&flag)
+        {
+            value = Some(v.to_string());
+        }
+    }
+    value
+}
+
+/// Value of `--profile NAME`, which applies to every command, so main reads it before the
+/// rest.
+pub fn profile_arg(args: &[String]) -> Option<String> {
+    option_value(args, &["--profile"])
+}
+
+/// Value of `--filter STR` or `--filter=STR`; the last one given wins.
+fn filter_arg(args: &[String]) -> Option<String> {
+    option_value(args, &["--filter"])
 }
@@ -10,3 +10,65 @@ pub const POLL_READ_CAP: usize = 512 * 1024; // 512 KiB
 
 /// When file is larger than this, we only read the last TAIL_READ_SIZE bytes (no full-file stream).
 pub const TAIL_READ_SIZE: u64 = 2 * 1024 * 1024; // 2 MiB
+
+/// Above this many bytes before a tail read, its lines are numbered from 1 instead of counting
+/// the lines before it (the log pane title says so).
+pub const TAIL_COUNT_LIMIT: u64 = 256 * 1024 * 1024; // 256 MiB
+
+/// Bytes at the start of a file that `--line-sep auto` looks at for `\n` and `\r`.
+pub const LINE_SEP_SNIFF: u64 = 8 * 1024; // 8 KiB
+
+/// Default number of spaces a tab expands to when displaying log lines.
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// Tab widths selectable in the settings panel.
+pub const TAB_WIDTHS: [usize; 3] = [2, 4, 8];
+
+/// Live-mode poll intervals in ms: new data resets to the first, each run of
+/// `IDLE_POLLS_PER_STEP` empty polls moves to the next. Also the choices for the idle cap.
+pub const POLL_INTERVALS_MS: [u64; 4] = [400, 1000, 2000, 5000];
+
+/// Default cap on the live-mode poll interval while the file is idle.
+pub const DEFAULT_MAX_POLL_MS: u64 = 2000;
+
+/// Consecutive empty polls before the poll interval steps up.
+pub const IDLE_POLLS_PER_STEP: u32 = 5;
+
+/// Span of recent live appends averaged for the lines-per-second rate.
+pub const RATE_WINDOW: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Outside live mode, how often the file size is checked to offer a reload.
+pub const SIZE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// With `--glob`, how often live mode looks for a newer matching file.
+pub const GLOB_SCAN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// In live mode, how often an HTTP log is asked for the bytes past the last ones received.
+pub const REMOTE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Default cap on the filter length in characters, so a huge paste can't stall every redraw.
+pub const MAX_FILTER_LEN: usize = 1000;
+
+/// Compiled size limit for `re:` filters; larger patterns are reported instead of built.
+pub const REGEX_SIZE_LIMIT: usize = 1 << 20; // 1 MiB
+
+/// Positions remembered for jumping back with Ctrl+O.
+pub const JUMP_LIST_LEN: usize = 50;
+
+/// How long a status message replaces the key hint line.
+pub const MESSAGE_DURATION: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Default cap on the content sent by one share; `RATLOG_SHARE_MAX_BYTES` overrides it.
+pub const MAX_SHARE_BYTES: usize = 5 * 1024 * 1024; // 5 MiB
+
+/// Files larger than this get a "Loading…" line on stderr until the viewer starts.
+pub const LOADING_NOTICE_BYTES: u64 = 1024 * 1024; // 1 MiB
+
+/// With `--all`, files larger than this get a memory warning before they are loaded.
+pub const ALL_WARN_BYTES: u64 = 100 * 1024 * 1024; // 100 MiB
+
+/// Smallest terminal the viewer draws into: room for the log pane gutter and selection marker,
+/// and for the filter box, one log row, the status bar and the hint line. Below it, only a
+/// "terminal too small" message is shown.
+pub const MIN_TERMINAL_WIDTH: u16 = 20;
+pub const MIN_TERMINAL_HEIGHT: u16 = 8;
@@ -0,0 +1,55 @@
+//! Token highlighting: IP addresses, URLs, UUIDs and durations found in log text.
+
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use regex::{Regex, RegexBuilder};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    Url,
+    Uuid,
+    Ip,
+    Duration,
+}
+
+/// One pattern with a named group per token; earlier alternatives win where they overlap,
+/// so the host of a URL is not also an IP.
+static TOKENS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"(?P<url>\b(?:https?|wss?|ftp)://[^\s<>\x22']+)",
+        r"|(?P<uuid>\b[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}\b)",
+        r"|(?P<ip>\b(?:\d{1,3}\.){3}\d{1,3}(?::\d{1,5})?\b)",
+        r"|(?P<duration>\b\d+(?:\.\d+)?(?:ns|us|µs|ms|s|m|h)\b)",
+    ))
+    .expect("token pattern")
+});
+
+/// Pattern finding `text` literally and case-insensitively, for `*` / `n` / `N`; `None` when
+/// `text` is empty.
+pub fn find_pattern(text: &str) -> Option<Regex> {
+    if text.is_empty() {
+        return None;
+    }
+    RegexBuilder::new(&regex::escape(text))
+        .case_insensitive(true)
+        .build()
+        .ok()
+}
+
+/// Byte ranges of recognised tokens in `text`, in order and not overlapping.
+pub fn find_tokens(text: &str) -> Vec<(Range<usize>, Token)> {
+    TOKENS
+        .captures_iter(text)
+        .filter_map(|caps| {
+            [
+                ("url", Token::Url),
+                ("uuid", Token::Uuid),
+                ("ip", Token::Ip),
+                ("duration", Token::Duration),
+            ]
+            .into_iter()
+            .find_map(|(name, token)| caps.name(name).map(|m| (m.range(), token)))
+        })
+        .collect()
+}
@@ -0,0 +1,675 @@
+//! UI strings in English (default) and Turkish, selected by the language setting.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    En,
+    Tr,
+}
+
+impl Language {
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Tr => "tr",
+        }
+    }
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::En => "English",
+            Language::Tr => "Türkçe",
+        }
+    }
+    pub fn all() -> &'static [Language] {
+        &[Language::En, Language::Tr]
+    }
+    pub fn from_code(code: &str) -> Option<Language> {
+        Language::all()
+            .iter()
+            .find(|l| l.code().eq_ignore_ascii_case(code.trim()))
+            .copied()
+    }
+}
+
+/// All user-facing strings. `{}` placeholders are filled in order by [`fill`].
+pub struct Strings {
+    pub press_any_key: &'static str,
+    pub shared: &'static str,
+    pub error: &'static str,
+    pub login_first: &'static str,
+    pub filter_title: &'static str,
+    pub filter_title_focused: &'static str,
+    pub filter_invalid_regex: &'static str,
+    pub filter_regex_too_big: &'static str,
+    pub filter_too_long: &'static str,
+    pub logs_title: &'static str,
+    pub logs_title_relative: &'static str,
+    pub terminal_too_small: &'static str,
+    pub context_title: &'static str,
+    pub lines: &'static str,
+    pub filter_none: &'static str,
+    pub status_filter: &'static str,
+    pub status_memory: &'static str,
+    pub older_lines_hidden: &'static str,
+    pub uptime: &'static str,
+    pub status_keys: &'static str,
+    pub status_live: &'static str,
+    pub status_keys_no_live: &'static str,
+    pub bottom_hint: &'static str,
+    pub copied_lines: &'static str,
+    pub accent_changed: &'static str,
+    pub copy_failed: &'static str,
+    pub copied_path: &'static str,
+    pub no_file_path: &'static str,
+    pub goto_line: &'static str,
+    pub goto_percent: &'static str,
+    pub live_not_following: &'static str,
+    pub file_changed: &'static str,
+    pub file_unavailable: &'static str,
+    pub file_reappeared: &'static str,
+    pub live_unavailable: &'static str,
+    pub remote_follow_failed: &'static str,
+    pub palette_title: &'static str,
+    pub palette_no_match: &'static str,
+    pub help_title: &'static str,
+    pub help_log_list: &'static str,
+    pub help_filter: &'static str,
+    pub action_quit: &'static str,
+    pub action_focus_filter: &'static str,
+    pub action_open_settings: &'static str,
+    pub action_toggle_live: &'static str,
+    pub action_reload: &'static str,
+    pub action_share: &'static str,
+    pub action_command_palette: &'static str,
+    pub action_toggle_invisibles: &'static str,
+    pub action_help: &'static str,
+    pub action_goto_line: &'static str,
+    pub action_goto_percent: &'static str,
+    pub action_jump_back: &'static str,
+    pub action_jump_forward: &'static str,
+    pub action_copy_screen: &'static str,
+    pub action_copy_path: &'static str,
+    pub action_pick_token: &'static str,
+    pub action_find: &'static str,
+    pub action_find_next: &'static str,
+    pub action_find_previous: &'static str,
+    pub action_split_view: &'static str,
+    pub action_toggle_dense: &'static str,
+    pub action_toggle_columns: &'static str,
+    pub action_cycle_accent: &'static str,
+    pub action_up: &'static str,
+    pub action_down: &'static str,
+    pub action_page_up: &'static str,
+    pub action_page_down: &'static str,
+    pub action_first: &'static str,
+    pub action_last: &'static str,
+    pub filter_help_back: &'static str,
+    pub filter_help_clear: &'static str,
+    pub filter_help_move: &'static str,
+    pub filter_help_move_word: &'static str,
+    pub filter_help_start_end: &'static str,
+    pub filter_help_delete: &'static str,
+    pub filter_help_delete_word: &'static str,
+    pub filter_help_delete_to_start: &'static str,
+    pub glob_switched: &'static str,
+    pub no_file_matches: &'static str,
+    pub log_file_not_found: &'static str,
+    pub log_file_not_found_expanded: &'static str,
+    pub redact_rule_invalid: &'static str,
+    pub settings_invalid: &'static str,
+    pub live_file_unavailable: &'static str,
+    pub reload_failed: &'static str,
+    pub pick_token: &'static str,
+    pub pick_token_keys: &'static str,
+    pub find_prompt: &'static str,
+    pub find_active: &'static str,
+    pub find_not_found: &'static str,
+    pub find_wrapped: &'static str,
+    pub share_title: &'static str,
+    pub share_result_title: &'static str,
+    pub share_confirm: &'static str,
+    pub share_expires: &'static str,
+    pub expiry_never: &'static str,
+    pub expiry_hour: &'static str,
+    pub expiry_day: &'static str,
+    pub expiry_week: &'static str,
+    pub public: &'static str,
+    pub private: &'static str,
+    pub settings_title: &'static str,
+    pub settings_accent: &'static str,
+    pub settings_text_color: &'static str,
+    pub settings_text_style: &'static str,
+    pub settings_border: &'static str,
+    pub settings_status: &'static str,
+    pub settings_gutter: &'static str,
+    pub settings_tab_width: &'static str,
+    pub settings_language: &'static str,
+    pub settings_filter_select: &'static str,
+    pub filter_select_current: &'static str,
+    pub filter_select_last: &'static str,
+    pub settings_long_lines: &'static str,
+    pub long_lines_clip: &'static str,
+    pub long_lines_middle: &'static str,
+    pub settings_max_poll: &'static str,
+    pub settings_status_fields: &'static str,
+    pub status_layout_full: &'static str,
+    pub status_layout_compact: &'static str,
+    pub status_layout_minimal: &'static str,
+    pub status_layout_custom: &'static str,
+    pub settings_filter_mode: &'static str,
+    pub filter_mode_hide: &'static str,
+    pub filter_mode_tint: &'static str,
+    pub settings_filter_scope: &'static str,
+    pub filter_scope_line: &'static str,
+    pub filter_scope_message: &'static str,
+    pub settings_group_entries: &'static str,
+    pub group_entries_off: &'static str,
+    pub group_entries_on: &'static str,
+    pub settings_per_file: &'static str,
+    pub per_file_off: &'static str,
+    pub per_file_path: &'static str,
+    pub per_file_name: &'static str,
+    pub settings_hint_line: &'static str,
+    pub hint_line_shown: &'static str,
+    pub hint_line_hidden: &'static str,
+    pub settings_blank_lines: &'static str,
+    pub blank_lines_shown: &'static str,
+    pub blank_lines_hidden: &'static str,
+    pub settings_quit_keys: &'static str,
+    pub settings_highlight: &'static str,
+    pub highlight_off: &'static str,
+    pub highlight_on: &'static str,
+    pub quit_keys_any: &'static str,
+    pub quit_keys_ctrl: &'static str,
+    pub settings_redact_warning: &'static str,
+    pub redact_warning_on: &'static str,
+    pub redact_warning_off: &'static str,
+    pub share_unredacted: &'static str,
+    pub settings_back: &'static str,
+    pub login_heading: &'static str,
+    pub login_opening_browser: &'static str,
+    pub login_browser_failed: &'static str,
+    pub login_open_manually: &'static str,
+    pub login_instructions: &'static str,
+    pub login_paste_token: &'static str,
+    pub login_empty_token: &'static str,
+    pub login_verifying: &'static str,
+    pub login_verify_failed: &'static str,
+    pub login_success: &'static str,
+    pub login_token_saved: &'static str,
+    pub config_dir_fallback: &'static str,
+    pub all_large_file: &'static str,
+    pub unknown_theme: &'static str,
+    pub unknown_line_sep: &'static str,
+    pub invalid_profile: &'static str,
+    pub config_imported: &'static str,
+    pub config_usage: &'static str,
+    pub loading: &'static str,
+    pub not_logged_in: &'static str,
+    pub invalid_web_url: &'static str,
+    pub invalid_web_url_scheme: &'static str,
+    pub invalid_web_url_host: &'static str,
+    pub share_failed: &'static str,
+    pub share_too_large: &'static str,
+    pub request_timed_out: &'static str,
+    pub unshare_usage: &'static str,
+    pub unshare_no_token: &'static str,
+    pub unshare_done: &'static str,
+    pub unshare_forbidden: &'static str,
+    pub unshare_not_found: &'static str,
+    pub unshare_failed: &'static str,
+    pub recent_shares_heading: &'static str,
+    pub no_recent_shares: &'static str,
+    pub shares_failed: &'static str,
+    pub no_shared_logs: &'static str,
+    pub shares_col_id: &'static str,
+    pub shares_col_url: &'static str,
+    pub shares_col_created: &'static str,
+    pub shares_col_lines: &'static str,
+    pub ssh_unexpected_output: &'static str,
+    pub log_not_found: &'static str,
+    pub home_dir_not_found: &'static str,
+    pub env_var_not_set: &'static str,
+}
+
+const EN: Strings = Strings {
+    press_any_key: "(Press any key)",
+    shared: "Shared!",
+    error: "Error: {}",
+    login_first: "Log in first: ratlog login",
+    filter_title: " Filter ",
+    filter_title_focused: " Filter (focus) ",
+    filter_invalid_regex: "invalid regex",
+    filter_regex_too_big: "regex too large",
+    filter_too_long: "Filter limited to {} characters (max_filter_len in settings.json)",
+    logs_title: " Logs ",
+    logs_title_relative: " Logs — line numbers count from the loaded tail ",
+    terminal_too_small: "Terminal too small (needs {}×{})",
+    context_title: " Context ",
+    lines: "lines",
+    filter_none: "(none)",
+    status_filter: "Filter: \"{}\"",
+    status_memory: "RAM: {}",
+    older_lines_hidden: "(+{} older lines not shown)",
+    uptime: "up {}",
+    status_keys: "Tab/ /: filter  |  L: live  |  S: settings  |  P: share  |  ?: help  |  q/Esc: quit",
+    status_live: "LIVE",
+    status_keys_no_live: "Tab/ /: filter  |  S: settings  |  P: share  |  ?: help  |  q/Esc: quit",
+    bottom_hint: " g: top  │  G: bottom  │  :: go to line  │  P: share  │  ?: help ",
+    copied_lines: "Copied {} lines to the clipboard",
+    accent_changed: "Accent colour: {} (saved)",
+    copy_failed: "Copy failed: {}",
+    copied_path: "Copied {} to the clipboard",
+    no_file_path: "No file path to copy for {} input",
+    goto_line: "Go to line (Enter, Esc cancels)",
+    goto_percent: "Go to {}% (press %, Esc cancels)",
+    live_not_following: "LIVE (G follows)",
+    file_changed: "File changed on disk — press r to reload",
+    file_unavailable: "File unavailable (deleted or moved) — waiting for it to come back",
+    file_reappeared: "File is back — reloaded",
+    live_unavailable: "Live mode unavailable for {} input — open a file to follow it",
+    remote_follow_failed: "Following the remote log failed: {}",
+    palette_title: " Commands — type to filter, ↑/↓, Enter run, Esc close ",
+    palette_no_match: "No matching command",
+    help_title: " Help — ↑/↓ scroll, Esc close ",
+    help_log_list: "Log list",
+    help_filter: "Filter",
+    action_quit: "Quit",
+    action_focus_filter: "Focus filter",
+    action_open_settings: "Settings (colours, tab width)",
+    action_toggle_live: "Toggle live mode (files and remote logs)",
+    action_reload: "Reload the file from disk",
+    action_share: "Share logs to Ratlog Web",
+    action_command_palette: "Command palette: find and run an action by name",
+    action_toggle_invisibles: "Show invisible characters",
+    action_help: "Show this help",
+    action_goto_line: "Go to file line number",
+    action_goto_percent: "Go to N% of the list (type the number first, e.g. 50%)",
+    action_jump_back: "Back to where you were before the last jump (:, %, g, G)",
+    action_jump_forward: "Forward again after Ctrl+O",
+    action_copy_screen: "Copy the lines on screen to the clipboard",
+    action_copy_path: "Copy the absolute path of the open file to the clipboard",
+    action_pick_token: "Filter by a word of the selected line",
+    action_find: "Find text in the lines, highlighting every match",
+    action_find_next: "Next find match (Esc clears the highlights)",
+    action_find_previous: "Previous find match",
+    action_split_view: "Split view: matches and the selected line in context",
+    action_toggle_dense: "Dense mode: no line numbers, narrow selection marker",
+    action_toggle_columns: "Column view: timestamps and levels aligned",
+    action_cycle_accent: "Next accent colour (saved, like in Settings)",
+    action_up: "Previous line",
+    action_down: "Next line",
+    action_page_up: "Scroll up a page",
+    action_page_down: "Scroll down a page",
+    action_first: "Go to first line",
+    action_last: "Go to last line",
+    filter_help_back: "Back to log list",
+    filter_help_clear: "Clear filter (quit when empty)",
+    filter_help_move: "Move cursor",
+    filter_help_move_word: "Move cursor by word",
+    filter_help_start_end: "Cursor to start / end",
+    filter_help_delete: "Delete character",
+    filter_help_delete_word: "Delete word before cursor",
+    filter_help_delete_to_start: "Delete to start of filter",
+    glob_switched: "Newer file — now following {}",
+    no_file_matches: "No file matches {}",
+    log_file_not_found: "Log file not found: {}",
+    log_file_not_found_expanded: "Log file not found: {} (expanded from {})",
+    redact_rule_invalid: "Redact rule not applied — {}",
+    settings_invalid: "settings.json not applied or saved — {}",
+    live_file_unavailable: "LIVE (file unavailable)",
+    reload_failed: "Reload failed: {}",
+    pick_token: "Filter by",
+    pick_token_keys: "(←/→, Enter, Esc cancels)",
+    find_prompt: "Find (Enter, Esc cancels)",
+    find_active: "Find: \"{}\" — n next, N previous, Esc clears",
+    find_not_found: "Not found: {}",
+    find_wrapped: "Search wrapped around",
+    share_title: " Share ",
+    share_result_title: " Share Log ",
+    share_confirm: "Share logs to Ratlog Web?\n\n  Visibility: {}  (←/→ or P/U)\n  Expires: {}  (↑/↓)\n\n  [Y]es (Enter)   [N]o (Esc)",
+    share_expires: "Expires: {}",
+    expiry_never: "Never",
+    expiry_hour: "In 1 hour",
+    expiry_day: "In 1 day",
+    expiry_week: "In 7 days",
+    public: "Public",
+    private: "Private",
+    settings_title: " Settings ",
+    settings_accent: "Accent (focus/highlight)",
+    settings_text_color: "Text colour",
+    settings_text_style: "Text style",
+    settings_border: "Border colour",
+    settings_status: "Status bar colour",
+    settings_gutter: "Line number colour",
+    settings_tab_width: "Tab width",
+    settings_language: "Language",
+    settings_filter_select: "Filter selects",
+    filter_select_current: "Current row",
+    filter_select_last: "Last match",
+    settings_long_lines: "Long lines",
+    long_lines_clip: "Clip end",
+    long_lines_middle: "Ellipsis in middle",
+    settings_max_poll: "Idle poll interval (max)",
+    settings_status_fields: "Status bar fields",
+    status_layout_full: "All",
+    status_layout_compact: "Count, live, filter",
+    status_layout_minimal: "Count, live",
+    status_layout_custom: "Custom (settings.json)",
+    settings_filter_mode: "Filter mode",
+    filter_mode_hide: "Hide other lines",
+    filter_mode_tint: "Tint matching lines",
+    settings_filter_scope: "Filter matches",
+    filter_scope_line: "Whole line",
+    filter_scope_message: "Message only",
+    settings_group_entries: "Multi-line entries",
+    group_entries_off: "One line each",
+    group_entries_on: "Group under timestamp",
+    settings_per_file: "Remember per file",
+    per_file_off: "Off",
+    per_file_path: "By path",
+    per_file_name: "By file name",
+    settings_hint_line: "Hint line",
+    hint_line_shown: "Shown",
+    hint_line_hidden: "Hidden (prompts only)",
+    settings_blank_lines: "Blank lines",
+    blank_lines_shown: "Shown",
+    blank_lines_hidden: "Hidden",
+    settings_quit_keys: "Quit keys",
+    quit_keys_any: "q, Esc or Ctrl+C",
+    quit_keys_ctrl: "Ctrl+C only",
+    settings_redact_warning: "Share without redact rules",
+    redact_warning_on: "Warn first",
+    redact_warning_off: "Share directly",
+    share_unredacted: "No redact rules are set in settings.json, so the lines are uploaded exactly as loaded — including any tokens, passwords or e-mail addresses.\n\n  Continue?  [Y]es (Enter)   [N]o (Esc)\n\n  Settings → “Share without redact rules” turns this warning off.",
+    settings_highlight: "Highlight tokens",
+    highlight_off: "Off",
+    highlight_on: "IPs, URLs, UUIDs, durations",
+    settings_back: "Back (Enter or Esc)",
+    login_heading: "Ratlog Web CLI Login",
+    login_opening_browser: "Opening browser: {}",
+    login_browser_failed: "Could not open browser: {}",
+    login_open_manually: "Please open this URL manually: {}",
+    login_instructions: "Log in in the browser and copy the token.",
+    login_paste_token: "Paste the token: ",
+    login_empty_token: "Token cannot be empty.",
+    login_verifying: "Verifying token...",
+    login_verify_failed: "Token verification failed: {}",
+    login_success: "✓ Logged in: {}",
+    login_token_saved: "Token saved: {}",
+    config_dir_fallback: "no config directory found; settings and token are stored in {}",
+    all_large_file: "--all loads the whole file ({}) into memory; this may take a while",
+    unknown_theme: "unknown theme '{}'; available themes: {}",
+    unknown_line_sep: "unknown --line-sep '{}'; use lf, crlf, cr or auto",
+    invalid_profile: "invalid --profile '{}'; use letters, digits, -, _ and . (not first)",
+    config_imported: "settings imported from {} into {}",
+    config_usage: "usage: ratlog config export | ratlog config import FILE",
+    loading: "Loading {}…",
+    not_logged_in: "Not logged in. Run 'ratlog login' first.",
+    invalid_web_url: "Invalid {}: {} ({})",
+    invalid_web_url_scheme: "scheme must be http or https, not {}",
+    invalid_web_url_host: "no host name",
+    share_failed: "Log share failed ({}): {}",
+    share_too_large: "Logs too large to share: {} (limit {}). Filter them down, or raise {}.",
+    request_timed_out: "Ratlog Web did not respond within {} seconds; check your connection and try again.",
+    unshare_usage: "Usage: ratlog unshare <url-or-id>",
+    unshare_no_token: "No delete token saved for {}; only logs shared from this machine can be deleted.",
+    unshare_done: "Deleted shared log {}.",
+    unshare_forbidden: "Not allowed to delete {} (try 'ratlog login' again).",
+    unshare_not_found: "Shared log {} not found (already deleted or expired).",
+    unshare_failed: "Deleting the shared log failed ({}): {}",
+    recent_shares_heading: "Recent shares from this machine:",
+    no_recent_shares: "No shares made from this machine yet.",
+    shares_failed: "Listing shared logs failed ({}): {}",
+    no_shared_logs: "You have no shared logs.",
+    shares_col_id: "ID",
+    shares_col_url: "URL",
+    shares_col_created: "CREATED",
+    shares_col_lines: "LINES",
+    ssh_unexpected_output: "ssh {}: unexpected wc output",
+    log_not_found: "Log not found: {}",
+    home_dir_not_found: "home directory not found",
+    env_var_not_set: "environment variable ${} is not set",
+};
+
+const TR: Strings = Strings {
+    press_any_key: "(Herhangi bir tuşa basın)",
+    shared: "Paylaşıldı!",
+    error: "Hata: {}",
+    login_first: "Önce giriş yapın: ratlog login",
+    filter_title: " Filtre ",
+    filter_title_focused: " Filtre (odak) ",
+    filter_invalid_regex: "geçersiz regex",
+    filter_regex_too_big: "regex çok büyük",
+    filter_too_long: "Filtre en fazla {} karakter olabilir (settings.json'da max_filter_len)",
+    logs_title: " Loglar ",
+    logs_title_relative: " Loglar — satır numaraları yüklenen sondan sayılır ",
+    terminal_too_small: "Terminal çok küçük ({}×{} gerekli)",
+    context_title: " Bağlam ",
+    lines: "satır",
+    filter_none: "(yok)",
+    status_filter: "Filtre: \"{}\"",
+    status_memory: "Bellek: {}",
+    older_lines_hidden: "(+{} eski satır gösterilmiyor)",
+    uptime: "süre {}",
+    status_keys: "Tab/ /: filtre  |  L: canlı  |  S: ayarlar  |  P: paylaş  |  ?: yardım  |  q/Esc: çıkış",
+    status_live: "CANLI",
+    status_keys_no_live: "Tab/ /: filtre  |  S: ayarlar  |  P: paylaş  |  ?: yardım  |  q/Esc: çıkış",
+    bottom_hint: " g: en üst  │  G: en alt  │  :: satıra git  │  P: paylaş  │  ?: yardım ",
+    copied_lines: "{} satır panoya kopyalandı",
+    accent_changed: "Vurgu rengi: {} (kaydedildi)",
+    copy_failed: "Kopyalanamadı: {}",
+    copied_path: "{} panoya kopyalandı",
+    no_file_path: "{} girdisi için kopyalanacak dosya yolu yok",
+    goto_line: "Satıra git (Enter, Esc iptal)",
+    goto_percent: "%{} konumuna git (% tuşuna basın, Esc iptal)",
+    live_not_following: "LIVE (G ile takip)",
+    file_changed: "Dosya diskte değişti — yeniden yüklemek için r",
+    file_unavailable: "Dosya yok (silindi veya taşındı) — geri gelmesi bekleniyor",
+    file_reappeared: "Dosya geri geldi — yeniden yüklendi",
+    live_unavailable: "Canlı mod {} girdisinde kullanılamaz — izlemek için bir dosya açın",
+    remote_follow_failed: "Uzak log izlenemedi: {}",
+    palette_title: " Komutlar — süzmek için yazın, ↑/↓, Enter çalıştır, Esc kapat ",
+    palette_no_match: "Eşleşen komut yok",
+    help_title: " Yardım — ↑/↓ kaydır, Esc kapat ",
+    help_log_list: "Log listesi",
+    help_filter: "Filtre",
+    action_quit: "Çık",
+    action_focus_filter: "Filtreye odaklan",
+    action_open_settings: "Ayarlar (renkler, sekme genişliği)",
+    action_toggle_live: "Canlı modu aç/kapat (dosyalar ve uzak loglar)",
+    action_reload: "Dosyayı diskten yeniden yükle",
+    action_share: "Logları Ratlog Web'de paylaş",
+    action_command_palette: "Komut paleti: bir eylemi adıyla bulup çalıştır",
+    action_toggle_invisibles: "Görünmez karakterleri göster",
+    action_help: "Bu yardımı göster",
+    action_goto_line: "Dosya satır numarasına git",
+    action_goto_percent: "Listenin %N'ine git (önce sayıyı yazın, ör. 50%)",
+    action_jump_back: "Son atlamadan önceki yere dön (:, %, g, G)",
+    action_jump_forward: "Ctrl+O sonrası yeniden ileri",
+    action_copy_screen: "Ekrandaki satırları panoya kopyala",
+    action_copy_path: "Açık dosyanın tam yolunu panoya kopyala",
+    action_pick_token: "Seçili satırdaki bir kelimeyle filtrele",
+    action_find: "Satırlarda metin ara, tüm eşleşmeleri vurgula",
+    action_find_next: "Sonraki eşleşme (Esc vurguları temizler)",
+    action_find_previous: "Önceki eşleşme",
+    action_split_view: "Bölünmüş görünüm: eşleşmeler ve seçili satırın çevresi",
+    action_toggle_dense: "Sıkı mod: satır numarası yok, dar seçim işareti",
+    action_toggle_columns: "Sütun görünümü: zaman damgaları ve seviyeler hizalı",
+    action_cycle_accent: "Sonraki vurgu rengi (Ayarlar'daki gibi kaydedilir)",
+    action_up: "Önceki satır",
+    action_down: "Sonraki satır",
+    action_page_up: "Bir sayfa yukarı kaydır",
+    action_page_down: "Bir sayfa aşağı kaydır",
+    action_first: "İlk satıra git",
+    action_last: "Son satıra git",
+    filter_help_back: "Log listesine dön",
+    filter_help_clear: "Filtreyi temizle (boşsa çık)",
+    filter_help_move: "İmleci taşı",
+    filter_help_move_word: "İmleci kelime kelime taşı",
+    filter_help_start_end: "İmleç başa / sona",
+    filter_help_delete: "Karakter sil",
+    filter_help_delete_word: "İmleçten önceki kelimeyi sil",
+    filter_help_delete_to_start: "Filtrenin başına kadar sil",
+    glob_switched: "Daha yeni dosya — artık {} izleniyor",
+    no_file_matches: "{} ile eşleşen dosya yok",
+    log_file_not_found: "Log dosyası bulunamadı: {}",
+    log_file_not_found_expanded: "Log dosyası bulunamadı: {} ({} ifadesinden açıldı)",
+    redact_rule_invalid: "Maskeleme kuralı uygulanmadı — {}",
+    settings_invalid: "settings.json uygulanmadı ve kaydedilmedi — {}",
+    live_file_unavailable: "LIVE (dosya yok)",
+    reload_failed: "Yeniden yükleme başarısız: {}",
+    pick_token: "Filtrele",
+    pick_token_keys: "(←/→, Enter, Esc iptal)",
+    find_prompt: "Bul (Enter, Esc iptal)",
+    find_active: "Bul: \"{}\" — n sonraki, N önceki, Esc temizler",
+    find_not_found: "Bulunamadı: {}",
+    find_wrapped: "Arama başa döndü",
+    share_title: " Paylaş ",
+    share_result_title: " Log Paylaş ",
+    share_confirm: "Logları Ratlog Web'e paylaşmak istiyor musunuz?\n\n  Görünürlük: {}  (←/→ veya P/U)\n  Süre: {}  (↑/↓)\n\n  [E]vet (Enter)   [H]ayır (Esc)",
+    share_expires: "Bitiş: {}",
+    expiry_never: "Süresiz",
+    expiry_hour: "1 saat",
+    expiry_day: "1 gün",
+    expiry_week: "7 gün",
+    public: "Herkese açık",
+    private: "Özel",
+    settings_title: " Ayarlar ",
+    settings_accent: "Vurgu (odak/seçim)",
+    settings_text_color: "Metin rengi",
+    settings_text_style: "Metin stili",
+    settings_border: "Kenarlık rengi",
+    settings_status: "Durum çubuğu rengi",
+    settings_gutter: "Satır numarası rengi",
+    settings_tab_width: "Tab genişliği",
+    settings_language: "Dil",
+    settings_filter_select: "Filtre seçimi",
+    filter_select_current: "Mevcut satır",
+    filter_select_last: "Son eşleşme",
+    settings_long_lines: "Uzun satırlar",
+    long_lines_clip: "Sonu kes",
+    long_lines_middle: "Ortada üç nokta",
+    settings_max_poll: "Boşta yoklama aralığı (en fazla)",
+    settings_status_fields: "Durum çubuğu alanları",
+    status_layout_full: "Tümü",
+    status_layout_compact: "Sayı, canlı, filtre",
+    status_layout_minimal: "Sayı, canlı",
+    status_layout_custom: "Özel (settings.json)",
+    settings_filter_mode: "Filtre modu",
+    filter_mode_hide: "Diğer satırları gizle",
+    filter_mode_tint: "Eşleşen satırları renklendir",
+    settings_filter_scope: "Filtre eşleşmesi",
+    filter_scope_line: "Tüm satır",
+    filter_scope_message: "Yalnızca mesaj",
+    settings_group_entries: "Çok satırlı kayıtlar",
+    group_entries_off: "Her satır ayrı",
+    group_entries_on: "Zaman damgası altında grupla",
+    settings_per_file: "Dosya başına hatırla",
+    per_file_off: "Kapalı",
+    per_file_path: "Yola göre",
+    per_file_name: "Dosya adına göre",
+    settings_hint_line: "İpucu satırı",
+    hint_line_shown: "Göster",
+    hint_line_hidden: "Gizle (yalnızca istemler)",
+    settings_blank_lines: "Boş satırlar",
+    blank_lines_shown: "Göster",
+    blank_lines_hidden: "Gizle",
+    settings_quit_keys: "Çıkış tuşları",
+    quit_keys_any: "q, Esc veya Ctrl+C",
+    quit_keys_ctrl: "Yalnızca Ctrl+C",
+    settings_redact_warning: "Maskeleme kuralı olmadan paylaşım",
+    redact_warning_on: "Önce uyar",
+    redact_warning_off: "Doğrudan paylaş",
+    share_unredacted: "settings.json'da maskeleme (redact) kuralı yok; satırlar yüklendiği gibi gönderilecek — token, parola veya e-posta adresleri dahil.\n\n  Devam edilsin mi?  [E]vet (Enter)   [H]ayır (Esc)\n\n  Ayarlar → “Maskeleme kuralı olmadan paylaşım” bu uyarıyı kapatır.",
+    settings_highlight: "Öğeleri vurgula",
+    highlight_off: "Kapalı",
+    highlight_on: "IP, URL, UUID, süreler",
+    settings_back: "Geri (Enter veya Esc)",
+    login_heading: "Ratlog Web CLI Girişi",
+    login_opening_browser: "Tarayıcı açılıyor: {}",
+    login_browser_failed: "Tarayıcı açılamadı: {}",
+    login_open_manually: "Lütfen şu adresi manuel açın: {}",
+    login_instructions: "Tarayıcıda giriş yapın ve token'ı kopyalayın.",
+    login_paste_token: "Token'ı yapıştırın: ",
+    login_empty_token: "Token boş bırakılamaz.",
+    login_verifying: "Token doğrulanıyor...",
+    login_verify_failed: "Token doğrulama başarısız: {}",
+    login_success: "✓ Giriş başarılı: {}",
+    login_token_saved: "Token kaydedildi: {}",
+    config_dir_fallback: "config dizini bulunamadı; ayarlar ve token {} içinde saklanıyor",
+    all_large_file: "--all tüm dosyayı ({}) belleğe yüklüyor; bu biraz sürebilir",
+    unknown_theme: "bilinmeyen tema '{}'; mevcut temalar: {}",
+    unknown_line_sep: "bilinmeyen --line-sep '{}'; lf, crlf, cr veya auto kullanın",
+    invalid_profile: "geçersiz --profile '{}'; harf, rakam, -, _ ve . kullanın (. başta olamaz)",
+    config_imported: "ayarlar {} dosyasından {} dosyasına aktarıldı",
+    config_usage: "kullanım: ratlog config export | ratlog config import DOSYA",
+    loading: "{} yükleniyor…",
+    not_logged_in: "Giriş yapılmamış. Önce 'ratlog login' çalıştırın.",
+    invalid_web_url: "Geçersiz {}: {} ({})",
+    invalid_web_url_scheme: "şema http veya https olmalı, {} değil",
+    invalid_web_url_host: "sunucu adı yok",
+    share_failed: "Log paylaşımı başarısız ({}): {}",
+    share_too_large: "Loglar paylaşmak için çok büyük: {} (sınır {}). Filtreleyerek azaltın veya {} değerini yükseltin.",
+    request_timed_out: "Ratlog Web {} saniye içinde yanıt vermedi; bağlantınızı kontrol edip tekrar deneyin.",
+    unshare_usage: "Kullanım: ratlog unshare <url-veya-id>",
+    unshare_no_token: "{} için silme token'ı kayıtlı değil; yalnızca bu makineden paylaşılan loglar silinebilir.",
+    unshare_done: "Paylaşılan log {} silindi.",
+    unshare_forbidden: "{} silinemiyor, yetki yok ('ratlog login' ile tekrar giriş yapın).",
+    unshare_not_found: "Paylaşılan log {} bulunamadı (silinmiş veya süresi dolmuş).",
+    unshare_failed: "Paylaşılan log silinemedi ({}): {}",
+    recent_shares_heading: "Bu makineden yapılan son paylaşımlar:",
+    no_recent_shares: "Bu makineden henüz paylaşım yapılmadı.",
+    shares_failed: "Paylaşılan loglar listelenemedi ({}): {}",
+    no_shared_logs: "Paylaşılmış logunuz yok.",
+    shares_col_id: "ID",
+    shares_col_url: "URL",
+    shares_col_created: "OLUŞTURULMA",
+    shares_col_lines: "SATIR",
+    ssh_unexpected_output: "ssh {}: beklenmeyen wc çıktısı",
+    log_not_found: "Log bulunamadı: {}",
+    home_dir_not_found: "ev dizini bulunamadı",
+    env_var_not_set: "${} ortam değişkeni tanımlı değil",
+};
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Set the language used by [`t`] for the rest of the process.
+pub fn set_language(lang: Language) {
+    CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Language::Tr,
+        _ => Language::En,
+    }
+}
+
+pub fn strings(lang: Language) -> &'static Strings {
+    match lang {
+        Language::En => &EN,
+        Language::Tr => &TR,
+    }
+}
+
+/// Strings for the current language.
+pub fn t() -> &'static Strings {
+    strings(language())
+}
+
+/// Replace each `{}` in `template` with the next argument.
+pub fn fill(template: &str, args: &[&str]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(pos) = rest.find("{}") {
+        out.push_str(&rest[..pos]);
+        out.push_str(args.next().copied().unwrap_or(""));
+        rest = &rest[pos + 2..];
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,323 @@
+//! Keymap: key bindings for the log list and help text generated from them.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::i18n::t;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    FocusFilter,
+    OpenSettings,
+    ToggleLive,
+    Reload,
+    Share,
+    CommandPalette,
+    ToggleInvisibles,
+    Help,
+    GotoLine,
+    GotoPercent,
+    JumpBack,
+    JumpForward,
+    CopyScreen,
+    CopyPath,
+    PickToken,
+    Find,
+    FindNext,
+    FindPrevious,
+    SplitView,
+    ToggleDense,
+    ToggleColumns,
+    CycleAccent,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    First,
+    Last,
+}
+
+impl Action {
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => t().action_quit,
+            Action::FocusFilter => t().action_focus_filter,
+            Action::OpenSettings => t().action_open_settings,
+            Action::ToggleLive => t().action_toggle_live,
+            Action::Reload => t().action_reload,
+            Action::Share => t().action_share,
+            Action::CommandPalette => t().action_command_palette,
+            Action::ToggleInvisibles => t().action_toggle_invisibles,
+            Action::Help => t().action_help,
+            Action::GotoLine => t().action_goto_line,
+            Action::GotoPercent => t().action_goto_percent,
+            Action::JumpBack => t().action_jump_back,
+            Action::JumpForward => t().action_jump_forward,
+            Action::CopyScreen => t().action_copy_screen,
+            Action::CopyPath => t().action_copy_path,
+            Action::PickToken => t().action_pick_token,
+            Action::Find => t().action_find,
+            Action::FindNext => t().action_find_next,
+            Action::FindPrevious => t().action_find_previous,
+            Action::SplitView => t().action_split_view,
+            Action::ToggleDense => t().action_toggle_dense,
+            Action::ToggleColumns => t().action_toggle_columns,
+            Action::CycleAccent => t().action_cycle_accent,
+            Action::Up => t().action_up,
+            Action::Down => t().action_down,
+            Action::PageUp => t().action_page_up,
+            Action::PageDown => t().action_page_down,
+            Action::First => t().action_first,
+            Action::Last => t().action_last,
+        }
+    }
+}
+
+/// A set of keys triggering one action. Keys with empty modifiers match regardless of
+/// modifiers held (e.g. Shift for `G`); keys with modifiers require them.
+pub struct Binding {
+    pub keys: &'static [(KeyModifiers, KeyCode)],
+    pub action: Action,
+}
+
+const NONE: KeyModifiers = KeyModifiers::NONE;
+const CTRL: KeyModifiers = KeyModifiers::CONTROL;
+
+/// Keys active everywhere except popups.
+pub const GLOBAL_BINDINGS: &[Binding] = &[Binding {
+    keys: &[
+        (NONE, KeyCode::Char('q')),
+        (CTRL, KeyCode::Char('c')),
+        (CTRL, KeyCode::Char('C')),
+    ],
+    action: Action::Quit,
+}];
+
+/// Keys active while the log list has focus. Earlier bindings win (Ctrl+F before F).
+pub const LOG_LIST_BINDINGS: &[Binding] = &[
+    Binding {
+        keys: &[(NONE, KeyCode::Esc)],
+        action: Action::Quit,
+    },
+    Binding {
+        keys: &[
+            (NONE, KeyCode::Char('/')),
+            (NONE, KeyCode::Tab),
+            (CTRL, KeyCode::Char('f')),
+        ],
+        action: Action::FocusFilter,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('s')), (NONE, KeyCode::Char('S'))],
+        action: Action::OpenSettings,
+    },
+    Binding {
+        keys: &[
+            (NONE, KeyCode::Char('l')),
+            (NONE, KeyCode::Char('L')),
+            (NONE, KeyCode::Char('f')),
+            (NONE, KeyCode::Char('F')),
+        ],
+        action: Action::ToggleLive,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('r')), (NONE, KeyCode::Char('R'))],
+        action: Action::Reload,
+    },
+    // Before `p`, which matches with any modifier.
+    Binding {
+        keys: &[(CTRL, KeyCode::Char('p')), (CTRL, KeyCode::Char('P'))],
+        action: Action::CommandPalette,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('p')), (NONE, KeyCode::Char('P'))],
+        action: Action::Share,
+    },
+    // Before `i`, which matches with any modifier. Most terminals send Ctrl+I as Tab.
+    Binding {
+        keys: &[(CTRL, KeyCode::Char('o')), (CTRL, KeyCode::Char('O'))],
+        action: Action::JumpBack,
+    },
+    Binding {
+        keys: &[(CTRL, KeyCode::Char('i')), (CTRL, KeyCode::Char('I'))],
+        action: Action::JumpForward,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('i')), (NONE, KeyCode::Char('I'))],
+        action: Action::ToggleInvisibles,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('?'))],
+        action: Action::Help,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char(':'))],
+        action: Action::GotoLine,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('%'))],
+        action: Action::GotoPercent,
+    },
+    // Before `y`, which matches with any modifier.
+    Binding {
+        keys: &[(CTRL, KeyCode::Char('y')), (CTRL, KeyCode::Char('Y'))],
+        action: Action::CopyPath,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('y')), (NONE, KeyCode::Char('Y'))],
+        action: Action::CopyScreen,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('w')), (NONE, KeyCode::Char('W'))],
+        action: Action::PickToken,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('*'))],
+        action: Action::Find,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('n'))],
+        action: Action::FindNext,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('N'))],
+        action: Action::FindPrevious,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('v')), (NONE, KeyCode::Char('V'))],
+        action: Action::SplitView,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('d')), (NONE, KeyCode::Char('D'))],
+        action: Action::ToggleDense,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('t')), (NONE, KeyCode::Char('T'))],
+        action: Action::ToggleColumns,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Char('c')), (NONE, KeyCode::Char('C'))],
+        action: Action::CycleAccent,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Up), (NONE, KeyCode::Char('k'))],
+        action: Action::Up,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Down), (NONE, KeyCode::Char('j'))],
+        action: Action::Down,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::PageUp)],
+        action: Action::PageUp,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::PageDown)],
+        action: Action::PageDown,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::Home), (NONE, KeyCode::Char('g'))],
+        action: Action::First,
+    },
+    Binding {
+        keys: &[(NONE, KeyCode::End), (NONE, KeyCode::Char('G'))],
+        action: Action::Last,
+    },
+];
+
+/// Help rows for the filter input, which edits text rather than dispatching actions.
+pub fn filter_help() -> [(&'static str, &'static str); 8] {
+    [
+        ("Enter, Tab", t().filter_help_back),
+        ("Esc", t().filter_help_clear),
+        ("←, →", t().filter_help_move),
+        ("Alt+←, Alt+→, Alt+B, Alt+F", t().filter_help_move_word),
+        ("Home, End, Ctrl+A, Ctrl+E", t().filter_help_start_end),
+        ("Backspace", t().filter_help_delete),
+        ("Ctrl+W", t().filter_help_delete_word),
+        ("Ctrl+U", t().filter_help_delete_to_start),
+    ]
+}
+
+/// Look up the action bound to `key` in `bindings`.
+pub fn action_for(bindings: &[Binding], key: &KeyEvent) -> Option<Action> {
+    bindings
+        .iter()
+        .find(|b| {
+            b.keys.iter().any(|&(mods, code)| {
+                code == key.code && (mods.is_empty() || key.modifiers.contains(mods))
+            })
+        })
+        .map(|b| b.action)
+}
+
+/// Whether `key` is pressed with Ctrl or Alt; Shift alone does not count.
+pub fn has_modifier(key: &KeyEvent) -> bool {
+    key.modifiers
+        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+}
+
+fn key_label(mods: KeyModifiers, code: KeyCode) -> String {
+    let name = match code {
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        other => other.to_string(),
+    };
+    if mods.contains(KeyModifiers::CONTROL) {
+        format!("Ctrl+{}", name.to_uppercase())
+    } else {
+        name
+    }
+}
+
+/// Comma-separated labels for a binding, collapsing duplicates like Ctrl+c / Ctrl+C.
+pub fn binding_label(binding: &Binding) -> String {
+    let mut labels: Vec<String> = Vec::new();
+    for &(mods, code) in binding.keys {
+        let label = key_label(mods, code);
+        if !labels.contains(&label) {
+            labels.push(label);
+        }
+    }
+    labels.join(", ")
+}
+
+/// Bindings listed in the command palette: each action once, leaving out the palette itself,
+/// line-by-line movement and N% (which needs the number typed first).
+pub fn palette_bindings() -> Vec<&'static Binding> {
+    let mut out: Vec<&'static Binding> = Vec::new();
+    for b in GLOBAL_BINDINGS.iter().chain(LOG_LIST_BINDINGS) {
+        let skip = matches!(
+            b.action,
+            Action::CommandPalette
+                | Action::GotoPercent
+                | Action::Up
+                | Action::Down
+                | Action::PageUp
+                | Action::PageDown
+        );
+        if !skip && !out.iter().any(|o| o.action == b.action) {
+            out.push(b);
+        }
+    }
+    out
+}
+
+/// Help rows as `(keys, description)`, with section headings as rows with empty keys.
+pub fn help_rows() -> Vec<(String, String)> {
+    let mut rows = vec![(String::new(), t().help_log_list.to_string())];
+    for b in GLOBAL_BINDINGS.iter().chain(LOG_LIST_BINDINGS) {
+        rows.push((binding_label(b), b.action.description().to_string()));
+    }
+    rows.push((String::new(), String::new()));
+    rows.push((String::new(), t().help_filter.to_string()));
+    for (keys, desc) in filter_help() {
+        rows.push((keys.to_string(), desc.to_string()));
+    }
+    rows
+}
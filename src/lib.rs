@@ -0,0 +1,2185 @@
+//! ratlog: terminal log viewer with live filtering and tail-style follow.
+//!
+//! The binary is a thin wrapper over this library; other Ratatui apps can embed
+//! [`App`] or reuse the loading and filtering helpers directly.
+
+pub mod app;
+pub mod cli;
+pub mod constants;
+pub mod highlight;
+pub mod i18n;
+pub mod keymap;
+pub mod login;
+pub mod logs;
+pub mod prefix;
+pub mod redact;
+pub mod remote;
+pub mod settings;
+pub mod status;
+pub mod theme;
+pub mod util;
+
+pub use app::{App, AppConfig};
+pub use logs::{
+    FilterSpec, LineSep, LoadOptions, LogSource, SourceKind, apply_filter, apply_filter_spec,
+    filter_indices, load_logs, load_source,
+};
+pub use theme::{AccentColor, BorderColor, Focus, StatusColor, TextColor, TextStyle};
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{CliAction, ConfigAction, RunOptions, parse_args, profile_arg};
+    use crate::constants::{
+        DEFAULT_MAX_POLL_MS, DEFAULT_TAB_WIDTH, IDLE_POLLS_PER_STEP, MAX_LINE_LEN, MAX_LINES,
+        MAX_SHARE_BYTES, POLL_READ_CAP, TAIL_READ_SIZE,
+    };
+    use crate::highlight::{Token, find_pattern, find_tokens};
+    use crate::i18n::{self, Language};
+    use crate::keymap::{self, Action, GLOBAL_BINDINGS, LOG_LIST_BINDINGS};
+    use crate::login::{
+        SavedShare, ShareExpiry, ShareLogResponse, SharedLog, auth_header, check_share_size,
+        format_shares_table, is_transient_status, normalize_app_url, parse_shared_logs,
+        push_recent_share, share_id_from, share_limit, share_request_body,
+    };
+    use crate::logs::{
+        FilterSpec, LineSep, LoadOptions, LogSource, apply_entry_filter, apply_filter,
+        apply_filter_spec, column_filter, column_widths, columnize, decode_appended,
+        decode_appended_utf16, entry_starts, entry_step, filter_alternatives, is_blank,
+        json_record, leading_timestamp, line_level, line_matches, line_tokens, load_logs,
+        load_logs_with, load_source, newest_match, parse_log_content, parse_tail_lines, remote_log,
+        sample_logs, split_appended, split_appended_with, split_columns, starts_with_timestamp,
+        tail_numbers_relative, timestamp_len,
+    };
+    use crate::prefix::LinePrefix;
+    use crate::redact::{RedactRule, Redactor};
+    use crate::remote::{Fetched, RemoteSource, content_range, parse_ssh_output};
+    use crate::settings::{
+        PerFileSettings, SavedSettings, Settings, export_json, read_saved_from, settings_key,
+    };
+    use crate::status::{StatusField, StatusLayout, fit_status};
+    use crate::theme::{AccentColor, GutterColor};
+    use crate::util::{
+        MEMORY_UNAVAILABLE, RateMeter, centered_rect, context_window, current_process_memory,
+        delete_word_before, display_column, expand_path, expand_tabs, format_bytes, format_rate,
+        format_uptime, glob_match, is_valid_profile_name, next_grapheme, next_word_end,
+        percent_index, poll_interval, prev_grapheme, prev_word_start, profile_path,
+        resolve_config_dir, scrollbar_index, truncate_chars, truncate_middle, visible_segments,
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::layout::Rect;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    fn write_temp_file(name: &str, content: &[u8]) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("ratlog-test-{}-{}", std::process::id(), name));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn args(items: &[&str]) -> Vec<String> {
+        std::iter::once("ratlog")
+            .chain(items.iter().copied())
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_args_sources() {
+        assert!(matches!(
+            parse_args(&args(&[])),
+            CliAction::Run(LogSource::Sample, _)
+        ));
+        assert!(matches!(
+            parse_args(&args(&["-"])),
+            CliAction::Run(LogSource::Stdin, _)
+        ));
+        match parse_args(&args(&["app.log"])) {
+            CliAction::Run(LogSource::File(p), _) => assert_eq!(p, PathBuf::from("app.log")),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            parse_args(&args(&["login"])),
+            CliAction::Login { check: false }
+        ));
+        assert!(matches!(
+            parse_args(&args(&["login", "--check"])),
+            CliAction::Login { check: true }
+        ));
+        assert!(matches!(
+            parse_args(&args(&["https://example.com/app.log"])),
+            CliAction::Run(LogSource::Remote(RemoteSource::Http(_)), _)
+        ));
+    }
+
+    #[test]
+    fn test_remote_source_parse() {
+        assert_eq!(
+            RemoteSource::parse("HTTPS://example.com/app.log"),
+            Some(RemoteSource::Http(
+                "HTTPS://example.com/app.log".to_string()
+            ))
+        );
+        let ssh = |host: &str, port, path: &str| RemoteSource::Ssh {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        };
+        assert_eq!(
+            RemoteSource::parse("ssh://deploy@web1:2222/var/log/app.log"),
+            Some(ssh("deploy@web1", Some(2222), "/var/log/app.log"))
+        );
+        assert_eq!(
+            RemoteSource::parse("ssh://web1/~/logs/app.log"),
+            Some(ssh("web1", None, "logs/app.log"))
+        );
+        for url in [
+            "ssh://deploy@web1:2222/var/log/app.log",
+            "ssh://web1/~/logs/app.log",
+        ] {
+            assert_eq!(RemoteSource::parse(url).unwrap().to_string(), url);
+        }
+        for arg in [
+            "app.log",
+            "ftp://host/app.log",
+            "ssh://web1",
+            "ssh://web1:x/a.log",
+        ] {
+            assert_eq!(RemoteSource::parse(arg), None, "{arg}");
+        }
+        assert_eq!(
+            content_range("bytes 900-999/1000"),
+            Some((900, 999, Some(1000)))
+        );
+        assert_eq!(content_range("bytes 0-41/42"), Some((0, 41, Some(42))));
+        assert_eq!(content_range("bytes 0-41/*"), Some((0, 41, None)));
+        assert_eq!(content_range("bytes */1000"), None);
+        assert_eq!(content_range("items 0-1/2"), None);
+        let command = RemoteSource::ssh_command("web1", Some(22), "/var/log/it's.log", 150);
+        let args: Vec<_> = command.get_args().map(|a| a.to_string_lossy()).collect();
+        assert_eq!(
+            args,
+            [
+                "-p",
+                "22",
+                "web1",
+                r"wc -lc < '/var/log/it'\''s.log' && tail -n 150 -- '/var/log/it'\''s.log'"
+            ]
+        );
+        let command = RemoteSource::ssh_follow_command("web1", None, "/var/log/app.log", 4096);
+        let args: Vec<_> = command.get_args().map(|a| a.to_string_lossy()).collect();
+        assert_eq!(
+            args,
+            [
+                "-o",
+                "BatchMode=yes",
+                "web1",
+                "tail -c +4097 -F -- '/var/log/app.log'"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remote_log_numbering() {
+        let options = LoadOptions::default();
+        // `wc -lc` then `tail -n 2`: the last two of 1000 lines, the last one unterminated.
+        let fetched = parse_ssh_output(b"  999 52000\nline 999\nline 1000".to_vec()).unwrap();
+        assert_eq!(fetched.total_lines, Some(1000));
+        assert_eq!(fetched.size, 52000);
+        let log = remote_log(fetched, 2, options).unwrap();
+        assert_eq!(log.lines, ["line 999", "line 1000"]);
+        assert_eq!(
+            (log.file_line_start, log.relative, log.size),
+            (999, false, 52000)
+        );
+        let empty = parse_ssh_output(b"0 0\n".to_vec()).unwrap();
+        assert_eq!((empty.total_lines, empty.bytes.len()), (Some(0), 0));
+        assert_eq!(
+            parse_ssh_output(b"wc: a.log: No such file\n".to_vec()),
+            None
+        );
+
+        // The end of a bigger HTTP log: its first, cut line is dropped and the rest numbered
+        // relative to it.
+        let partial = Fetched {
+            bytes: b"ut line\nnext\nlast\n".to_vec(),
+            partial: true,
+            total_lines: None,
+            size: 9000,
+        };
+        let log = remote_log(partial, MAX_LINES, options).unwrap();
+        assert_eq!(log.lines, ["next", "last"]);
+        assert_eq!(
+            (log.file_line_start, log.relative, log.size),
+            (1, true, 9000)
+        );
+        let whole = Fetched {
+            bytes: b"first\nsecond\n".to_vec(),
+            partial: false,
+            total_lines: None,
+            size: 13,
+        };
+        let log = remote_log(whole, MAX_LINES, options).unwrap();
+        assert_eq!(log.lines, ["first", "second"]);
+        assert_eq!((log.file_line_start, log.relative), (1, false));
+    }
+
+    #[test]
+    fn test_parse_args_no_mem() {
+        match parse_args(&args(&["--no-mem", "app.log"])) {
+            CliAction::Run(LogSource::File(p), options) => {
+                assert_eq!(p, PathBuf::from("app.log"));
+                assert!(options.no_mem);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            parse_args(&args(&[])),
+            CliAction::Run(_, RunOptions { no_mem: false, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_args_filter() {
+        match parse_args(&args(&["--filter", "ERROR", "app.log"])) {
+            CliAction::Run(LogSource::File(p), options) => {
+                assert_eq!(p, PathBuf::from("app.log"));
+                assert_eq!(options.filter.as_deref(), Some("ERROR"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["--filter=a b"])) {
+            CliAction::Run(LogSource::Sample, options) => {
+                assert_eq!(options.filter.as_deref(), Some("a b"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            parse_args(&args(&["app.log"])),
+            CliAction::Run(_, RunOptions { filter: None, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_args_all() {
+        assert!(matches!(
+            parse_args(&args(&["--all", "app.log"])),
+            CliAction::Run(LogSource::File(_), RunOptions { all: true, .. })
+        ));
+        assert!(matches!(
+            parse_args(&args(&["app.log"])),
+            CliAction::Run(_, RunOptions { all: false, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_args_follow() {
+        for flag in ["-f", "--follow"] {
+            match parse_args(&args(&[flag, "app.log"])) {
+                CliAction::Run(LogSource::File(p), options) => {
+                    assert_eq!(p, PathBuf::from("app.log"));
+                    assert!(options.follow);
+                }
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+        assert!(matches!(
+            parse_args(&args(&["app.log"])),
+            CliAction::Run(_, RunOptions { follow: false, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_args_inline() {
+        assert!(matches!(
+            parse_args(&args(&["--inline", "app.log"])),
+            CliAction::Run(LogSource::File(_), RunOptions { inline: true, .. })
+        ));
+        assert!(matches!(
+            parse_args(&args(&["app.log"])),
+            CliAction::Run(_, RunOptions { inline: false, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_args_cat() {
+        match parse_args(&args(&["--cat", "-n", "20", "--line-numbers", "app.log"])) {
+            CliAction::Run(LogSource::File(p), options) => {
+                assert_eq!(p, PathBuf::from("app.log"));
+                assert!(options.cat && options.line_numbers);
+                assert_eq!(options.lines, Some(20));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            parse_args(&args(&["--lines=5", "app.log"])),
+            CliAction::Run(
+                _,
+                RunOptions {
+                    lines: Some(5),
+                    cat: false,
+                    ..
+                }
+            )
+        ));
+        assert!(matches!(
+            parse_args(&args(&["-n", "lots", "app.log"])),
+            CliAction::Run(_, RunOptions { lines: None, .. })
+        ));
+    }
+
+    #[test]
+    fn test_output_json() {
+        assert!(matches!(
+            parse_args(&args(&["--output-json", "--filter", "error", "app.log"])),
+            CliAction::Run(
+                LogSource::File(_),
+                RunOptions {
+                    output_json: true,
+                    ..
+                }
+            )
+        ));
+        assert_eq!(
+            json_record(42, "ERROR \"db\"\ttimeout"),
+            r#"{"line_no":42,"text":"ERROR \"db\"\ttimeout"}"#
+        );
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1024), "1 KiB");
+        assert_eq!(format_bytes(1536), "1 KiB");
+        assert_eq!(format_bytes(1024 * 1024), "1.0 MiB");
+        assert_eq!(format_bytes(1024 * 1024 * 1024), "1.0 GiB");
+        assert_eq!(format_bytes(1536 * 1024 * 1024), "1.5 GiB");
+    }
+
+    #[test]
+    fn test_memory_fallback_is_not_mojibake() {
+        assert_eq!(MEMORY_UNAVAILABLE, "\u{2014}");
+        assert!(!current_process_memory().contains("â€"));
+    }
+
+    #[test]
+    fn test_parse_log_content_empty() {
+        let (lines, offset, start) = parse_log_content("");
+        assert!(lines.is_empty());
+        assert_eq!(offset, 0);
+        assert_eq!(start, 1);
+    }
+
+    #[test]
+    fn test_parse_log_content_one_line() {
+        let (lines, offset, start) = parse_log_content("hello\n");
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0], "hello");
+        assert_eq!(offset, 0);
+        assert_eq!(start, 1);
+    }
+
+    #[test]
+    fn test_parse_log_content_last_max_lines() {
+        let n = MAX_LINES + 50;
+        let content = (0..n)
+            .map(|i| format!("line {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let (lines, _offset, start) = parse_log_content(&content);
+        assert_eq!(lines.len(), MAX_LINES);
+        assert_eq!(start, 51);
+        assert_eq!(lines[0], "line 50");
+        assert_eq!(lines[MAX_LINES - 1], format!("line {}", n - 1));
+    }
+
+    #[test]
+    fn test_parse_log_content_trailing_newline_does_not_change_count() {
+        for content in ["a\nb\nc", "a\nb\nc\n"] {
+            let (lines, offset, start) = parse_log_content(content);
+            assert_eq!(lines, vec!["a", "b", "c"], "{content:?}");
+            assert_eq!((offset, start), (0, 1));
+        }
+        // A blank last line is still a line, as `wc -l` counts it.
+        assert_eq!(parse_log_content("a\n\n").0, vec!["a", ""]);
+    }
+
+    #[test]
+    fn test_parse_log_content_crlf_offset() {
+        let content = (0..MAX_LINES + 2)
+            .map(|i| format!("{}\r\n", i))
+            .collect::<String>();
+        let (lines, offset, start) = parse_log_content(&content);
+        assert_eq!(start, 3);
+        assert_eq!(lines[0], "2");
+        assert_eq!(&content[offset as usize..offset as usize + 3], "2\r\n");
+    }
+
+    #[test]
+    fn test_load_logs_last_line_number_with_and_without_trailing_newline() {
+        let n = MAX_LINES + 5;
+        let body = (1..=n)
+            .map(|i| format!("line {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        for (name, content) in [
+            ("no-trailing-nl.log", body.clone()),
+            ("trailing-nl.log", format!("{}\n", body)),
+        ] {
+            let path = write_temp_file(name, content.as_bytes());
+            let (lines, _, offset, start) = load_logs(Some(path.clone())).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(lines.len(), MAX_LINES, "{name}");
+            assert_eq!(start, 6, "{name}");
+            // The last kept line's displayed number is the file's line count.
+            assert_eq!(start + lines.len() - 1, n, "{name}");
+            assert_eq!(lines[lines.len() - 1], format!("line {}", n), "{name}");
+            // Live mode continues after the last line, not from the first kept one.
+            assert_eq!(offset, content.len() as u64, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_load_logs_offset_counts_bom_crlf_and_long_lines() {
+        let long = "x".repeat(MAX_LINE_LEN + 100);
+        let mut content = "\u{feff}first\r\n".to_string();
+        content.push_str(&format!("{}\n", long));
+        for i in 3..=MAX_LINES + 3 {
+            content.push_str(&format!("line {}\r\n", i));
+        }
+        let path = write_temp_file("offset-single-pass.log", content.as_bytes());
+        let (lines, _, offset, start) = load_logs(Some(path.clone())).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(start, 4);
+        assert_eq!(lines[0], "line 4");
+        // Every byte read counts, BOM, `\r` and the cut part of the long line included.
+        assert_eq!(offset, content.len() as u64);
+    }
+
+    #[test]
+    fn test_parse_tail_lines_truncates_multibyte_on_char_boundary() {
+        // "a" shifts the two-byte chars so MAX_LINE_LEN lands inside one.
+        let long = format!("a{}", "ğ".repeat(MAX_LINE_LEN));
+        let content = format!("partial\n{}\nshort\n", long);
+        let lines = parse_tail_lines(content.as_bytes(), MAX_LINE_LEN, LineSep::Auto);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("ğ..."));
+        assert_eq!(lines[0].len(), MAX_LINE_LEN - 1 + 3);
+        assert_eq!(lines[1], "short");
+    }
+
+    #[test]
+    fn test_raw_load_keeps_long_lines_whole() {
+        let long = "x".repeat(MAX_LINE_LEN * 2);
+        let content = format!("{}\nshort\n", long);
+        let path = write_temp_file("raw-long.log", content.as_bytes());
+        let (cut, _, _, _) = load_logs(Some(path.clone())).unwrap();
+        let raw = LoadOptions {
+            raw: true,
+            ..LoadOptions::default()
+        };
+        let (whole, _, _, _) = load_logs_with(Some(path.clone()), raw).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(cut[0].len(), MAX_LINE_LEN);
+        assert_eq!(whole, vec![long.clone(), "short".to_string()]);
+        let lines = parse_tail_lines(
+            format!("partial\n{}\n", long).as_bytes(),
+            usize::MAX,
+            LineSep::Auto,
+        );
+        assert_eq!(lines, vec![long]);
+    }
+
+    #[test]
+    fn test_load_logs_crlf_strips_carriage_returns() {
+        let path = write_temp_file("crlf.log", b"first\r\nsecond\r\nthird\r\n");
+        let (lines, _, _, start) = load_logs(Some(path.clone())).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(lines, vec!["first", "second", "third"]);
+        assert!(lines.iter().all(|l| !l.contains('\r')));
+        assert_eq!(start, 1);
+    }
+
+    #[test]
+    fn test_tail_read_numbers_lines_like_the_file() {
+        // Over TAIL_READ_SIZE, with blank lines that must not shift the numbers.
+        let text = |i: usize| {
+            if i.is_multiple_of(100) {
+                String::new()
+            } else {
+                format!("line {i}")
+            }
+        };
+        let total = TAIL_READ_SIZE as usize / 8;
+        let content: String = (1..=total).map(|i| format!("{}\n", text(i))).collect();
+        assert!(content.len() as u64 > TAIL_READ_SIZE);
+        let path = write_temp_file("tail-numbers.log", content.as_bytes());
+        let (lines, _, offset, start) = load_logs(Some(path.clone())).unwrap();
+        assert!(!tail_numbers_relative(&path, LoadOptions::default()));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(offset, content.len() as u64);
+        assert_eq!(lines.len(), MAX_LINES);
+        assert_eq!(start, total - MAX_LINES + 1);
+        for (k, line) in lines.iter().enumerate() {
+            assert_eq!(*line, text(start + k));
+        }
+    }
+
+    #[test]
+    fn test_line_sep_each_separator() {
+        let load = |name: &str, content: &[u8], line_sep: LineSep| {
+            let path = write_temp_file(name, content);
+            let options = LoadOptions {
+                line_sep,
+                ..LoadOptions::default()
+            };
+            let (lines, _, offset, start) = load_logs_with(Some(path.clone()), options).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            (lines, offset, start)
+        };
+        // lf keeps a `\r` before the newline; crlf and auto drop it.
+        let crlf = b"a\r\nb\r\n";
+        assert_eq!(load("sep-lf.log", crlf, LineSep::Lf).0, ["a\r", "b\r"]);
+        assert_eq!(load("sep-crlf.log", crlf, LineSep::Crlf).0, ["a", "b"]);
+        assert_eq!(load("sep-auto.log", crlf, LineSep::Auto).0, ["a", "b"]);
+        // A CR-only file is one line unless split at `\r`.
+        let cr = b"one\rtwo\rthree\r";
+        assert_eq!(
+            load("sep-cr-lf.log", cr, LineSep::Lf).0,
+            ["one\rtwo\rthree\r"]
+        );
+        assert_eq!(
+            load("sep-cr.log", cr, LineSep::Cr).0,
+            ["one", "two", "three"]
+        );
+        assert_eq!(
+            load("sep-cr-auto.log", cr, LineSep::Auto).0,
+            ["one", "two", "three"]
+        );
+        // Line numbers count `\r`-terminated lines too.
+        let many: String = (1..=MAX_LINES + 2).map(|i| format!("l{}\r", i)).collect();
+        let (lines, offset, start) = load("sep-cr-many.log", many.as_bytes(), LineSep::Auto);
+        assert_eq!(lines.len(), MAX_LINES);
+        assert_eq!(start, 3);
+        assert_eq!(offset, many.len() as u64);
+        // A file with newlines keeps lone `\r`s (progress output) inside its lines.
+        assert_eq!(
+            load("sep-mixed.log", b"10%\r50%\rdone\nnext\n", LineSep::Auto).0,
+            ["10%\r50%\rdone", "next"]
+        );
+
+        assert_eq!(LineSep::Auto.detect(b"a\rb\r"), LineSep::Cr);
+        assert_eq!(LineSep::Auto.detect(b"a\r\nb"), LineSep::Crlf);
+        assert_eq!(LineSep::Auto.detect(b""), LineSep::Crlf);
+        assert_eq!(LineSep::Lf.detect(b"a\rb\r"), LineSep::Lf);
+        assert_eq!(LineSep::from_name("CR"), Some(LineSep::Cr));
+        assert_eq!(LineSep::from_name("nul"), None);
+        assert_eq!(
+            parse_tail_lines(b"tial\rx\ry\r", MAX_LINE_LEN, LineSep::Auto),
+            ["x", "y"]
+        );
+
+        let mut partial = String::new();
+        assert_eq!(
+            split_appended_with(&mut partial, "a\rb\rpa", LineSep::Cr),
+            ["a", "b"]
+        );
+        assert_eq!(
+            split_appended_with(&mut partial, "rt\r", LineSep::Cr),
+            ["part"]
+        );
+        assert_eq!(
+            split_appended_with(&mut partial, "x\r\ny\n", LineSep::Lf),
+            ["x\r", "y"]
+        );
+        assert_eq!(
+            split_appended_with(&mut partial, "x\r\ny\n", LineSep::Crlf),
+            ["x", "y"]
+        );
+    }
+
+    #[test]
+    fn test_parse_args_line_sep() {
+        match parse_args(&args(&["--line-sep", "cr", "app.log"])) {
+            CliAction::Run(LogSource::File(p), options) => {
+                assert_eq!(p, PathBuf::from("app.log"));
+                assert_eq!(options.line_sep(), LineSep::Cr);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["app.log"])) {
+            CliAction::Run(_, options) => assert_eq!(options.line_sep(), LineSep::Auto),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_newest_match() {
+        let dir = std::env::temp_dir().join(format!("ratlog-test-{}-glob", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let old = dir.join("app.log.2024-06-01");
+        let new = dir.join("app.log.2024-06-02");
+        std::fs::write(&old, "old\n").unwrap();
+        std::fs::write(&new, "new\n").unwrap();
+        std::fs::write(dir.join("other.log"), "x\n").unwrap();
+        let set_time = |path: &PathBuf, secs: u64| {
+            let file = std::fs::File::options().write(true).open(path).unwrap();
+            let time = std::time::SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+            file.set_modified(time).unwrap();
+        };
+        let pattern = format!("{}/app.log.*", dir.display());
+        set_time(&old, 2_000_000_000);
+        set_time(&new, 1_000_000_000);
+        assert_eq!(newest_match(&pattern).unwrap(), Some(old.clone()));
+        set_time(&old, 1_000_000_000);
+        // Same time: the greater name wins.
+        assert_eq!(newest_match(&pattern).unwrap(), Some(new.clone()));
+        let (lines, path, _, _) =
+            load_source(LogSource::Glob(pattern), LoadOptions::default()).unwrap();
+        assert_eq!(lines, ["new"]);
+        assert_eq!(path, Some(new));
+        let missing = format!("{}/none.*", dir.display());
+        assert_eq!(newest_match(&missing).unwrap(), None);
+        let err = load_source(LogSource::Glob(missing), LoadOptions::default()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_parse_args_glob() {
+        match parse_args(&args(&["--glob", "logs/app.log.*", "--raw"])) {
+            CliAction::Run(LogSource::Glob(pattern), options) => {
+                assert_eq!(pattern, "logs/app.log.*");
+                assert_eq!(options.glob.as_deref(), Some("logs/app.log.*"));
+                assert!(options.raw);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            parse_args(&args(&["app.log", "--glob=*.log"])),
+            CliAction::Run(LogSource::Glob(_), _)
+        ));
+    }
+
+    #[test]
+    fn test_parse_args_theme() {
+        match parse_args(&args(&["--theme", "app.log"])) {
+            CliAction::Run(LogSource::Sample, options) => {
+                assert_eq!(options.theme.as_deref(), Some("app.log"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_args(&args(&["app.log", "--theme=Mono"])) {
+            CliAction::Run(LogSource::File(_), options) => {
+                assert_eq!(options.theme.as_deref(), Some("Mono"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_theme_presets() {
+        use crate::theme::ThemePreset;
+        assert_eq!(ThemePreset::find("MONO").map(|p| p.name), Some("mono"));
+        assert!(ThemePreset::find("nope").is_none());
+        assert!(ThemePreset::names().starts_with("default, "));
+        // "default" matches a fresh configuration.
+        assert_eq!(
+            ThemePreset::find("default").map(|p| p.colors),
+            Some(crate::settings::Settings::default().colors())
+        );
+        let mut settings = crate::settings::Settings::default();
+        let neon = ThemePreset::find("neon").unwrap();
+        settings.set_colors(neon.colors);
+        assert_eq!(settings.colors(), neon.colors);
+        assert_eq!(settings.accent_color, AccentColor::Magenta);
+    }
+
+    #[test]
+    fn test_load_logs_with_line_count() {
+        let content: String = (1..=MAX_LINES + 50)
+            .map(|i| format!("line {i}\n"))
+            .collect();
+        let path = write_temp_file("lines.log", content.as_bytes());
+        let load = |n| {
+            let options = LoadOptions {
+                lines: Some(n),
+                ..LoadOptions::default()
+            };
+            let (lines, _, _, start) = load_logs_with(Some(path.clone()), options).unwrap();
+            (lines.len(), start)
+        };
+        assert_eq!(load(3), (3, MAX_LINES + 48));
+        assert_eq!(load(MAX_LINES + 10), (MAX_LINES + 10, 41));
+        assert_eq!(load(10_000), (MAX_LINES + 50, 1));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_load_logs_with_all_keeps_every_line() {
+        let content: String = (1..=MAX_LINES + 50)
+            .map(|i| format!("line {i}\n"))
+            .collect();
+        let path = write_temp_file("all.log", content.as_bytes());
+        let (capped, _, _, capped_start) = load_logs(Some(path.clone())).unwrap();
+        let (all, _, offset, start) = load_logs_with(
+            Some(path.clone()),
+            LoadOptions {
+                all: true,
+                ..LoadOptions::default()
+            },
+        )
+        .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((capped.len(), capped_start), (MAX_LINES, 51));
+        assert_eq!(all.len(), MAX_LINES + 50);
+        assert_eq!(all[0], "line 1");
+        assert_eq!((offset, start), (content.len() as u64, 1));
+    }
+
+    #[test]
+    fn test_load_logs_strips_utf8_bom() {
+        let content = b"\xEF\xBB\xBFfirst\nsecond\n";
+        let path = write_temp_file("bom8.log", content);
+        let (lines, _, offset, start) = load_logs(Some(path.clone())).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(lines, vec!["first", "second"]);
+        assert_eq!((offset, start), (content.len() as u64, 1));
+    }
+
+    #[test]
+    fn test_load_logs_decodes_utf16_with_bom() {
+        let text = "héllo\r\nwörld ✓\r\n";
+        let mut le = vec![0xFF, 0xFE];
+        le.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        let mut be = vec![0xFE, 0xFF];
+        be.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+        for (name, bytes) in [("bom16le.log", le), ("bom16be.log", be)] {
+            let size = bytes.len() as u64;
+            let path = write_temp_file(name, &bytes);
+            let (lines, _, offset, start) = load_logs(Some(path.clone())).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(lines, vec!["héllo", "wörld ✓"], "{}", name);
+            assert_eq!((offset, start), (size, 1), "{}", name);
+        }
+        // Long lines are cut like UTF-8 ones.
+        let mut long = vec![0xFF, 0xFE];
+        let text = format!("{}\nshort\n", "é".repeat(MAX_LINE_LEN));
+        long.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        let path = write_temp_file("bom16long.log", &long);
+        let (lines, ..) = load_logs(Some(path.clone())).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(lines[0].len() <= MAX_LINE_LEN + 3 && lines[0].ends_with("..."));
+        assert_eq!(lines[1], "short");
+    }
+
+    #[test]
+    fn test_decode_appended_utf16_across_reads() {
+        let text = "a😀é\nb";
+        for big_endian in [false, true] {
+            let bytes: Vec<u8> = text
+                .encode_utf16()
+                .flat_map(|u| {
+                    if big_endian {
+                        u.to_be_bytes()
+                    } else {
+                        u.to_le_bytes()
+                    }
+                })
+                .collect();
+            // Cut between the bytes of a unit and between the halves of the surrogate pair.
+            for cut in 1..bytes.len() {
+                let mut pending = Vec::new();
+                let mut s = decode_appended_utf16(&mut pending, &bytes[..cut], big_endian);
+                s.push_str(&decode_appended_utf16(
+                    &mut pending,
+                    &bytes[cut..],
+                    big_endian,
+                ));
+                assert_eq!(s, text, "cut at {}", cut);
+                assert!(pending.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_appended_crlf_and_partial() {
+        let mut partial = String::new();
+        let lines = split_appended(&mut partial, "a\r\nb\r\npart");
+        assert_eq!(lines, vec!["a", "b"]);
+        assert_eq!(partial, "part");
+        let lines = split_appended(&mut partial, "ial\r\n");
+        assert_eq!(lines, vec!["partial"]);
+        assert!(partial.is_empty());
+    }
+
+    #[test]
+    fn test_blank_lines_same_when_loaded_or_appended() {
+        let content = "a\n\n  \nb\n";
+        let (loaded, _, _) = parse_log_content(content);
+        assert_eq!(loaded, ["a", "", "  ", "b"]);
+        // Appended in one read, byte by byte, or with the blank line arriving on its own.
+        let mut partial = String::new();
+        assert_eq!(split_appended(&mut partial, content), loaded);
+        let mut got = Vec::new();
+        for i in 0..content.len() {
+            got.extend(split_appended(&mut partial, &content[i..i + 1]));
+        }
+        assert_eq!(got, loaded);
+        assert_eq!(split_appended(&mut partial, "a\n"), ["a"]);
+        assert_eq!(split_appended(&mut partial, "\n"), [""]);
+        assert_eq!(split_appended(&mut partial, "\r\nb"), [""]);
+        assert_eq!(partial, "b");
+    }
+
+    #[test]
+    fn test_decode_appended_burst_over_read_cap() {
+        // Reads cut at POLL_READ_CAP land inside lines and inside two-byte characters.
+        let lines: Vec<String> = (0..80_000).map(|i| format!("{} çğü line", i)).collect();
+        let content = lines.iter().map(|l| format!("{}\n", l)).collect::<String>();
+        assert!(content.len() > 2 * POLL_READ_CAP);
+        let (mut pending, mut partial, mut got) = (Vec::new(), String::new(), Vec::new());
+        for chunk in content.as_bytes().chunks(POLL_READ_CAP) {
+            let s = decode_appended(&mut pending, chunk);
+            got.extend(split_appended(&mut partial, &s));
+        }
+        assert!(pending.is_empty() && partial.is_empty());
+        assert_eq!(got, lines);
+
+        let mut pending = Vec::new();
+        assert_eq!(decode_appended(&mut pending, &[b'a', 0xC3]), "a");
+        assert_eq!(pending, vec![0xC3]);
+        assert_eq!(
+            decode_appended(&mut pending, &[0xA7, 0xFF, b'b']),
+            "ç\u{fffd}b"
+        );
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_apply_filter_empty_query_returns_all() {
+        let lines = vec!["a".into(), "b".into(), "c".into()];
+        let out = apply_filter(&lines, "", 10);
+        assert_eq!(out.len(), 3);
+        assert_eq!(out[0], (0, "a".to_string()));
+        assert_eq!(out[1], (1, "b".to_string()));
+        assert_eq!(out[2], (2, "c".to_string()));
+    }
+
+    #[test]
+    fn test_apply_filter_matching_case_insensitive() {
+        let lines = vec!["INFO foo".into(), "ERROR bar".into(), "info baz".into()];
+        let out = apply_filter(&lines, "info", 10);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0], (0, "INFO foo".to_string()));
+        assert_eq!(out[1], (2, "info baz".to_string()));
+    }
+
+    #[test]
+    fn test_apply_filter_number_does_not_match_gutter() {
+        // Shown with gutter numbers 1..=3, but only the content is searched.
+        let lines = vec!["alpha".into(), "beta".into(), "took 2ms".into()];
+        let out = apply_filter(&lines, "2", 10);
+        assert_eq!(out, vec![(2, "took 2ms".to_string())]);
+        assert!(apply_filter(&lines, "1", 10).is_empty());
+    }
+
+    #[test]
+    fn test_apply_filter_separator_does_not_match_gutter() {
+        // Every row is drawn as `     N │ text`, yet none of that prefix is in the lines.
+        let lines: Vec<String> = vec!["alpha".into(), "    indented beta".into(), "gamma".into()];
+        let gutter = LinePrefix::default().render(2, &lines[1], false);
+        for filter in ["│", gutter.as_str(), "2 │", "┆"] {
+            assert!(apply_filter(&lines, filter, 10).is_empty(), "{filter:?}");
+            assert!(
+                apply_entry_filter(&lines, filter, 10).is_empty(),
+                "{filter:?}"
+            );
+        }
+        // Leading spaces of the filter are trimmed; those of the line are content.
+        assert_eq!(apply_filter(&lines, "  indented", 10).len(), 1);
+        assert!(apply_filter(&lines, "│ indented", 10).is_empty());
+        let lines = vec!["table │ row".to_string()];
+        assert_eq!(apply_filter(&lines, "│", 10).len(), 1);
+    }
+
+    #[test]
+    fn test_line_matches_agrees_with_filter() {
+        assert!(line_matches("ERROR disk full", " error "));
+        assert!(!line_matches("INFO ok", "error"));
+        assert!(!line_matches("anything", ""));
+        assert!(!line_matches("anything", "   "));
+    }
+
+    #[test]
+    fn test_starts_with_timestamp() {
+        assert!(starts_with_timestamp("2025-02-15T10:00:00Z INFO x"));
+        assert!(starts_with_timestamp("[2025/02/15 10:00:00] x"));
+        assert!(starts_with_timestamp("10:00:01.123 x"));
+        assert!(starts_with_timestamp("Feb  5 10:00:01 host x"));
+        assert!(!starts_with_timestamp(
+            "    at com.example.Main.run(Main.java:12)"
+        ));
+        assert!(!starts_with_timestamp("Caused by: java.io.IOException"));
+        assert!(!starts_with_timestamp("2025 was a year"));
+        assert!(!starts_with_timestamp(""));
+    }
+
+    #[test]
+    fn test_entry_filter_keeps_whole_stack_trace() {
+        let lines: Vec<String> = [
+            "stray preamble",
+            "2025-02-15 10:00:00 INFO started",
+            "2025-02-15 10:00:01 ERROR request failed",
+            "java.lang.IllegalStateException: boom",
+            "    at com.example.Main.run(Main.java:12)",
+            "2025-02-15 10:00:02 INFO recovered",
+        ]
+        .map(String::from)
+        .to_vec();
+        assert_eq!(entry_starts(&lines), vec![0, 1, 2, 2, 2, 5]);
+        // A match on a continuation line keeps the timestamped line and the rest of the trace.
+        let out: Vec<usize> = apply_entry_filter(&lines, "Main.java", 10)
+            .into_iter()
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(out, vec![2, 3, 4]);
+        assert_eq!(apply_entry_filter(&lines, "", 10).len(), lines.len());
+        assert_eq!(apply_entry_filter(&lines, "Main.java", 2).len(), 2);
+    }
+
+    #[test]
+    fn test_entry_step_moves_by_entry() {
+        let rows = [0, 1, 1, 1, 4, 5, 5];
+        assert_eq!(entry_step(&rows, 0, true), 1);
+        assert_eq!(entry_step(&rows, 1, true), 4);
+        assert_eq!(entry_step(&rows, 2, true), 4);
+        assert_eq!(entry_step(&rows, 5, true), 5);
+        assert_eq!(entry_step(&rows, 6, false), 5);
+        assert_eq!(entry_step(&rows, 5, false), 4);
+        assert_eq!(entry_step(&rows, 4, false), 1);
+        assert_eq!(entry_step(&rows, 1, false), 0);
+        assert_eq!(entry_step(&rows, 0, false), 0);
+        assert_eq!(entry_step(&[], 0, true), 0);
+    }
+
+    #[test]
+    fn test_filter_alternatives_or_and_escape() {
+        assert_eq!(
+            filter_alternatives("ERROR|timeout"),
+            vec!["ERROR", "timeout"]
+        );
+        assert_eq!(filter_alternatives(" a | b |"), vec!["a", "b"]);
+        assert_eq!(filter_alternatives(r"a\|b"), vec!["a|b"]);
+        assert_eq!(filter_alternatives(r"a\b"), vec![r"a\b"]);
+        assert!(filter_alternatives("|").is_empty());
+
+        assert!(line_matches("request timeout after 30s", "ERROR|timeout"));
+        assert!(line_matches("ERROR disk full", "error|timeout"));
+        assert!(!line_matches("INFO ok", "error|timeout"));
+        assert!(!line_matches("INFO ok", "|"));
+        assert!(!line_matches("a or b", r"a\|b"));
+        assert!(line_matches("cmd: a|b", r"a\|b"));
+        assert!(line_matches("x WARN y", "col2:error|warn"));
+        assert!(!line_matches("warn x y", "col2:error|warn"));
+    }
+
+    #[test]
+    fn test_filter_spec_parse() {
+        assert!(FilterSpec::parse("").is_empty());
+        assert!(FilterSpec::parse(" | ").is_empty());
+        let spec = FilterSpec::parse(" col2:error|WARN ");
+        assert_eq!(spec.column, Some(2));
+        assert_eq!(spec.include, vec!["error", "WARN"]);
+        assert!(!spec.case_sensitive && !spec.is_empty());
+        // `col3:` alone keeps lines that have a third field.
+        let spec = FilterSpec::parse("col3:");
+        assert!(spec.include.is_empty() && !spec.is_empty());
+        assert!(spec.matches("a b c") && !spec.matches("a b"));
+    }
+
+    #[test]
+    fn test_filter_spec_regex() {
+        let spec = FilterSpec::try_parse(r"re:user=\d+ (login|logout)").unwrap();
+        assert!(spec.regex.is_some() && spec.include.is_empty());
+        assert!(spec.matches("INFO USER=42 Login ok"));
+        assert!(!spec.matches("INFO user=bob login"));
+        // Whole-field matching after `colN:`.
+        let spec = FilterSpec::try_parse("col2:RE:^(error|warn)$").unwrap();
+        assert!(spec.matches("x WARN y") && !spec.matches("x WARNING y"));
+        assert!(FilterSpec::try_parse("re:").unwrap().is_empty());
+        // A pattern that doesn't compile is an error, and `parse` matches it as text.
+        assert!(FilterSpec::try_parse("re:timeout (").is_err());
+        let spec = FilterSpec::parse("re:timeout (");
+        assert!(spec.regex.is_none());
+        assert!(spec.matches("auth timeout (5s)") && !spec.matches("auth timeout"));
+        // Patterns that would compile to a huge automaton are refused, not built.
+        let err = FilterSpec::try_parse(r"re:(\w{100}){100}").unwrap_err();
+        assert!(matches!(err, regex::Error::CompiledTooBig(_)), "{err}");
+        assert!(FilterSpec::try_parse(r"re:\w{20}").is_ok());
+    }
+
+    #[test]
+    fn test_filter_spec_include_exclude_and_case() {
+        let mut spec = FilterSpec {
+            include: vec!["Timeout".into(), "refused".into()],
+            exclude: vec!["healthcheck".into()],
+            ..FilterSpec::default()
+        };
+        assert!(spec.matches("ERROR timeout calling auth"));
+        assert!(spec.matches("connection REFUSED"));
+        assert!(!spec.matches("timeout in /healthcheck"));
+        assert!(!spec.matches("INFO ok"));
+        spec.case_sensitive = true;
+        assert!(spec.matches("Timeout calling auth"));
+        assert!(!spec.matches("timeout calling auth"));
+        assert!(spec.matches("timeout in /HealthCheck refused"));
+
+        // Exclude terms alone keep everything else.
+        let spec = FilterSpec {
+            exclude: vec!["debug".into()],
+            ..FilterSpec::default()
+        };
+        assert!(spec.matches("INFO ready") && !spec.matches("DEBUG poll"));
+    }
+
+    #[test]
+    fn test_filter_spec_column_level_and_time() {
+        let lines: Vec<String> = [
+            "2025-02-15T10:00:00Z INFO  Server started",
+            "2025-02-15T10:00:10Z ERROR cache refused",
+            "    at Cache.connect",
+            "2025-02-15T10:00:15Z ERROR auth timeout",
+            "2025-02-15T10:00:25Z WARN  rate limit for 10.0.0.1",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let indices = |spec: &FilterSpec| -> Vec<usize> {
+            apply_filter_spec(&lines, spec, 10)
+                .into_iter()
+                .map(|(i, _)| i)
+                .collect()
+        };
+        let levels = FilterSpec {
+            levels: vec!["error".into(), "warn".into()],
+            ..FilterSpec::default()
+        };
+        assert_eq!(indices(&levels), vec![1, 3, 4]);
+        let time = FilterSpec {
+            time_range: Some(("2025-02-15T10:00:05".into(), "2025-02-15T10:00:20".into())),
+            ..FilterSpec::default()
+        };
+        assert_eq!(indices(&time), vec![1, 3]);
+        let combined = FilterSpec {
+            include: vec!["timeout".into(), "limit".into()],
+            column: Some(3),
+            levels: vec!["ERROR".into()],
+            ..time.clone()
+        };
+        assert!(combined.matches("2025-02-15T10:00:15Z ERROR timeout"));
+        assert_eq!(indices(&combined), Vec::<usize>::new());
+        let combined = FilterSpec {
+            column: None,
+            ..combined
+        };
+        assert_eq!(indices(&combined), vec![3]);
+        assert_eq!(indices(&FilterSpec::default()), vec![0, 1, 2, 3, 4]);
+        assert_eq!(apply_filter_spec(&lines, &levels, 2)[0].0, 3);
+    }
+
+    #[test]
+    fn test_column_filter_matches_only_that_field() {
+        assert_eq!(column_filter("col3:ERROR"), Some((3, "ERROR")));
+        assert_eq!(column_filter("COL12: x"), Some((12, "x")));
+        assert_eq!(column_filter("col0:x"), None);
+        assert_eq!(column_filter("col:x"), None);
+        assert_eq!(column_filter("colx:x"), None);
+        assert_eq!(column_filter("error"), None);
+
+        let lines = vec![
+            "2024-01-01 10:00:00 ERROR disk full".to_string(),
+            "2024-01-01 10:00:01 INFO retry after ERROR".to_string(),
+            "short line".to_string(),
+        ];
+        let out = apply_filter(&lines, "col3:error", 10);
+        assert_eq!(out, vec![(0, lines[0].clone())]);
+        assert!(apply_filter(&lines, "col9:error", 10).is_empty());
+        assert_eq!(apply_filter(&lines, "col3:", 10).len(), 2);
+        assert_eq!(apply_filter(&lines, "col0:error", 10).len(), 0);
+    }
+
+    #[test]
+    fn test_line_tokens_trims_punctuation() {
+        assert_eq!(
+            line_tokens("WARN  Rate limit approaching for client 192.168.1.1"),
+            vec![
+                "WARN",
+                "Rate",
+                "limit",
+                "approaching",
+                "for",
+                "client",
+                "192.168.1.1"
+            ]
+        );
+        assert_eq!(
+            line_tokens(r#"[req-42] user="bob", took (12ms): done."#),
+            vec!["req-42", "user=\"bob", "took", "12ms", "done"]
+        );
+        assert!(line_tokens("  -- ").contains(&"--"));
+        assert!(line_tokens(" ... ").is_empty());
+    }
+
+    #[test]
+    fn test_apply_filter_cap_max_lines() {
+        let lines: Vec<String> = (0..20).map(|i| format!("x {}", i)).collect();
+        let out = apply_filter(&lines, "x", 5);
+        assert_eq!(out.len(), 5);
+        assert_eq!(out[0].1, "x 15");
+        assert_eq!(out[4].1, "x 19");
+    }
+
+    #[test]
+    fn test_sample_logs_non_empty() {
+        let logs = sample_logs();
+        assert!(!logs.is_empty());
+        assert!(logs.len() <= MAX_LINES);
+        assert!(
+            logs[0].contains("INFO")
+                || logs[0].contains("DEBUG")
+                || logs[0].contains("WARN")
+                || logs[0].contains("ERROR")
+        );
+    }
+
+    #[test]
+    fn test_i18n_fill_and_language_codes() {
+        assert_eq!(i18n::fill("Error: {}", &["boom"]), "Error: boom");
+        assert_eq!(i18n::fill("{} / {}", &["a", "b"]), "a / b");
+        assert_eq!(i18n::fill("no args", &[]), "no args");
+        assert_eq!(Language::from_code("TR"), Some(Language::Tr));
+        assert_eq!(Language::from_code("de"), None);
+        assert_eq!(i18n::strings(Language::En).shared, "Shared!");
+        assert_eq!(i18n::strings(Language::Tr).shared, "Paylaşıldı!");
+        let tr = i18n::strings(Language::Tr);
+        assert_eq!(i18n::fill(tr.status_filter, &["x"]), "Filtre: \"x\"");
+        assert_eq!(
+            i18n::fill(tr.log_file_not_found_expanded, &["/var/log/a", "~/a"]),
+            "Log dosyası bulunamadı: /var/log/a (~/a ifadesinden açıldı)"
+        );
+        assert_eq!(tr.action_goto_line, "Dosya satır numarasına git");
+        assert_eq!(
+            i18n::fill(tr.env_var_not_set, &["LOG_DIR"]),
+            "$LOG_DIR ortam değişkeni tanımlı değil"
+        );
+        assert_eq!(
+            i18n::fill(i18n::strings(Language::En).env_var_not_set, &["LOG_DIR"]),
+            "environment variable $LOG_DIR is not set"
+        );
+    }
+
+    #[test]
+    fn test_palette_bindings() {
+        let actions: Vec<Action> = keymap::palette_bindings()
+            .iter()
+            .map(|b| b.action)
+            .collect();
+        assert_eq!(actions.first(), Some(&Action::Quit));
+        assert_eq!(actions.iter().filter(|&&a| a == Action::Quit).count(), 1);
+        assert!(actions.contains(&Action::Share));
+        assert!(actions.contains(&Action::GotoLine));
+        assert!(actions.contains(&Action::FindNext));
+        assert!(!actions.contains(&Action::CommandPalette));
+        assert!(!actions.contains(&Action::Down));
+    }
+
+    #[test]
+    fn test_keymap_action_for() {
+        let key = |mods, code| KeyEvent::new(code, mods);
+        let none = KeyModifiers::NONE;
+        let ctrl = KeyModifiers::CONTROL;
+        let find = |k: KeyEvent| keymap::action_for(LOG_LIST_BINDINGS, &k);
+        assert_eq!(
+            find(key(ctrl, KeyCode::Char('f'))),
+            Some(Action::FocusFilter)
+        );
+        assert_eq!(
+            find(key(none, KeyCode::Char('f'))),
+            Some(Action::ToggleLive)
+        );
+        assert_eq!(
+            find(key(KeyModifiers::SHIFT, KeyCode::Char('G'))),
+            Some(Action::Last)
+        );
+        assert_eq!(
+            find(key(none, KeyCode::Char('t'))),
+            Some(Action::ToggleColumns)
+        );
+        assert_eq!(
+            find(key(ctrl, KeyCode::Char('p'))),
+            Some(Action::CommandPalette)
+        );
+        assert_eq!(find(key(none, KeyCode::Char('p'))), Some(Action::Share));
+        assert_eq!(find(key(none, KeyCode::Char('?'))), Some(Action::Help));
+        assert_eq!(
+            find(key(none, KeyCode::Char('c'))),
+            Some(Action::CycleAccent)
+        );
+        assert_eq!(find(key(ctrl, KeyCode::Char('y'))), Some(Action::CopyPath));
+        assert_eq!(
+            find(key(none, KeyCode::Char('y'))),
+            Some(Action::CopyScreen)
+        );
+        assert_eq!(find(key(ctrl, KeyCode::Char('o'))), Some(Action::JumpBack));
+        assert_eq!(
+            find(key(ctrl, KeyCode::Char('i'))),
+            Some(Action::JumpForward)
+        );
+        assert_eq!(
+            find(key(none, KeyCode::Char('i'))),
+            Some(Action::ToggleInvisibles)
+        );
+        assert_eq!(find(key(none, KeyCode::Char('z'))), None);
+        assert_eq!(
+            keymap::action_for(GLOBAL_BINDINGS, &key(ctrl, KeyCode::Char('c'))),
+            Some(Action::Quit)
+        );
+        assert_eq!(
+            keymap::action_for(GLOBAL_BINDINGS, &key(none, KeyCode::Char('c'))),
+            None
+        );
+        assert!(keymap::has_modifier(&key(ctrl, KeyCode::Char('c'))));
+        assert!(!keymap::has_modifier(&key(none, KeyCode::Char('q'))));
+        assert!(!keymap::has_modifier(&key(
+            KeyModifiers::SHIFT,
+            KeyCode::Char('Q')
+        )));
+    }
+
+    #[test]
+    fn test_help_rows_cover_every_binding() {
+        let rows = keymap::help_rows();
+        for b in GLOBAL_BINDINGS.iter().chain(LOG_LIST_BINDINGS) {
+            assert!(
+                rows.iter()
+                    .any(|(keys, desc)| *keys == keymap::binding_label(b)
+                        && desc == b.action.description())
+            );
+        }
+        let quit = rows.iter().find(|(_, d)| d == "Quit").unwrap();
+        assert_eq!(quit.0, "q, Ctrl+C");
+    }
+
+    #[test]
+    fn test_centered_rect() {
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: 100,
+            height: 20,
+        };
+        let r = centered_rect(area, 50, 50);
+        assert_eq!(r.width, 50);
+        assert_eq!(r.height, 10);
+        assert_eq!(r.x, 25);
+        assert_eq!(r.y, 5);
+    }
+
+    #[test]
+    fn test_saved_settings_roundtrip() {
+        let saved = SavedSettings {
+            accent: "Cyan".to_string(),
+            text_color: "White".to_string(),
+            text_style: "Normal".to_string(),
+            border_color: "Gray".to_string(),
+            status_color: "Gray".to_string(),
+            gutter_color: "Text".to_string(),
+            tab_width: 8,
+            language: "tr".to_string(),
+            filter_select_last: true,
+            truncate_middle: true,
+            max_poll_ms: 5000,
+            filter_tint: true,
+            status_fields: vec!["filter".to_string(), "count".to_string()],
+            per_file: "name".to_string(),
+            group_entries: true,
+            hide_hint_line: true,
+            highlight_tokens: true,
+            quit_requires_modifier: true,
+            skip_redact_warning: true,
+            hide_blank_lines: true,
+            filter_message_only: true,
+            split_view: true,
+            show_invisibles: true,
+            dense: true,
+            columns: true,
+            max_filter_len: 200,
+            hint_text: Some(" ?: help ".to_string()),
+            web_url: Some("https://logs.example.com".to_string()),
+            line_prefix: Some("{lineno} {level} | ".to_string()),
+            redact: vec![RedactRule {
+                pattern: "token=\\S+".to_string(),
+                replacement: "token=***".to_string(),
+            }],
+            filter: None,
+            files: Default::default(),
+        };
+        let s = serde_json::to_string_pretty(&saved).unwrap();
+        let loaded: SavedSettings = serde_json::from_str(&s).unwrap();
+        assert_eq!(loaded.accent, saved.accent);
+        assert_eq!(loaded.text_color, saved.text_color);
+        assert_eq!(loaded.text_style, saved.text_style);
+        assert_eq!(loaded.border_color, saved.border_color);
+        assert_eq!(loaded.status_color, saved.status_color);
+        assert_eq!(loaded.gutter_color, saved.gutter_color);
+        assert_eq!(loaded.tab_width, saved.tab_width);
+        assert_eq!(loaded.language, saved.language);
+        assert_eq!(loaded.filter_select_last, saved.filter_select_last);
+        assert_eq!(loaded.truncate_middle, saved.truncate_middle);
+        assert_eq!(loaded.max_poll_ms, saved.max_poll_ms);
+        assert_eq!(loaded.filter_tint, saved.filter_tint);
+        assert_eq!(loaded.status_fields, saved.status_fields);
+        assert_eq!(loaded.per_file, saved.per_file);
+        assert_eq!(loaded.group_entries, saved.group_entries);
+        assert_eq!(loaded.quit_requires_modifier, saved.quit_requires_modifier);
+        assert_eq!(loaded.skip_redact_warning, saved.skip_redact_warning);
+        assert_eq!(loaded.hide_blank_lines, saved.hide_blank_lines);
+        assert_eq!(loaded.filter_message_only, saved.filter_message_only);
+        assert_eq!(loaded.split_view, saved.split_view);
+        assert_eq!(loaded.show_invisibles, saved.show_invisibles);
+        assert_eq!(loaded.dense, saved.dense);
+        assert_eq!(loaded.columns, saved.columns);
+        assert_eq!(loaded.max_filter_len, saved.max_filter_len);
+        assert_eq!(loaded.line_prefix, saved.line_prefix);
+        assert_eq!(loaded.redact, saved.redact);
+        assert_eq!(loaded.hide_hint_line, saved.hide_hint_line);
+        assert_eq!(loaded.highlight_tokens, saved.highlight_tokens);
+        assert_eq!(loaded.hint_text, saved.hint_text);
+        assert_eq!(loaded.web_url, saved.web_url);
+        assert!(!s.contains("\"files\""));
+    }
+
+    #[test]
+    fn test_read_saved_from_reports_invalid_json() {
+        let missing = std::env::temp_dir().join("ratlog-test-no-such-settings.json");
+        assert!(matches!(read_saved_from(&missing), Ok(None)));
+        let valid = write_temp_file("settings-ok.json", export_json(None).as_bytes());
+        assert!(matches!(read_saved_from(&valid), Ok(Some(_))));
+        // A typo must not read as "no settings": saving would then replace the redact rules.
+        let invalid = write_temp_file(
+            "settings-bad.json",
+            br#"{"redact": [{"pattern": "secret"}],}"#,
+        );
+        let Err(error) = read_saved_from(&invalid) else {
+            panic!("invalid JSON read as settings");
+        };
+        assert!(error.contains("line 1"), "{error}");
+        let _ = std::fs::remove_file(valid);
+        let _ = std::fs::remove_file(invalid);
+    }
+
+    #[test]
+    fn test_per_file_settings_keep_global_default() {
+        let global = Settings {
+            per_file: PerFileSettings::Name,
+            ..Settings::default()
+        };
+        let mut saved = SavedSettings::from(&global);
+        let nginx = Settings {
+            accent_color: AccentColor::Magenta,
+            ..global
+        };
+        saved.store(&nginx, Some("nginx.log"), "status=500");
+        let s = serde_json::to_string(&saved).unwrap();
+        let saved: SavedSettings = serde_json::from_str(&s).unwrap();
+
+        assert_eq!(saved.settings_for(Some("nginx.log")), nginx);
+        assert_eq!(saved.filter_for(Some("nginx.log")), Some("status=500"));
+        assert_eq!(saved.settings_for(Some("other.log")), global);
+        assert_eq!(saved.filter_for(Some("other.log")), None);
+        assert_eq!(saved.settings_for(None), global);
+
+        // Saving globally keeps the per-file entries.
+        let mut saved = saved;
+        let changed = Settings {
+            tab_width: 2,
+            ..global
+        };
+        saved.store(&changed, None, "ignored");
+        assert_eq!(saved.settings_for(None), changed);
+        assert_eq!(saved.filter_for(None), None);
+        assert_eq!(saved.settings_for(Some("nginx.log")), nginx);
+    }
+
+    #[test]
+    fn test_settings_key() {
+        let path = std::path::Path::new("/var/log/nginx/access.log");
+        assert_eq!(settings_key(path, PerFileSettings::Off), None);
+        assert_eq!(
+            settings_key(path, PerFileSettings::Name).as_deref(),
+            Some("access.log")
+        );
+        assert_eq!(
+            settings_key(path, PerFileSettings::Path).as_deref(),
+            Some("/var/log/nginx/access.log")
+        );
+    }
+
+    #[test]
+    fn test_saved_settings_missing_tab_width_uses_default() {
+        let s = r#"{"accent":"Green","text_color":"White","text_style":"Bold","border_color":"Gray","status_color":"Gray"}"#;
+        let saved: SavedSettings = serde_json::from_str(s).unwrap();
+        let settings = Settings::from(&saved);
+        assert_eq!(settings.tab_width, DEFAULT_TAB_WIDTH);
+        assert_eq!(settings.accent_color.name(), "Green");
+        assert_eq!(settings.language, Language::En);
+        assert!(!settings.filter_select_last);
+        assert_eq!(settings.max_poll_ms, DEFAULT_MAX_POLL_MS);
+        assert_eq!(settings.status_layout, StatusLayout::FULL);
+        assert_eq!(settings.gutter_color, GutterColor::DarkGray);
+    }
+
+    #[test]
+    fn test_redact_rules() {
+        let s = r#"{"accent":"Cyan","text_color":"White","text_style":"Normal",
+            "border_color":"Gray","status_color":"Gray","redact":[
+            {"pattern":"[\\w.+-]+@[\\w-]+(\\.[\\w-]+)+","replacement":"<email>"},
+            {"pattern":"(token=)\\S+","replacement":"${1}***"},
+            {"pattern":"password=\\S+"},
+            {"pattern":"(unclosed"}
+        ]}"#;
+        let saved: SavedSettings = serde_json::from_str(s).unwrap();
+        assert_eq!(saved.redact[2].replacement, "***");
+        let (redactor, errors) = Redactor::new(&saved.redact);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("(unclosed: "));
+        assert_eq!(
+            redactor.apply("login ok for jane.doe@example.co.uk from 10.0.0.1"),
+            "login ok for <email> from 10.0.0.1"
+        );
+        assert_eq!(
+            redactor.apply("GET /api?token=abc123&x=1 200 token=zz"),
+            "GET /api?token=*** 200 token=***"
+        );
+        assert_eq!(redactor.apply("password=hunter2 ok"), "*** ok");
+        assert!(matches!(
+            redactor.apply("nothing secret"),
+            std::borrow::Cow::Borrowed(_)
+        ));
+        let mut lines = vec!["a@b.io token=t".to_string(), "plain".to_string()];
+        redactor.apply_all(&mut lines);
+        assert_eq!(lines, ["<email> token=***", "plain"]);
+
+        // Hand-written rules survive saving the global settings.
+        let mut saved = saved;
+        saved.store(&Settings::default(), None, "");
+        assert_eq!(saved.redact.len(), 4);
+        assert!(SavedSettings::from(&Settings::default()).redact.is_empty());
+    }
+
+    #[test]
+    fn test_status_layout_from_names() {
+        let names = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let layout = StatusLayout::from_names(&names(&["filter", "COUNT", "bogus", "filter"]));
+        assert_eq!(layout.fields(), &[StatusField::Filter, StatusField::Count]);
+        assert_eq!(
+            StatusLayout::from_names(&names(&["count", "live"])),
+            StatusLayout::MINIMAL
+        );
+        assert_eq!(StatusLayout::from_names(&[]), StatusLayout::FULL);
+    }
+
+    #[test]
+    fn test_fit_status_drops_low_priority_fields_first() {
+        let segments = vec![
+            (StatusField::Count, "3 / 3 lines".to_string()),
+            (StatusField::Memory, "RAM: 5 MiB".to_string()),
+            (StatusField::Live, String::new()),
+            (StatusField::Filter, "Filter: \"x\"".to_string()),
+            (StatusField::Keys, "q: quit".to_string()),
+        ];
+        let full = fit_status(&segments, 200);
+        assert_eq!(
+            full,
+            " 3 / 3 lines  |  RAM: 5 MiB  |  Filter: \"x\"  |  q: quit "
+        );
+        assert_eq!(
+            fit_status(&segments, full.chars().count() - 1),
+            " 3 / 3 lines  |  RAM: 5 MiB  |  Filter: \"x\" "
+        );
+        assert_eq!(fit_status(&segments, 30), " 3 / 3 lines  |  Filter: \"x\" ");
+        assert_eq!(fit_status(&segments, 13), " 3 / 3 lines ");
+        // A single segment that still does not fit is cut between words.
+        assert_eq!(fit_status(&segments, 10), " 3 / 3…");
+        assert_eq!(fit_status(&segments, 0), "");
+    }
+
+    #[test]
+    fn test_word_movement() {
+        let s = "error  user=42 ğüş";
+        assert_eq!(prev_word_start(s, s.len()), 15);
+        assert_eq!(prev_word_start(s, 15), 7);
+        assert_eq!(prev_word_start(s, 7), 0);
+        assert_eq!(prev_word_start(s, 0), 0);
+        assert_eq!(next_word_end(s, 0), 5);
+        assert_eq!(next_word_end(s, 5), 14);
+        assert_eq!(next_word_end(s, 14), s.len());
+        assert_eq!(next_word_end(s, s.len()), s.len());
+    }
+
+    #[test]
+    fn test_filter_cursor_steps_over_multibyte_graphemes() {
+        // Insert as the filter input does: the cursor advances by the char's UTF-8 length.
+        let mut s = String::new();
+        let mut cursor = 0;
+        for c in ['ğ', 'e', '\u{301}', '👍', 'a'] {
+            s.insert(cursor, c);
+            cursor += c.len_utf8();
+        }
+        assert_eq!(s, "ğe\u{301}👍a");
+        assert_eq!(display_column(&s, cursor), 5);
+        // Left steps a whole grapheme: "a", the emoji, then e + accent.
+        let after_emoji = prev_grapheme(&s, cursor);
+        let before_emoji = prev_grapheme(&s, after_emoji);
+        let before_e = prev_grapheme(&s, before_emoji);
+        assert_eq!(&s[before_emoji..after_emoji], "👍");
+        assert_eq!(&s[before_e..before_emoji], "e\u{301}");
+        assert_eq!(display_column(&s, before_emoji), 2);
+        assert_eq!(display_column(&s, after_emoji), 4);
+        assert_eq!(next_grapheme(&s, before_e), before_emoji);
+        assert_eq!(prev_grapheme(&s, 0), 0);
+        assert_eq!(next_grapheme(&s, s.len()), s.len());
+        // Backspace at the emoji's end removes it whole.
+        s.drain(before_emoji..after_emoji);
+        assert_eq!(s, "ğe\u{301}a");
+        // And again removes the accented e, never splitting a char.
+        let start = prev_grapheme(&s, before_emoji);
+        s.drain(start..before_emoji);
+        assert_eq!(s, "ğa");
+    }
+
+    #[test]
+    fn test_delete_word_before_multibyte() {
+        let mut s = "hata: bağlantı  reddedildi ✓".to_string();
+        let end = s.len();
+        let cursor = delete_word_before(&mut s, end);
+        assert_eq!((s.as_str(), cursor), ("hata: bağlantı  reddedildi ", 29));
+        let cursor = delete_word_before(&mut s, cursor);
+        assert_eq!((s.as_str(), cursor), ("hata: bağlantı  ", 18));
+        let cursor = delete_word_before(&mut s, cursor);
+        assert_eq!((s.as_str(), cursor), ("hata: ", 6));
+        // Mid-word deletes only the part before the cursor.
+        let mut s = "ğüşiö".to_string();
+        let cursor = delete_word_before(&mut s, 4);
+        assert_eq!((s.as_str(), cursor), ("şiö", 0));
+        assert_eq!(delete_word_before(&mut s, 0), 0);
+        assert_eq!(s, "şiö");
+    }
+
+    #[test]
+    fn test_resolve_config_dir_fallbacks() {
+        assert_eq!(
+            resolve_config_dir(Some(PathBuf::from("/home/u/.config")), None),
+            (PathBuf::from("/home/u/.config/ratlog"), false)
+        );
+        assert_eq!(
+            resolve_config_dir(None, Some("/xdg".into())),
+            (PathBuf::from("/xdg/ratlog"), false)
+        );
+        assert_eq!(
+            resolve_config_dir(None, Some("".into())),
+            (PathBuf::from(".ratlog"), true)
+        );
+        assert_eq!(
+            resolve_config_dir(None, None),
+            (PathBuf::from(".ratlog"), true)
+        );
+    }
+
+    #[test]
+    fn test_profile_paths() {
+        let dir = PathBuf::from("/home/u/.config/ratlog");
+        assert_eq!(
+            profile_path(dir.clone(), None, "settings.json", ".json"),
+            dir.join("settings.json")
+        );
+        assert_eq!(
+            profile_path(dir.clone(), Some("work"), "token", ".token"),
+            dir.join("profiles/work.token")
+        );
+        assert!(is_valid_profile_name("work"));
+        assert!(is_valid_profile_name("client-a_2.staging"));
+        assert!(!is_valid_profile_name(""));
+        assert!(!is_valid_profile_name(".."));
+        assert!(!is_valid_profile_name("../work"));
+        assert!(!is_valid_profile_name("a/b"));
+
+        // The value is not mistaken for the log file, and applies to subcommands too.
+        let argv = args(&["--profile", "work", "app.log"]);
+        assert_eq!(profile_arg(&argv).as_deref(), Some("work"));
+        match parse_args(&argv) {
+            CliAction::Run(LogSource::File(p), _) => assert_eq!(p, PathBuf::from("app.log")),
+            other => panic!("unexpected {:?}", other),
+        }
+        let argv = args(&["--profile=personal", "login"]);
+        assert_eq!(profile_arg(&argv).as_deref(), Some("personal"));
+        assert!(matches!(
+            parse_args(&argv),
+            CliAction::Login { check: false }
+        ));
+        assert_eq!(profile_arg(&args(&["app.log"])), None);
+    }
+
+    #[test]
+    fn test_poll_interval_backs_off_to_cap() {
+        assert_eq!(poll_interval(0, 2000), Duration::from_millis(400));
+        assert_eq!(
+            poll_interval(IDLE_POLLS_PER_STEP - 1, 2000),
+            Duration::from_millis(400)
+        );
+        assert_eq!(
+            poll_interval(IDLE_POLLS_PER_STEP, 2000),
+            Duration::from_millis(1000)
+        );
+        assert_eq!(
+            poll_interval(IDLE_POLLS_PER_STEP * 2, 2000),
+            Duration::from_millis(2000)
+        );
+        assert_eq!(poll_interval(u32::MAX, 2000), Duration::from_millis(2000));
+        assert_eq!(poll_interval(u32::MAX, 5000), Duration::from_millis(5000));
+        assert_eq!(poll_interval(u32::MAX, 400), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_rate_meter_averages_over_window() {
+        let start = Instant::now();
+        let mut meter = RateMeter::default();
+        assert_eq!(meter.rate(start), 0.0);
+        meter.record(start, 100);
+        meter.record(start + Duration::from_secs(1), 110);
+        assert_eq!(meter.rate(start + Duration::from_secs(2)), 42.0);
+        // The first sample ages out of the 5 s window.
+        assert_eq!(meter.rate(start + Duration::from_millis(5500)), 22.0);
+        assert_eq!(meter.rate(start + Duration::from_secs(10)), 0.0);
+        meter.record(start + Duration::from_secs(10), 5);
+        meter.clear();
+        assert_eq!(meter.rate(start + Duration::from_secs(10)), 0.0);
+    }
+
+    #[test]
+    fn test_format_rate() {
+        assert_eq!(format_rate(42.0), "~42 l/s");
+        assert_eq!(format_rate(0.4), "~0.4 l/s");
+        assert_eq!(format_rate(0.0), "~0.0 l/s");
+        assert_eq!(format_rate(123.6), "~124 l/s");
+    }
+
+    #[test]
+    fn test_format_uptime() {
+        let secs = Duration::from_secs;
+        assert_eq!(format_uptime(secs(0)), "0s");
+        assert_eq!(format_uptime(secs(45)), "45s");
+        assert_eq!(format_uptime(secs(192)), "3m12s");
+        assert_eq!(format_uptime(secs(2 * 3600 + 5 * 60 + 59)), "2h05m");
+        assert_eq!(format_uptime(secs(3 * 86_400 + 4 * 3600 + 1)), "3d04h");
+    }
+
+    #[test]
+    fn test_context_window_centres_and_clamps() {
+        assert_eq!(context_window(50, 100, 10), (45, 55));
+        assert_eq!(context_window(2, 100, 10), (0, 10));
+        assert_eq!(context_window(98, 100, 10), (90, 100));
+        assert_eq!(context_window(3, 5, 10), (0, 5));
+        assert_eq!(context_window(0, 0, 10), (0, 0));
+        assert_eq!(context_window(7, 100, 0), (7, 7));
+    }
+
+    #[test]
+    fn test_line_prefix_default_matches_builtin_gutter() {
+        let prefix = LinePrefix::default();
+        assert_eq!(prefix.render(324, "x", false), "   324 │ ");
+        assert_eq!(prefix.render(7, "x", true), "     7 ┆ ");
+    }
+
+    #[test]
+    fn test_line_prefix_placeholders() {
+        let line = "2025-02-15 10:00:05 WARN  High memory usage";
+        let prefix = LinePrefix::parse("{lineno} {level} | ");
+        assert_eq!(prefix.render(3, line, false), "     3 WARN  | ");
+        assert_eq!(prefix.render(3, "no level here", false), "     3       | ");
+        let prefix = LinePrefix::parse("[{time}] {foo} {sep}{");
+        assert_eq!(
+            prefix.render(1, line, false),
+            "[2025-02-15 10:00:05] {foo} │{"
+        );
+        assert_eq!(prefix.render(1, "  at Foo.bar", true), "[] {foo} ┆{");
+    }
+
+    #[test]
+    fn test_leading_timestamp_and_level() {
+        assert_eq!(
+            leading_timestamp("2025-02-15T10:00:00Z INFO  Server started"),
+            Some("2025-02-15T10:00:00Z")
+        );
+        assert_eq!(
+            leading_timestamp("[2025-02-15 10:00:01] ERROR boom"),
+            Some("2025-02-15 10:00:01")
+        );
+        assert_eq!(
+            leading_timestamp("Feb 15 10:00:01 host sshd[1]: ok"),
+            Some("Feb 15 10:00:01")
+        );
+        assert_eq!(leading_timestamp("10:00:01 start"), Some("10:00:01"));
+        assert_eq!(leading_timestamp("  at Foo.bar"), None);
+        assert_eq!(line_level("[x] ERROR: failed"), Some("ERROR"));
+        assert_eq!(line_level("more info here"), None);
+    }
+
+    #[test]
+    fn test_is_blank() {
+        for line in ["", " ", "\t  ", "\r", "\u{a0}"] {
+            assert!(is_blank(line), "{:?}", line);
+        }
+        for line in ["x", "  indented", "\t.", "-"] {
+            assert!(!is_blank(line), "{:?}", line);
+        }
+        let lines: Vec<String> = ["start", "", "   ", "  at Foo.bar", "end"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let mut rows = apply_filter(&lines, "", lines.len());
+        rows.retain(|(_, line)| !is_blank(line));
+        let kept: Vec<usize> = rows.iter().map(|(idx, _)| *idx).collect();
+        assert_eq!(kept, [0, 3, 4]);
+    }
+
+    #[test]
+    fn test_filter_message_only() {
+        assert_eq!(timestamp_len("2025-02-15 10:00:01 ERROR x"), Some(20));
+        assert_eq!(timestamp_len("[10:00:01]  ok"), Some(12));
+        assert_eq!(timestamp_len("Feb 15 10:00:01 host"), Some(16));
+        assert_eq!(timestamp_len("2025-02-15"), Some(10));
+        assert_eq!(timestamp_len("no time 2025"), None);
+
+        let lines = vec![
+            "2025-02-15 10:00:01 INFO start".to_string(),
+            "2025-02-15 10:00:02 ERROR release 2025 failed".to_string(),
+            "  at build 2025".to_string(),
+        ];
+        let spec = FilterSpec {
+            message_only: true,
+            ..FilterSpec::parse("2025")
+        };
+        let kept: Vec<usize> = apply_filter_spec(&lines, &spec, 10)
+            .into_iter()
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(kept, vec![1, 2]);
+        assert_eq!(apply_filter(&lines, "2025", 10).len(), 3);
+        // `colN:` counts fields of the message.
+        let spec = FilterSpec {
+            message_only: true,
+            ..FilterSpec::parse("col1:error")
+        };
+        assert!(spec.matches(&lines[1]));
+        assert!(!spec.matches(&lines[0]));
+    }
+
+    #[test]
+    fn test_split_columns() {
+        assert_eq!(
+            split_columns("2025-02-15 10:00:01 ERROR db timeout"),
+            Some(("2025-02-15 10:00:01", "ERROR", "db timeout"))
+        );
+        assert_eq!(
+            split_columns("[10:00:01] [WARN] slow"),
+            Some(("10:00:01", "WARN", "slow"))
+        );
+        assert_eq!(
+            split_columns("Feb 15 10:00:01 host sshd[1]: ok"),
+            Some(("Feb 15 10:00:01", "", "host sshd[1]: ok"))
+        );
+        assert_eq!(split_columns("  at Foo.bar"), None);
+
+        let lines = [
+            "10:00:01 INFO start",
+            "2025-02-15 10:00:02 WARNING: low disk",
+            "  at Foo.bar",
+        ];
+        let widths = column_widths(lines);
+        assert_eq!(widths, (19, 7));
+        assert_eq!(
+            columnize(lines[0], widths),
+            "10:00:01            INFO    start"
+        );
+        assert_eq!(
+            columnize(lines[1], widths),
+            "2025-02-15 10:00:02 WARNING low disk"
+        );
+        assert_eq!(
+            columnize(lines[2], widths),
+            format!("{}  at Foo.bar", " ".repeat(28))
+        );
+        // Nothing to align: lines are left as they are.
+        assert_eq!(column_widths(["plain"]), (0, 0));
+        assert_eq!(columnize("plain", (0, 0)), "plain");
+    }
+
+    #[test]
+    fn test_find_tokens() {
+        let line = "GET https://api.example.com/v1/users?id=7 from 192.168.1.10:443 \
+                    req 3f2b8c1e-9a4d-4e5f-8b6a-1c2d3e4f5a6b took 45ms (retry after 1.5s)";
+        let found: Vec<(&str, Token)> = find_tokens(line)
+            .into_iter()
+            .map(|(range, token)| (&line[range], token))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("https://api.example.com/v1/users?id=7", Token::Url),
+                ("192.168.1.10:443", Token::Ip),
+                ("3f2b8c1e-9a4d-4e5f-8b6a-1c2d3e4f5a6b", Token::Uuid),
+                ("45ms", Token::Duration),
+                ("1.5s", Token::Duration),
+            ]
+        );
+        // The host of a URL stays part of the URL; plain numbers and words are left alone.
+        let found = find_tokens("see http://10.0.0.1:8080/health");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1, Token::Url);
+        assert!(find_tokens("attempt 2 of 5, version 1.2.3, items").is_empty());
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        assert!(find_pattern("").is_none());
+        let re = find_pattern("a.b").unwrap();
+        let line = "A.B axb a.b";
+        let found: Vec<&str> = re.find_iter(line).map(|m| m.as_str()).collect();
+        assert_eq!(found, vec!["A.B", "a.b"]);
+    }
+
+    #[test]
+    fn test_percent_index() {
+        assert_eq!(percent_index(0, 101), 0);
+        assert_eq!(percent_index(50, 101), 50);
+        assert_eq!(percent_index(100, 101), 100);
+        assert_eq!(percent_index(250, 101), 100);
+        assert_eq!(percent_index(50, 4), 1);
+        assert_eq!(percent_index(50, 0), 0);
+    }
+
+    #[test]
+    fn test_scrollbar_index_maps_track_to_rows() {
+        // Track rows 5..=14 (10 cells) over 100 list rows.
+        assert_eq!(scrollbar_index(5, 5, 10, 100), 0);
+        assert_eq!(scrollbar_index(14, 5, 10, 100), 99);
+        assert_eq!(scrollbar_index(9, 5, 10, 100), 44);
+        assert_eq!(scrollbar_index(0, 5, 10, 100), 0);
+        assert_eq!(scrollbar_index(40, 5, 10, 100), 99);
+        assert_eq!(scrollbar_index(7, 5, 10, 0), 0);
+        assert_eq!(scrollbar_index(7, 5, 1, 50), 0);
+    }
+
+    #[test]
+    fn test_visible_segments() {
+        assert_eq!(
+            visible_segments("plain", 4),
+            vec![("plain".to_string(), false)]
+        );
+        assert_eq!(
+            visible_segments("line\r", 4),
+            vec![("line".to_string(), false), ("^M".to_string(), true)]
+        );
+        assert_eq!(
+            visible_segments("a\tb\x07\x7f", 4),
+            vec![
+                ("a".to_string(), false),
+                ("→  ".to_string(), true),
+                ("b".to_string(), false),
+                ("^G".to_string(), true),
+                ("^?".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_path() {
+        let home = dirs::home_dir().unwrap().to_string_lossy().to_string();
+        assert_eq!(expand_path("~").unwrap(), home);
+        assert_eq!(expand_path("~/a.log").unwrap(), format!("{}/a.log", home));
+        assert_eq!(expand_path("a~/b.log").unwrap(), "a~/b.log");
+        let path = std::env::var("PATH").unwrap();
+        assert_eq!(expand_path("$PATH/x").unwrap(), format!("{}/x", path));
+        assert_eq!(expand_path("${PATH}x").unwrap(), format!("{}x", path));
+        assert_eq!(expand_path("cost$ 5").unwrap(), "cost$ 5");
+        let err = expand_path("$RATLOG_TEST_SURELY_UNSET/app.log").unwrap_err();
+        assert!(err.contains("RATLOG_TEST_SURELY_UNSET"));
+    }
+
+    #[test]
+    fn test_load_logs_reports_expanded_missing_path() {
+        let err = load_logs(Some(PathBuf::from("~/ratlog-surely-missing.log"))).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        assert!(
+            err.to_string()
+                .contains("expanded from ~/ratlog-surely-missing.log")
+        );
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("app.log.*", "app.log.2024-06-01"));
+        assert!(glob_match("app.log.*", "app.log."));
+        assert!(!glob_match("app.log.*", "app.log"));
+        assert!(glob_match("app-??.log", "app-01.log"));
+        assert!(!glob_match("app-??.log", "app-1.log"));
+        assert!(glob_match("*.log", "a.b.log"));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(!glob_match("a*b*c", "aXbYbZ"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("журнал-*", "журнал-1"));
+    }
+
+    #[test]
+    fn test_truncate_chars() {
+        let mut s = "héllo wörld".to_string();
+        assert!(!truncate_chars(&mut s, 11));
+        assert!(truncate_chars(&mut s, 7));
+        assert_eq!(s, "héllo w");
+        assert!(truncate_chars(&mut s, 0) && s.is_empty());
+    }
+
+    #[test]
+    fn test_truncate_middle() {
+        assert_eq!(truncate_middle("short", 10), "short");
+        assert_eq!(truncate_middle("abcdefghij", 5), "ab…ij");
+        assert_eq!(truncate_middle("abcdefghij", 6), "abc…ij");
+        assert_eq!(truncate_middle("ğüşiöçĞÜŞİ", 5), "ğü…Şİ");
+        assert_eq!(truncate_middle("abc", 1), "…");
+        assert_eq!(truncate_middle("abc", 0), "");
+    }
+
+    #[test]
+    fn test_expand_tabs() {
+        assert_eq!(expand_tabs("no tabs", 4), "no tabs");
+        assert_eq!(expand_tabs("\tx", 4), "    x");
+        assert_eq!(expand_tabs("ab\tc", 4), "ab  c");
+        assert_eq!(expand_tabs("abcd\te", 4), "abcd    e");
+        assert_eq!(expand_tabs("a\tb", 8), "a       b");
+    }
+
+    #[test]
+    fn test_share_request_body_expiry() {
+        let body = share_request_body("x", true, ShareExpiry::Never);
+        assert_eq!(body["is_public"], true);
+        assert!(body.get("expires_in_hours").is_none());
+        let body = share_request_body("x", false, ShareExpiry::Week);
+        assert_eq!(body["expires_in_hours"], 168);
+    }
+
+    #[test]
+    fn test_share_response_expires_at_is_optional() {
+        let res: ShareLogResponse =
+            serde_json::from_str(r#"{"id":"1","url":"u","expires_at":"2026-01-01T00:00:00Z"}"#)
+                .unwrap();
+        assert_eq!(res.expires_at.as_deref(), Some("2026-01-01T00:00:00Z"));
+        let res: ShareLogResponse = serde_json::from_str(r#"{"id":"1","url":"u"}"#).unwrap();
+        assert!(res.expires_at.is_none());
+    }
+
+    #[test]
+    fn test_normalize_app_url() {
+        assert_eq!(
+            normalize_app_url(" https://logs.example.com:8443/ratlog// ").as_deref(),
+            Ok("https://logs.example.com:8443/ratlog")
+        );
+        assert_eq!(
+            normalize_app_url("http://localhost:3000").as_deref(),
+            Ok("http://localhost:3000")
+        );
+        assert!(normalize_app_url("logs.example.com").is_err());
+        assert!(normalize_app_url("ftp://logs.example.com").is_err());
+        assert!(normalize_app_url("https://").is_err());
+    }
+
+    #[test]
+    fn test_share_size_limit() {
+        assert_eq!(share_limit(None), MAX_SHARE_BYTES);
+        assert_eq!(share_limit(Some(" 1024 ")), 1024);
+        assert_eq!(share_limit(Some("0")), MAX_SHARE_BYTES);
+        assert_eq!(share_limit(Some("5MB")), MAX_SHARE_BYTES);
+        assert!(check_share_size(1024, 1024).is_ok());
+        let err = check_share_size(2048, 1024).unwrap_err();
+        assert!(
+            err.contains("2 KiB") && err.contains("RATLOG_SHARE_MAX_BYTES"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_auth_header() {
+        let header = |h, s| auth_header("tok", h, s);
+        assert_eq!(
+            header(None, None),
+            ("Authorization".to_string(), "Bearer tok".to_string())
+        );
+        assert_eq!(
+            header(Some("X-Api-Key"), Some("")),
+            ("X-Api-Key".to_string(), "tok".to_string())
+        );
+        assert_eq!(
+            header(Some(" "), Some("Token")),
+            ("Authorization".to_string(), "Token tok".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_args_unshare() {
+        match parse_args(&args(&["unshare", "https://ratlog.info/logs/abc"])) {
+            CliAction::Unshare(Some(t)) => assert_eq!(t, "https://ratlog.info/logs/abc"),
+            other => panic!("expected Unshare, got {:?}", other),
+        }
+        assert!(matches!(
+            parse_args(&args(&["unshare"])),
+            CliAction::Unshare(None)
+        ));
+    }
+
+    #[test]
+    fn test_share_id_from_url_or_id() {
+        assert_eq!(share_id_from("abc123"), "abc123");
+        assert_eq!(share_id_from("https://ratlog.info/logs/abc123"), "abc123");
+        assert_eq!(share_id_from("https://ratlog.info/logs/abc123/"), "abc123");
+        assert_eq!(
+            share_id_from("https://ratlog.info/logs/abc123?x=1#top"),
+            "abc123"
+        );
+    }
+
+    #[test]
+    fn test_push_recent_share_dedupes_newest_first() {
+        let share = |id: &str| SavedShare {
+            id: id.to_string(),
+            url: format!("https://ratlog.info/logs/{}", id),
+            delete_token: Some("t".to_string()),
+            expires_at: None,
+        };
+        let mut shares = vec![share("a"), share("b")];
+        push_recent_share(&mut shares, share("b"));
+        assert_eq!(
+            shares.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(),
+            vec!["b", "a"]
+        );
+        for i in 0..100 {
+            push_recent_share(&mut shares, share(&i.to_string()));
+        }
+        assert_eq!(shares.len(), 50);
+        assert_eq!(shares[0].id, "99");
+    }
+
+    #[test]
+    fn test_parse_args_shares_json() {
+        assert!(matches!(
+            parse_args(&args(&["shares"])),
+            CliAction::Shares { json: false }
+        ));
+        assert!(matches!(
+            parse_args(&args(&["shares", "--json"])),
+            CliAction::Shares { json: true }
+        ));
+    }
+
+    #[test]
+    fn test_parse_args_config() {
+        assert!(matches!(
+            parse_args(&args(&["config", "export"])),
+            CliAction::Config(Some(ConfigAction::Export))
+        ));
+        match parse_args(&args(&["config", "import", "ratlog.json"])) {
+            CliAction::Config(Some(ConfigAction::Import(file))) => assert_eq!(file, "ratlog.json"),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            parse_args(&args(&["config", "import"])),
+            CliAction::Config(None)
+        ));
+        assert!(matches!(
+            parse_args(&args(&["config"])),
+            CliAction::Config(None)
+        ));
+    }
+
+    #[test]
+    fn test_export_json_fills_in_settings() {
+        let exported: SavedSettings = serde_json::from_str(&export_json(None)).unwrap();
+        assert_eq!(exported.settings_for(None), Settings::default());
+        assert_eq!(exported.max_filter_len, crate::constants::MAX_FILTER_LEN);
+
+        let saved: SavedSettings = serde_json::from_str(
+            r#"{"accent":"green","text_color":"nope","text_style":"Bold","border_color":"White",
+               "status_color":"Gray","line_prefix":"{lineno} ",
+               "files":{"app.log":{"accent":"Blue","text_color":"White","text_style":"Normal",
+               "border_color":"Gray","status_color":"Gray","filter":"ERROR"}}}"#,
+        )
+        .unwrap();
+        let json = export_json(Some(saved));
+        let exported: SavedSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(exported.accent, "Green");
+        assert_eq!(exported.text_color, "White");
+        assert_eq!(exported.line_prefix.as_deref(), Some("{lineno} "));
+        assert_eq!(exported.filter_for(Some("app.log")), Some("ERROR"));
+        // Exporting an export changes nothing.
+        assert_eq!(export_json(Some(exported)), json);
+    }
+
+    #[test]
+    fn test_parse_shared_logs_accepts_wrapped_or_bare_list() {
+        let item = r#"{"id":"a1","url":"https://ratlog.info/logs/a1","created_at":"2026-01-02","line_count":12}"#;
+        for body in [
+            format!("[{}]", item),
+            format!(r#"{{"data":[{}]}}"#, item),
+            format!(r#"{{"logs":[{}]}}"#, item),
+        ] {
+            let logs = parse_shared_logs(&body).unwrap();
+            assert_eq!(logs.len(), 1, "{body}");
+            assert_eq!(logs[0].line_count, Some(12));
+        }
+        assert!(parse_shared_logs("{}").is_err());
+    }
+
+    #[test]
+    fn test_format_shares_table_aligns_columns() {
+        i18n::set_language(Language::En);
+        let logs = vec![
+            SharedLog {
+                id: "a1".to_string(),
+                url: "https://x/a1".to_string(),
+                created_at: Some("2026-01-02".to_string()),
+                line_count: Some(12),
+            },
+            SharedLog {
+                id: "bbbb".to_string(),
+                url: "https://x/bbbb".to_string(),
+                created_at: None,
+                line_count: None,
+            },
+        ];
+        let table = format_shares_table(&logs);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "ID    URL             CREATED     LINES");
+        assert_eq!(lines[1], "a1    https://x/a1    2026-01-02  12");
+        assert_eq!(lines[2], "bbbb  https://x/bbbb  -           -");
+    }
+
+    #[test]
+    fn test_is_transient_status() {
+        assert!(is_transient_status(500));
+        assert!(is_transient_status(503));
+        assert!(!is_transient_status(201));
+        assert!(!is_transient_status(401));
+        assert!(!is_transient_status(404));
+    }
+}
@@ -1,21 +1,91 @@
-//! CLI login to Ratlog Web: open browser, verify token, save locally.
+//! CLI login to Ratlog Web: open browser, verify token, save locally; share and unshare logs.
 
 use std::fs;
 use std::io::{self, Write};
 use std::path::PathBuf;
 use std::process::Command;
+use std::time::Duration;
+
+use crate::constants::MAX_SHARE_BYTES;
+use crate::i18n::{fill, t};
+use crate::settings::load_web_url;
+use crate::util::{format_bytes, profile_file};
 
 const DEFAULT_APP_URL: &str = "https://ratlog.info";
 const RATLOG_WEB_URL_ENV: &str = "RATLOG_WEB_URL";
 
-fn app_url() -> String {
-    std::env::var(RATLOG_WEB_URL_ENV).unwrap_or_else(|_| DEFAULT_APP_URL.to_string())
+/// Base URL of Ratlog Web from `RATLOG_WEB_URL`, else `web_url` in the settings of the
+/// profile (default `https://ratlog.info`), checked by [`normalize_app_url`] so a typo fails up
+/// front instead of deep inside a request.
+fn app_url() -> Result<String, String> {
+    let (name, url) = match std::env::var(RATLOG_WEB_URL_ENV) {
+        Ok(url) => (RATLOG_WEB_URL_ENV, url),
+        Err(_) => match load_web_url() {
+            Some(url) => ("web_url", url),
+            None => return Ok(DEFAULT_APP_URL.to_string()),
+        },
+    };
+    normalize_app_url(&url).map_err(|reason| fill(t().invalid_web_url, &[name, &url, &reason]))
+}
+
+/// `url` without surrounding whitespace and trailing slashes, so API paths can be appended as
+/// `{base}/api/...`. Fails unless it is an http(s) URL with a host.
+pub fn normalize_app_url(url: &str) -> Result<String, String> {
+    let url = url.trim();
+    let parsed = reqwest::Url::parse(url).map_err(|e| e.to_string())?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(fill(t().invalid_web_url_scheme, &[parsed.scheme()]));
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        return Err(t().invalid_web_url_host.to_string());
+    }
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+const AUTH_HEADER_ENV: &str = "RATLOG_AUTH_HEADER";
+const AUTH_SCHEME_ENV: &str = "RATLOG_AUTH_SCHEME";
+const SHARE_MAX_BYTES_ENV: &str = "RATLOG_SHARE_MAX_BYTES";
+
+/// Header name and value carrying `token`: `Authorization: Bearer <token>` unless `header` or
+/// `scheme` say otherwise. An empty `scheme` sends the token alone (e.g. `X-Api-Key: <token>`).
+pub fn auth_header(token: &str, header: Option<&str>, scheme: Option<&str>) -> (String, String) {
+    let name = header
+        .map(str::trim)
+        .filter(|h| !h.is_empty())
+        .unwrap_or("Authorization");
+    let value = match scheme.map(str::trim) {
+        None => format!("Bearer {}", token),
+        Some("") => token.to_string(),
+        Some(scheme) => format!("{} {}", scheme, token),
+    };
+    (name.to_string(), value)
 }
 
-fn token_path() -> Option<PathBuf> {
-    dirs::config_dir().map(|d| d.join("ratlog").join("token"))
+/// [`auth_header`] as configured by `RATLOG_AUTH_HEADER` and `RATLOG_AUTH_SCHEME`.
+fn token_header(token: &str) -> (String, String) {
+    let header = std::env::var(AUTH_HEADER_ENV).ok();
+    let scheme = std::env::var(AUTH_SCHEME_ENV).ok();
+    auth_header(token, header.as_deref(), scheme.as_deref())
 }
 
+/// `token`, or `profiles/NAME.token` with `--profile NAME`.
+fn token_path() -> PathBuf {
+    profile_file("token", ".token")
+}
+
+fn shares_path() -> PathBuf {
+    profile_file("shares.json", ".shares.json")
+}
+
+/// Connect and read timeout for every request to Ratlog Web.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Waits before each retry of a request that failed transiently.
+const RETRY_DELAYS_MS: [u64; 2] = [500, 1500];
+
+/// How many shares made from this machine are remembered for `ratlog unshare`.
+const MAX_RECENT_SHARES: usize = 50;
+
 /// Open default browser to the given URL.
 fn open_browser(url: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     #[cfg(target_os = "macos")]
@@ -32,93 +102,167 @@ fn open_browser(url: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync
 
 /// Read token from stdin.
 fn read_token_from_stdin() -> Result<String, io::Error> {
-    print!("Token'ı yapıştırın: ");
+    print!("{}", t().login_paste_token);
     io::stdout().flush()?;
     let mut token = String::new();
     io::stdin().read_line(&mut token)?;
     Ok(token.trim().to_string())
 }
 
+/// Client with connect and read timeouts, so an unresponsive server fails instead of hanging.
+pub(crate) fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(REQUEST_TIMEOUT)
+        .read_timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// Server errors (5xx) are worth retrying; anything else is the final answer.
+pub fn is_transient_status(status: u16) -> bool {
+    (500..600).contains(&status)
+}
+
+/// A request that failed to connect never reached the server and can always be sent again. After
+/// a timeout or a 5xx the server may have acted on it, so only idempotent ones are repeated.
+fn is_transient_error(e: &reqwest::Error, idempotent: bool) -> bool {
+    e.is_connect() || (idempotent && e.is_timeout())
+}
+
+/// Send the request built by `build`, retrying after `RETRY_DELAYS_MS` on connection
+/// failures and, for idempotent methods (GET, DELETE), also on timeouts and 5xx responses, so a
+/// share POST is not created twice. Timeouts become a readable error.
+async fn send_with_retry(
+    build: impl Fn(&reqwest::Client) -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
+    let client = http_client();
+    let mut delays = RETRY_DELAYS_MS.iter();
+    loop {
+        let request = build(&client).build()?;
+        let idempotent = request.method().is_idempotent();
+        let result = client.execute(request).await;
+        let transient = match &result {
+            Ok(response) => idempotent && is_transient_status(response.status().as_u16()),
+            Err(e) => is_transient_error(e, idempotent),
+        };
+        match delays.next() {
+            Some(&ms) if transient => tokio::time::sleep(Duration::from_millis(ms)).await,
+            _ => {
+                return result.map_err(|e| {
+                    if e.is_timeout() {
+                        let secs = REQUEST_TIMEOUT.as_secs().to_string();
+                        fill(t().request_timed_out, &[&secs]).into()
+                    } else {
+                        e.into()
+                    }
+                });
+            }
+        }
+    }
+}
+
 /// Verify token via GET /api/user.
 async fn verify_token(
     base_url: &str,
     token: &str,
 ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
-    let url = format!("{}/api/user", base_url.trim_end_matches('/'));
-    let client = reqwest::Client::new();
-    let response = client
-        .get(&url)
-        .header("Accept", "application/json")
-        .header("Authorization", format!("Bearer {}", token))
-        .send()
-        .await?;
+    let url = format!("{}/api/user", base_url);
+    let (name, value) = token_header(token);
+    let response = send_with_retry(|client| {
+        client
+            .get(&url)
+            .header("Accept", "application/json")
+            .header(name.as_str(), value.as_str())
+    })
+    .await?;
 
     if response.status().is_success() {
         let user: serde_json::Value = response.json().await?;
         Ok(user)
     } else {
-        Err(format!("Token doğrulama başarısız: {}", response.status()).into())
+        Err(fill(t().login_verify_failed, &[&response.status().to_string()]).into())
     }
 }
 
-/// Save token to config file (~/.config/ratlog/token).
-fn save_token(token: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let path = token_path().ok_or("Config dizini bulunamadı")?;
+/// Write a file readable only by the user (it holds a token).
+fn write_private(path: &PathBuf, contents: &str) -> io::Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
-    fs::write(&path, token)?;
+    fs::write(path, contents)?;
 
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
-        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
     }
 
     Ok(())
 }
 
-/// Run the login flow: open browser, prompt for token, verify, save.
+/// Save token to config file (~/.config/ratlog/token, see [`crate::util::config_dir`]).
+fn save_token(token: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    write_private(&token_path(), token)?;
+    Ok(())
+}
+
+/// Email of the account `token` belongs to, as reported by the server.
+async fn verified_email(base_url: &str, token: &str) -> color_eyre::Result<String> {
+    let user = verify_token(base_url, token)
+        .await
+        .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+    Ok(user["email"].as_str().unwrap_or("?").to_string())
+}
+
+/// `ratlog login --check`: verify the saved token and print its account, without the browser.
+/// Fails (non-zero exit) when there is no token or the server rejects it.
+pub async fn check() -> color_eyre::Result<()> {
+    let base_url = app_url().map_err(|e| color_eyre::eyre::eyre!(e))?;
+    let token = load_token().ok_or_else(|| color_eyre::eyre::eyre!(t().not_logged_in))?;
+    let email = verified_email(&base_url, &token).await?;
+    println!("{}", fill(t().login_success, &[&email]));
+    Ok(())
+}
+
+/// `ratlog login`: open the browser, read the token it shows, verify and save it.
 pub async fn run() -> color_eyre::Result<()> {
-    let base_url = app_url();
-    let login_url = format!("{}/cli-login", base_url.trim_end_matches('/'));
+    let base_url = app_url().map_err(|e| color_eyre::eyre::eyre!(e))?;
+    let login_url = format!("{}/cli-login", base_url);
 
-    println!("Ratlog Web CLI Girişi");
+    println!("{}", t().login_heading);
     println!();
-    println!("Tarayıcı açılıyor: {}", login_url);
+    println!("{}", fill(t().login_opening_browser, &[&login_url]));
 
     if let Err(e) = open_browser(&login_url) {
-        eprintln!("Tarayıcı açılamadı: {}", e);
-        eprintln!("Lütfen şu adresi manuel açın: {}", login_url);
+        eprintln!("{}", fill(t().login_browser_failed, &[&e.to_string()]));
+        eprintln!("{}", fill(t().login_open_manually, &[&login_url]));
     }
 
     println!();
-    println!("Tarayıcıda giriş yapın ve token'ı kopyalayın.");
+    println!("{}", t().login_instructions);
     println!();
 
     let token = read_token_from_stdin()?;
     if token.is_empty() {
-        color_eyre::eyre::bail!("Token boş bırakılamaz.");
+        color_eyre::eyre::bail!("{}", t().login_empty_token);
     }
 
-    println!("Token doğrulanıyor...");
-    let user = verify_token(&base_url, &token)
-        .await
-        .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+    println!("{}", t().login_verifying);
+    let email = verified_email(&base_url, &token).await?;
 
     save_token(&token).map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
 
-    let email = user["email"].as_str().unwrap_or("?");
     println!();
-    println!("✓ Giriş başarılı: {}", email);
-    println!("Token kaydedildi: {:?}", token_path().unwrap_or_default());
+    println!("{}", fill(t().login_success, &[&email]));
+    let saved_to = format!("{:?}", token_path());
+    println!("{}", fill(t().login_token_saved, &[&saved_to]));
 
     Ok(())
 }
 
 /// Load saved token from config file. Returns None if not found or invalid.
 pub fn load_token() -> Option<String> {
-    let path = token_path()?;
+    let path = token_path();
     let s = fs::read_to_string(&path).ok()?;
     let token = s.trim();
     if token.is_empty() {
@@ -130,7 +274,6 @@ pub fn load_token() -> Option<String> {
 
 /// Response from POST /api/logs.
 #[derive(Debug, serde::Deserialize)]
-#[allow(dead_code)]
 pub struct ShareLogResponse {
     pub id: String,
     pub url: String,
@@ -144,34 +287,373 @@ pub struct ShareLogResponse {
     pub line_count: Option<u64>,
 }
 
+/// How long a shared log stays available; chosen in the share dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShareExpiry {
+    #[default]
+    Never,
+    Hour,
+    Day,
+    Week,
+}
+
+impl ShareExpiry {
+    /// Value sent as `expires_in_hours`; `None` leaves the expiry to the server.
+    pub fn hours(self) -> Option<u32> {
+        match self {
+            ShareExpiry::Never => None,
+            ShareExpiry::Hour => Some(1),
+            ShareExpiry::Day => Some(24),
+            ShareExpiry::Week => Some(24 * 7),
+        }
+    }
+    pub fn label(self) -> &'static str {
+        match self {
+            ShareExpiry::Never => t().expiry_never,
+            ShareExpiry::Hour => t().expiry_hour,
+            ShareExpiry::Day => t().expiry_day,
+            ShareExpiry::Week => t().expiry_week,
+        }
+    }
+    pub fn all() -> &'static [ShareExpiry] {
+        &[
+            ShareExpiry::Never,
+            ShareExpiry::Hour,
+            ShareExpiry::Day,
+            ShareExpiry::Week,
+        ]
+    }
+}
+
+/// JSON body for POST /api/logs.
+pub fn share_request_body(
+    content: &str,
+    is_public: bool,
+    expiry: ShareExpiry,
+) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "content": content,
+        "is_public": is_public
+    });
+    if let Some(hours) = expiry.hours() {
+        body["expires_in_hours"] = hours.into();
+    }
+    body
+}
+
+/// Byte cap on shared content: `value` (from `RATLOG_SHARE_MAX_BYTES`) when it is a positive
+/// number, otherwise [`MAX_SHARE_BYTES`].
+pub fn share_limit(value: Option<&str>) -> usize {
+    value
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(MAX_SHARE_BYTES)
+}
+
+/// Refuse content over `limit` bytes up front, rather than uploading it as one huge request.
+pub fn check_share_size(len: usize, limit: usize) -> Result<(), String> {
+    if len > limit {
+        return Err(fill(
+            t().share_too_large,
+            &[
+                &format_bytes(len as u64),
+                &format_bytes(limit as u64),
+                SHARE_MAX_BYTES_ENV,
+            ],
+        ));
+    }
+    Ok(())
+}
+
 /// Share log content to Ratlog Web. Returns the share URL or error.
 pub async fn share_log(
     content: &str,
     is_public: bool,
+    expiry: ShareExpiry,
 ) -> Result<ShareLogResponse, Box<dyn std::error::Error + Send + Sync>> {
-    let token = load_token().ok_or("Giriş yapılmamış. Önce 'ratlog login' çalıştırın.")?;
-    let base_url = app_url();
-    let url = format!("{}/api/logs", base_url.trim_end_matches('/'));
-
-    let client = reqwest::Client::new();
-    let response = client
-        .post(&url)
-        .header("Accept", "application/json")
-        .header("Authorization", format!("Bearer {}", token))
-        .header("Content-Type", "application/json")
-        .json(&serde_json::json!({
-            "content": content,
-            "is_public": is_public
-        }))
-        .send()
-        .await?;
+    let limit = share_limit(std::env::var(SHARE_MAX_BYTES_ENV).ok().as_deref());
+    check_share_size(content.len(), limit)?;
+    let base_url = app_url()?;
+    let token = load_token().ok_or(t().not_logged_in)?;
+    let url = format!("{}/api/logs", base_url);
+
+    let body = share_request_body(content, is_public, expiry);
+    let (name, value) = token_header(&token);
+    let response = send_with_retry(|client| {
+        client
+            .post(&url)
+            .header("Accept", "application/json")
+            .header(name.as_str(), value.as_str())
+            .header("Content-Type", "application/json")
+            .json(&body)
+    })
+    .await?;
 
     if response.status().as_u16() == 201 {
         let body: ShareLogResponse = response.json().await?;
+        remember_share(&body);
         Ok(body)
     } else {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
-        Err(format!("Log paylaşımı başarısız ({}): {}", status, body).into())
+        Err(fill(t().share_failed, &[&status.to_string(), &body]).into())
     }
 }
+
+/// A share made from this machine, kept in `shares.json` so it can be deleted later.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SavedShare {
+    pub id: String,
+    pub url: String,
+    #[serde(default)]
+    pub delete_token: Option<String>,
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+impl From<&ShareLogResponse> for SavedShare {
+    fn from(res: &ShareLogResponse) -> Self {
+        Self {
+            id: res.id.clone(),
+            url: res.view_url.clone().unwrap_or_else(|| res.url.clone()),
+            delete_token: res.delete_token.clone(),
+            expires_at: res.expires_at.clone(),
+        }
+    }
+}
+
+/// Recent shares, newest first. Missing or unreadable store means none.
+pub fn load_shares() -> Vec<SavedShare> {
+    fs::read_to_string(shares_path())
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_shares(shares: &[SavedShare]) {
+    if let Ok(s) = serde_json::to_string_pretty(shares) {
+        let _ = write_private(&shares_path(), &s);
+    }
+}
+
+/// Put `share` first in `shares`, replacing an entry with the same id and keeping at most
+/// `MAX_RECENT_SHARES`.
+pub fn push_recent_share(shares: &mut Vec<SavedShare>, share: SavedShare) {
+    shares.retain(|s| s.id != share.id);
+    shares.insert(0, share);
+    shares.truncate(MAX_RECENT_SHARES);
+}
+
+fn remember_share(res: &ShareLogResponse) {
+    let mut shares = load_shares();
+    push_recent_share(&mut shares, SavedShare::from(res));
+    save_shares(&shares);
+}
+
+fn forget_share(id: &str) {
+    let mut shares = load_shares();
+    shares.retain(|s| s.id != id);
+    save_shares(&shares);
+}
+
+/// Share id from either a bare id or a share URL (last path segment, query and fragment ignored).
+pub fn share_id_from(url_or_id: &str) -> &str {
+    let s = url_or_id.trim();
+    let s = s.split(['?', '#']).next().unwrap_or(s);
+    s.trim_end_matches('/').rsplit('/').next().unwrap_or(s)
+}
+
+/// Delete a shared log via DELETE /api/logs/{id}, authorised by the share's delete token.
+/// On success, or when the server no longer has it, the share is dropped from the local store.
+pub async fn delete_shared_log(
+    id: &str,
+    delete_token: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let base_url = app_url()?;
+    let url = format!("{}/api/logs/{}", base_url, id);
+
+    let token = load_token();
+    let response = send_with_retry(|client| {
+        let request = client
+            .delete(&url)
+            .header("Accept", "application/json")
+            .header("X-Delete-Token", delete_token);
+        match &token {
+            Some(token) => {
+                let (name, value) = token_header(token);
+                request.header(name, value)
+            }
+            None => request,
+        }
+    })
+    .await?;
+
+    match response.status().as_u16() {
+        200 | 202 | 204 => {
+            forget_share(id);
+            Ok(())
+        }
+        401 | 403 => Err(fill(t().unshare_forbidden, &[id]).into()),
+        404 => {
+            forget_share(id);
+            Err(fill(t().unshare_not_found, &[id]).into())
+        }
+        _ => {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            Err(fill(t().unshare_failed, &[&status.to_string(), &body]).into())
+        }
+    }
+}
+
+fn print_recent_shares(shares: &[SavedShare]) {
+    if shares.is_empty() {
+        println!("{}", t().no_recent_shares);
+        return;
+    }
+    println!("{}", t().recent_shares_heading);
+    for share in shares {
+        match &share.expires_at {
+            Some(at) => println!(
+                "  {}  {}  ({})",
+                share.id,
+                share.url,
+                fill(t().share_expires, &[at])
+            ),
+            None => println!("  {}  {}", share.id, share.url),
+        }
+    }
+}
+
+/// Run `ratlog unshare [URL_OR_ID]`: delete a share made from this machine, or list them.
+pub async fn unshare(url_or_id: Option<String>) -> color_eyre::Result<()> {
+    let shares = load_shares();
+    let Some(arg) = url_or_id else {
+        println!("{}", t().unshare_usage);
+        println!();
+        print_recent_shares(&shares);
+        return Ok(());
+    };
+    let id = share_id_from(&arg);
+    let delete_token = shares
+        .iter()
+        .find(|s| s.id == id || s.url == arg)
+        .and_then(|s| s.delete_token.clone())
+        .ok_or_else(|| color_eyre::eyre::eyre!("{}", fill(t().unshare_no_token, &[id])))?;
+
+    delete_shared_log(id, &delete_token)
+        .await
+        .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+    println!("{}", fill(t().unshare_done, &[id]));
+    Ok(())
+}
+
+/// One entry from GET /api/logs.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SharedLog {
+    pub id: String,
+    pub url: String,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub line_count: Option<u64>,
+}
+
+/// Parse the GET /api/logs body: a bare array, or an object wrapping it in `data` or `logs`.
+pub fn parse_shared_logs(body: &str) -> Result<Vec<SharedLog>, serde_json::Error> {
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Body {
+        List(Vec<SharedLog>),
+        Data { data: Vec<SharedLog> },
+        Logs { logs: Vec<SharedLog> },
+    }
+    Ok(match serde_json::from_str(body)? {
+        Body::List(logs) | Body::Data { data: logs } | Body::Logs { logs } => logs,
+    })
+}
+
+/// The user's shared logs, newest first as returned by the server.
+pub async fn list_shared_logs() -> Result<Vec<SharedLog>, Box<dyn std::error::Error + Send + Sync>>
+{
+    let base_url = app_url()?;
+    let token = load_token().ok_or(t().not_logged_in)?;
+    let url = format!("{}/api/logs", base_url);
+
+    let (name, value) = token_header(&token);
+    let response = send_with_retry(|client| {
+        client
+            .get(&url)
+            .header("Accept", "application/json")
+            .header(name.as_str(), value.as_str())
+    })
+    .await?;
+
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if status.is_success() {
+        Ok(parse_shared_logs(&body)?)
+    } else {
+        Err(fill(t().shares_failed, &[&status.to_string(), &body]).into())
+    }
+}
+
+/// Plain-text table of shared logs with aligned columns.
+pub fn format_shares_table(logs: &[SharedLog]) -> String {
+    let header = [
+        t().shares_col_id,
+        t().shares_col_url,
+        t().shares_col_created,
+        t().shares_col_lines,
+    ];
+    let rows: Vec<[String; 4]> = logs
+        .iter()
+        .map(|log| {
+            [
+                log.id.clone(),
+                log.url.clone(),
+                log.created_at.clone().unwrap_or_else(|| "-".to_string()),
+                log.line_count
+                    .map_or_else(|| "-".to_string(), |n| n.to_string()),
+            ]
+        })
+        .collect();
+    let mut widths = header.map(|h| h.chars().count());
+    for row in &rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    let line = |cells: [&str; 4]| {
+        let mut out = String::new();
+        for (i, cell) in cells.iter().enumerate() {
+            if i == cells.len() - 1 {
+                out.push_str(cell);
+            } else {
+                out.push_str(&format!("{:<width$}  ", cell, width = widths[i]));
+            }
+        }
+        out
+    };
+    let mut out = line(header);
+    for row in &rows {
+        out.push('\n');
+        out.push_str(&line([&row[0], &row[1], &row[2], &row[3]]));
+    }
+    out
+}
+
+/// Run `ratlog shares [--json]`.
+pub async fn shares(json: bool) -> color_eyre::Result<()> {
+    let logs = list_shared_logs()
+        .await
+        .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&logs)?);
+    } else if logs.is_empty() {
+        println!("{}", t().no_shared_logs);
+    } else {
+        println!("{}", format_shares_table(&logs));
+    }
+    Ok(())
+}
@@ -4,52 +4,672 @@ use std::collections::VecDeque;
 use std::fs;
 use std::fs::File;
 use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-use crate::constants::{MAX_LINE_LEN, MAX_LINES, TAIL_READ_SIZE};
+use regex::{Regex, RegexBuilder};
+use unicode_width::UnicodeWidthStr;
+
+use crate::constants::{
+    LINE_SEP_SNIFF, MAX_LINE_LEN, MAX_LINES, REGEX_SIZE_LIMIT, TAIL_COUNT_LIMIT, TAIL_READ_SIZE,
+};
+use crate::i18n::{fill, t};
+use crate::remote::{Fetched, RemoteSource};
+use crate::util::{expand_path, glob_match};
+
+/// What ends a line (`--line-sep`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineSep {
+    /// [`LineSep::Cr`] when the start of the data (see [`LineSep::detect`]) has a `\r` but no
+    /// `\n`, otherwise [`LineSep::Crlf`].
+    #[default]
+    Auto,
+    /// `\n` only; a `\r` before it stays in the line.
+    Lf,
+    /// `\n`, dropping one `\r` before it, so files mixing `\r\n` and `\n` read cleanly.
+    Crlf,
+    /// A lone `\r`, as written by classic Mac OS tools.
+    Cr,
+}
+
+impl LineSep {
+    pub fn name(self) -> &'static str {
+        match self {
+            LineSep::Auto => "auto",
+            LineSep::Lf => "lf",
+            LineSep::Crlf => "crlf",
+            LineSep::Cr => "cr",
+        }
+    }
+    pub fn all() -> &'static [LineSep] {
+        &[LineSep::Auto, LineSep::Lf, LineSep::Crlf, LineSep::Cr]
+    }
+    pub fn from_name(name: &str) -> Option<LineSep> {
+        LineSep::all()
+            .iter()
+            .find(|s| s.name().eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    /// [`LineSep::Auto`] resolved for data starting with `head`; other values as they are.
+    pub fn detect(self, head: &[u8]) -> LineSep {
+        match self {
+            LineSep::Auto if !head.contains(&b'\n') && head.contains(&b'\r') => LineSep::Cr,
+            LineSep::Auto => LineSep::Crlf,
+            sep => sep,
+        }
+    }
+
+    /// [`LineSep::detect`] with the first bytes of the file at `path`.
+    pub fn detect_file(self, path: &Path) -> LineSep {
+        if self != LineSep::Auto {
+            return self;
+        }
+        let mut head = Vec::new();
+        let _ = File::open(path).and_then(|f| f.take(LINE_SEP_SNIFF).read_to_end(&mut head));
+        self.detect(&head)
+    }
+
+    /// The byte ending a line; `Auto` counts as `Crlf`.
+    fn byte(self) -> u8 {
+        match self {
+            LineSep::Cr => b'\r',
+            _ => b'\n',
+        }
+    }
+
+    /// `line` without its terminator (and the `\r` before it for `Crlf`).
+    fn trim(self, line: &[u8]) -> &[u8] {
+        let line = line.strip_suffix(&[self.byte()]).unwrap_or(line);
+        match self {
+            LineSep::Auto | LineSep::Crlf => line.strip_suffix(b"\r").unwrap_or(line),
+            LineSep::Lf | LineSep::Cr => line,
+        }
+    }
+
+    fn trim_str(self, line: &str) -> &str {
+        // Only ASCII bytes are removed, so the rest is still valid UTF-8.
+        let len = self.trim(line.as_bytes()).len();
+        &line[..len]
+    }
+}
 
 /// Given file content, returns (last MAX_LINES lines, byte offset, 1-based file line number of first line).
-#[allow(dead_code)]
+/// A final line without `\n` counts like any other, so `"a\nb"` and `"a\nb\n"` both have 2 lines.
 pub fn parse_log_content(content: &str) -> (Vec<String>, u64, usize) {
-    let lines: Vec<&str> = content.lines().collect();
-    let total = lines.len();
-    let skip = total.saturating_sub(MAX_LINES);
+    parse_last_lines(content, MAX_LINES, LineSep::Auto)
+}
+
+/// [`parse_log_content`] keeping the last `max_lines` lines, split at `sep`.
+fn parse_last_lines(content: &str, max_lines: usize, sep: LineSep) -> (Vec<String>, u64, usize) {
+    let sep = sep.detect(&content.as_bytes()[..content.len().min(LINE_SEP_SNIFF as usize)]);
+    // Keep terminators so the offset counts `\r\n` endings as two bytes.
+    let raw: Vec<&str> = content.split_inclusive(sep.byte() as char).collect();
+    let skip = raw.len().saturating_sub(max_lines);
     let file_line_start = skip + 1;
-    let kept: Vec<String> = lines[skip..].iter().map(|s| s.to_string()).collect();
-    let file_offset = content
-        .lines()
-        .take(skip)
-        .map(|l| l.len() + 1)
-        .sum::<usize>() as u64;
+    let kept: Vec<String> = raw[skip..]
+        .iter()
+        .map(|l| sep.trim_str(l).to_string())
+        .collect();
+    let file_offset = raw[..skip].iter().map(|l| l.len()).sum::<usize>() as u64;
     (kept, file_offset, file_line_start)
 }
 
+/// Compiled `re:` pattern of a [`FilterSpec`]; two are equal when their sources are.
+#[derive(Debug, Clone)]
+pub struct FilterRegex(pub Regex);
+
+impl PartialEq for FilterRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for FilterRegex {}
+
+/// A parsed filter: which lines to keep. [`FilterSpec::parse`] reads the filter field syntax;
+/// embedders can also fill the fields directly. An empty spec keeps every line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterSpec {
+    /// Keep lines containing any of these terms; no terms keeps all lines.
+    pub include: Vec<String>,
+    /// Keep only lines matching this regular expression (the `re:` prefix), as well as `include`.
+    pub regex: Option<FilterRegex>,
+    /// Drop lines containing any of these terms.
+    pub exclude: Vec<String>,
+    /// Compare terms with the exact case instead of case-insensitively.
+    pub case_sensitive: bool,
+    /// Match terms against this 1-based whitespace-separated field only; lines with fewer
+    /// fields don't match.
+    pub column: Option<usize>,
+    /// Keep lines whose level word (see [`line_level`]) is one of these; empty keeps any.
+    pub levels: Vec<String>,
+    /// Keep lines whose leading timestamp (see [`leading_timestamp`]) sorts within
+    /// `from..=to` as text, which works for timestamps of one fixed format such as ISO 8601.
+    /// Lines without a timestamp don't match.
+    pub time_range: Option<(String, String)>,
+    /// Match terms, the regex and `column` against the line after its leading timestamp (see
+    /// [`timestamp_len`]), so a year or hour in the timestamp doesn't match every line.
+    pub message_only: bool,
+}
+
+impl FilterSpec {
+    /// Spec for the filter field: terms separated by `|` (see [`filter_alternatives`]), matched
+    /// case-insensitively, optionally after a `colN:` prefix (see [`column_filter`]). After
+    /// `re:` the rest is a case-insensitive regular expression; one that doesn't compile is
+    /// matched as plain text instead (see [`FilterSpec::try_parse`]).
+    pub fn parse(filter: &str) -> FilterSpec {
+        Self::parse_with_error(filter).0
+    }
+
+    /// [`FilterSpec::parse`], failing when a `re:` pattern is not a valid regular expression or
+    /// compiles to more than REGEX_SIZE_LIMIT (`regex::Error::CompiledTooBig`).
+    pub fn try_parse(filter: &str) -> Result<FilterSpec, regex::Error> {
+        match Self::parse_with_error(filter) {
+            (spec, None) => Ok(spec),
+            (_, Some(e)) => Err(e),
+        }
+    }
+
+    fn parse_with_error(filter: &str) -> (FilterSpec, Option<regex::Error>) {
+        let filter = filter.trim();
+        let (column, terms) = match column_filter(filter) {
+            Some((column, terms)) => (Some(column), terms),
+            None => (None, filter),
+        };
+        let spec = FilterSpec {
+            column,
+            ..FilterSpec::default()
+        };
+        let Some(pattern) = regex_pattern(terms) else {
+            let include = filter_alternatives(terms);
+            return (FilterSpec { include, ..spec }, None);
+        };
+        if pattern.is_empty() {
+            return (spec, None);
+        }
+        let built = RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .dfa_size_limit(REGEX_SIZE_LIMIT)
+            .build();
+        match built {
+            Ok(re) => (
+                FilterSpec {
+                    regex: Some(FilterRegex(re)),
+                    ..spec
+                },
+                None,
+            ),
+            Err(e) => (
+                FilterSpec {
+                    include: vec![pattern.to_string()],
+                    ..spec
+                },
+                Some(e),
+            ),
+        }
+    }
+
+    /// Whether the spec keeps every line.
+    pub fn is_empty(&self) -> bool {
+        self == &FilterSpec {
+            case_sensitive: self.case_sensitive,
+            message_only: self.message_only,
+            ..FilterSpec::default()
+        }
+    }
+
+    pub fn matches(&self, line: &str) -> bool {
+        let message = if self.message_only {
+            &line[timestamp_len(line).unwrap_or(0)..]
+        } else {
+            line
+        };
+        let text = match self.column {
+            Some(column) => match message.split_whitespace().nth(column - 1) {
+                Some(field) => field,
+                None => return false,
+            },
+            None => message,
+        };
+        let fold = |s: &str| {
+            if self.case_sensitive {
+                s.to_string()
+            } else {
+                s.to_lowercase()
+            }
+        };
+        if let Some(FilterRegex(re)) = &self.regex
+            && !re.is_match(text)
+        {
+            return false;
+        }
+        let text = fold(text);
+        let contains = |term: &String| text.contains(&fold(term));
+        if !self.include.is_empty() && !self.include.iter().any(contains) {
+            return false;
+        }
+        if self.exclude.iter().any(contains) {
+            return false;
+        }
+        if !self.levels.is_empty()
+            && !line_level(line)
+                .is_some_and(|level| self.levels.iter().any(|l| l.eq_ignore_ascii_case(level)))
+        {
+            return false;
+        }
+        match &self.time_range {
+            Some((from, to)) => leading_timestamp(line)
+                .is_some_and(|time| from.as_str() <= time && time <= to.as_str()),
+            None => true,
+        }
+    }
+}
+
 /// Filter lines by query (case-insensitive substring); returns at most max_lines (last N matches).
+/// A `colN:` prefix restricts the match to the N-th whitespace-separated field (see
+/// [`column_filter`]); `a|b` matches either term (see [`filter_alternatives`]).
+/// Only the stored line text is matched: the gutter (line number and `│`) is display-only, so a
+/// numeric query finds numbers inside log lines, not line numbers (use `:` to go to a line).
 pub fn apply_filter(lines: &[String], filter: &str, max_lines: usize) -> Vec<(usize, String)> {
-    let q = filter.trim().to_lowercase();
-    let with_idx: Vec<(usize, String)> = if q.is_empty() {
-        lines
-            .iter()
-            .enumerate()
-            .map(|(i, s)| (i, s.clone()))
-            .collect()
+    apply_filter_spec(lines, &FilterSpec::parse(filter), max_lines)
+}
+
+/// One `--output-json` record: `{"line_no": N, "text": "..."}`, N being the file line number.
+pub fn json_record(line_no: usize, text: &str) -> String {
+    #[derive(serde::Serialize)]
+    struct Record<'a> {
+        line_no: usize,
+        text: &'a str,
+    }
+    serde_json::to_string(&Record { line_no, text }).unwrap_or_default()
+}
+
+/// Lines kept by `spec` with their indices; at most `max_lines` (the last N matches).
+pub fn apply_filter_spec(
+    lines: &[String],
+    spec: &FilterSpec,
+    max_lines: usize,
+) -> Vec<(usize, String)> {
+    with_lines(lines, filter_indices(lines, spec, max_lines))
+}
+
+/// Indices of the lines [`apply_filter_spec`] keeps, without copying them.
+pub fn filter_indices(lines: &[String], spec: &FilterSpec, max_lines: usize) -> Vec<usize> {
+    let keep_all = spec.is_empty();
+    let indices = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| keep_all || spec.matches(line))
+        .map(|(i, _)| i)
+        .collect();
+    last_n(indices, max_lines)
+}
+
+/// Like [`apply_filter`], but lines are grouped into entries (see [`entry_starts`]) and a match
+/// on any line of an entry keeps the whole entry, so a stack trace stays with its message.
+pub fn apply_entry_filter(
+    lines: &[String],
+    filter: &str,
+    max_lines: usize,
+) -> Vec<(usize, String)> {
+    apply_entry_filter_spec(lines, &FilterSpec::parse(filter), max_lines)
+}
+
+/// [`apply_entry_filter`] with a [`FilterSpec`].
+pub fn apply_entry_filter_spec(
+    lines: &[String],
+    spec: &FilterSpec,
+    max_lines: usize,
+) -> Vec<(usize, String)> {
+    with_lines(lines, entry_filter_indices(lines, spec, max_lines))
+}
+
+/// Indices of the lines [`apply_entry_filter_spec`] keeps, without copying them.
+pub fn entry_filter_indices(lines: &[String], spec: &FilterSpec, max_lines: usize) -> Vec<usize> {
+    if spec.is_empty() {
+        return filter_indices(lines, spec, max_lines);
+    }
+    let entries = entry_starts(lines);
+    let mut matched = vec![false; lines.len()];
+    for (i, line) in lines.iter().enumerate() {
+        if spec.matches(line) {
+            matched[entries[i]] = true;
+        }
+    }
+    let indices = (0..lines.len()).filter(|&i| matched[entries[i]]).collect();
+    last_n(indices, max_lines)
+}
+
+fn with_lines(lines: &[String], indices: Vec<usize>) -> Vec<(usize, String)> {
+    indices.into_iter().map(|i| (i, lines[i].clone())).collect()
+}
+
+fn last_n(mut indices: Vec<usize>, max_lines: usize) -> Vec<usize> {
+    if indices.len() > max_lines {
+        indices.drain(..indices.len() - max_lines);
+    }
+    indices
+}
+
+/// Whether `line` opens a new log entry: after an optional `[`, it starts with a date
+/// (`2025-02-15`, `2025/02/15`), a time (`10:00:01`) or a syslog month and day (`Feb 15`).
+pub fn starts_with_timestamp(line: &str) -> bool {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let s = line.strip_prefix('[').unwrap_or(line).as_bytes();
+    let digits = |range: std::ops::Range<usize>| {
+        s.get(range)
+            .is_some_and(|b| b.iter().all(u8::is_ascii_digit))
+    };
+    let date = digits(0..4) && matches!(s.get(4), Some(b'-' | b'/')) && digits(5..7);
+    let time = digits(0..2) && s.get(2) == Some(&b':') && digits(3..5);
+    let syslog = MONTHS.iter().any(|m| s.starts_with(m.as_bytes()))
+        && s.get(3) == Some(&b' ')
+        && s.get(4..6).is_some_and(|d| {
+            d.iter().all(|&b| b == b' ' || b.is_ascii_digit()) && d[1].is_ascii_digit()
+        });
+    date || time || syslog
+}
+
+/// Whether `line` is empty or only whitespace (hidden by the **Blank lines** setting).
+pub fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Level words recognised by [`line_level`].
+const LEVELS: [&str; 9] = [
+    "TRACE", "DEBUG", "INFO", "NOTICE", "WARN", "WARNING", "ERROR", "CRITICAL", "FATAL",
+];
+
+/// First word of `line` that is an upper-case log level such as `ERROR` or `WARN`.
+pub fn line_level(line: &str) -> Option<&str> {
+    line.split(|c: char| !c.is_ascii_alphabetic())
+        .find(|word| LEVELS.contains(word))
+}
+
+/// Timestamp at the start of `line` (see [`starts_with_timestamp`]) without surrounding
+/// brackets: a syslog `Feb 15 10:00:01`, or the first word, joined with the next one when it
+/// is the time after a bare date (`2025-02-15 10:00:01`).
+pub fn leading_timestamp(line: &str) -> Option<&str> {
+    if !starts_with_timestamp(line) {
+        return None;
+    }
+    let s = line.strip_prefix('[').unwrap_or(line);
+    if s.as_bytes().first().is_some_and(u8::is_ascii_alphabetic) {
+        return s.get(..15).or(Some(s));
+    }
+    let word_end = |from: usize| {
+        s[from..]
+            .find(|c: char| c.is_whitespace() || c == ']')
+            .map_or(s.len(), |i| from + i)
+    };
+    let mut end = word_end(0);
+    let next = s[end..].strip_prefix(' ');
+    let is_time = |t: &str| t.as_bytes().get(2) == Some(&b':') && t.as_bytes()[0].is_ascii_digit();
+    if end == 10 && next.is_some_and(is_time) {
+        end = word_end(end + 1);
+    }
+    Some(&s[..end])
+}
+
+/// Length in bytes of the leading timestamp of `line` (see [`leading_timestamp`]) with its
+/// brackets and the whitespace after it: where the message starts. `None` without one.
+pub fn timestamp_len(line: &str) -> Option<usize> {
+    let time = leading_timestamp(line)?;
+    // The timestamp starts the line, after at most a `[`.
+    let after = usize::from(line.starts_with('[')) + time.len();
+    let rest = line[after..].strip_prefix(']').unwrap_or(&line[after..]);
+    Some(line.len() - rest.trim_start().len())
+}
+
+/// `line` split into its leading timestamp (see [`leading_timestamp`]), the level word right
+/// after it (`ERROR`, `[WARN]`, `INFO:`; empty when there is none) and the rest of the line.
+/// `None` when the line does not start with a timestamp.
+pub fn split_columns(line: &str) -> Option<(&str, &str, &str)> {
+    let time = leading_timestamp(line)?;
+    let rest = &line[timestamp_len(line)?..];
+    let word = rest.split_whitespace().next().unwrap_or("");
+    let level = word.trim_start_matches('[').trim_end_matches([']', ':']);
+    if LEVELS.contains(&level) {
+        Some((time, level, rest[word.len()..].trim_start()))
     } else {
-        lines
-            .iter()
-            .enumerate()
-            .filter(|(_, line)| line.to_lowercase().contains(&q))
-            .map(|(i, s)| (i, s.clone()))
-            .collect()
+        Some((time, "", rest))
+    }
+}
+
+/// Timestamp and level column widths fitting every line of `lines` that [`split_columns`]
+/// can split.
+pub fn column_widths<'a>(lines: impl IntoIterator<Item = &'a str>) -> (usize, usize) {
+    lines.into_iter().filter_map(split_columns).fold(
+        (0, 0),
+        |(time_width, level_width), (time, level, _)| {
+            (time_width.max(time.width()), level_width.max(level.len()))
+        },
+    )
+}
+
+/// `line` for the column view: timestamp and level padded to `widths` (see [`column_widths`]),
+/// then the message. Lines without a timestamp are indented to the message column, so stack
+/// traces and wrapped messages line up under it.
+pub fn columnize(line: &str, (time_width, level_width): (usize, usize)) -> String {
+    if time_width == 0 {
+        return line.to_string();
+    }
+    let level_col = if level_width > 0 { level_width + 1 } else { 0 };
+    match split_columns(line) {
+        Some((time, level, rest)) if level_width > 0 => {
+            let pad = time_width.saturating_sub(time.width());
+            format!(
+                "{}{} {:<level_width$} {}",
+                time,
+                " ".repeat(pad),
+                level,
+                rest
+            )
+        }
+        Some((time, _, rest)) => {
+            let pad = time_width.saturating_sub(time.width());
+            format!("{}{} {}", time, " ".repeat(pad), rest)
+        }
+        None => format!("{}{}", " ".repeat(time_width + 1 + level_col), line),
+    }
+}
+
+/// For each line, the index of the first line of its entry. An entry is a timestamped line
+/// plus the lines after it up to the next timestamped one (stack traces, wrapped messages);
+/// lines before the first timestamped line are entries of their own.
+pub fn entry_starts(lines: &[String]) -> Vec<usize> {
+    let mut current = None;
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            if starts_with_timestamp(line) {
+                current = Some(i);
+            }
+            current.unwrap_or(i)
+        })
+        .collect()
+}
+
+/// Row to select when moving one entry up or down from `selected`, given each row's entry id
+/// (see [`entry_starts`]). Moving up from inside an entry first goes to its first row.
+pub fn entry_step(row_entries: &[usize], selected: usize, forward: bool) -> usize {
+    let Some(&current) = row_entries.get(selected) else {
+        return selected;
     };
-    if with_idx.len() <= max_lines {
-        with_idx
+    if forward {
+        row_entries[selected..]
+            .iter()
+            .position(|&e| e != current)
+            .map_or(selected, |p| selected + p)
     } else {
-        with_idx[with_idx.len() - max_lines..].to_vec()
+        let start = row_entries[..selected]
+            .iter()
+            .rposition(|&e| e != current)
+            .map_or(0, |p| p + 1);
+        if start < selected || start == 0 {
+            start
+        } else {
+            let previous = row_entries[start - 1];
+            row_entries[..start]
+                .iter()
+                .rposition(|&e| e != previous)
+                .map_or(0, |p| p + 1)
+        }
     }
 }
 
-fn read_line_bounded<R: BufRead>(r: &mut R) -> io::Result<Option<String>> {
-    let mut buf = Vec::with_capacity(4096.min(MAX_LINE_LEN));
+/// Whether `line` matches a non-empty filter, using the same rule as [`apply_filter`].
+pub fn line_matches(line: &str, filter: &str) -> bool {
+    let spec = FilterSpec::parse(filter);
+    !spec.is_empty() && spec.matches(line)
+}
+
+/// Pattern after a `re:` prefix (any case), trimmed; `None` for plain filters.
+pub fn regex_pattern(filter: &str) -> Option<&str> {
+    filter
+        .get(..3)
+        .filter(|p| p.eq_ignore_ascii_case("re:"))
+        .map(|_| filter[3..].trim())
+}
+
+/// Split a filter on `|` into trimmed, non-empty alternatives: `ERROR|timeout` gives `ERROR`
+/// and `timeout`. Write `\|` for a literal `|` (`a\|b` is the single term `a|b`).
+pub fn filter_alternatives(filter: &str) -> Vec<String> {
+    let mut alternatives = Vec::new();
+    let mut current = String::new();
+    let mut chars = filter.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                current.push('|');
+                chars.next();
+            }
+            '|' => alternatives.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    alternatives.push(current);
+    alternatives
+        .into_iter()
+        .map(|alt| alt.trim().to_string())
+        .filter(|alt| !alt.is_empty())
+        .collect()
+}
+
+/// Split a `colN:term` filter into the 1-based column and the term, e.g. `col3:ERROR` gives
+/// `(3, "ERROR")`. Returns `None` for plain filters, including `col0:`, so they match as text.
+pub fn column_filter(filter: &str) -> Option<(usize, &str)> {
+    if !filter.get(..3)?.eq_ignore_ascii_case("col") {
+        return None;
+    }
+    let (digits, term) = filter[3..].split_once(':')?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let column = digits.parse::<usize>().ok().filter(|&n| n > 0)?;
+    Some((column, term.trim_start()))
+}
+
+/// Whitespace-separated words of `line` with surrounding quotes, brackets and punctuation
+/// trimmed, for picking a filter: `client 192.168.1.1,` gives `client`, `192.168.1.1`.
+pub fn line_tokens(line: &str) -> Vec<&str> {
+    const TRIM: &[char] = &[
+        '"', '\'', '`', '(', ')', '[', ']', '{', '}', '<', '>', ',', ';', ':', '.', '!', '?',
+    ];
+    line.split_whitespace()
+        .map(|word| word.trim_matches(TRIM))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Split newly appended text into complete lines, carrying an unterminated tail in `partial`.
+/// A trailing `\r` (CRLF endings) is removed from each line. Blank lines are kept, as when the
+/// file is loaded, however the text is cut into reads.
+pub fn split_appended(partial: &mut String, chunk: &str) -> Vec<String> {
+    split_appended_with(partial, chunk, LineSep::Crlf)
+}
+
+/// [`split_appended`] for lines ending in `sep`. A read can stop anywhere, so `Auto` is not
+/// detected here and reads as `Crlf`; resolve it once with [`LineSep::detect_file`].
+pub fn split_appended_with(partial: &mut String, chunk: &str, sep: LineSep) -> Vec<String> {
+    let mut full = std::mem::take(partial);
+    full.push_str(chunk);
+    let mut lines: Vec<&str> = full.split(sep.byte() as char).collect();
+    // The piece after the last terminator is unterminated (empty when the text ends with one).
+    *partial = lines.pop().unwrap_or("").to_string();
+    lines
+        .into_iter()
+        .map(|line| sep.trim_str(line).to_string())
+        .collect()
+}
+
+/// Decode appended bytes after the `pending` ones from the previous read. An incomplete UTF-8
+/// sequence at the end (a read cut mid-character, e.g. at POLL_READ_CAP) is carried back into
+/// `pending` for the next read; other invalid bytes become `�`.
+pub fn decode_appended(pending: &mut Vec<u8>, chunk: &[u8]) -> String {
+    let mut bytes = std::mem::take(pending);
+    bytes.extend_from_slice(chunk);
+    let tail_start = bytes.len().saturating_sub(3);
+    let carry = (tail_start..bytes.len())
+        .rev()
+        .find(|&i| bytes[i] & 0xC0 != 0x80)
+        .filter(|&i| std::str::from_utf8(&bytes[i..]).is_err_and(|e| e.error_len().is_none()))
+        .unwrap_or(bytes.len());
+    *pending = bytes.split_off(carry);
+    match String::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+    }
+}
+
+/// [`decode_appended`] for a UTF-16 file in the given byte order. A read cut between the two
+/// bytes of a code unit, or after the first half of a surrogate pair, carries those bytes in
+/// `pending` to the next read.
+pub fn decode_appended_utf16(pending: &mut Vec<u8>, chunk: &[u8], big_endian: bool) -> String {
+    let mut bytes = std::mem::take(pending);
+    bytes.extend_from_slice(chunk);
+    let mut carry = bytes.len() - bytes.len() % 2;
+    if carry >= 2 {
+        let pair = [bytes[carry - 2], bytes[carry - 1]];
+        let last = if big_endian {
+            u16::from_be_bytes(pair)
+        } else {
+            u16::from_le_bytes(pair)
+        };
+        if (0xD800..0xDC00).contains(&last) {
+            carry -= 2;
+        }
+    }
+    *pending = bytes.split_off(carry);
+    decode_utf16_bytes(&bytes, big_endian)
+}
+
+/// `line` cut to at most `max_len` bytes on a char boundary, with `...` marking the cut.
+fn cut_line(line: String, max_len: usize) -> String {
+    if line.len() <= max_len {
+        return line;
+    }
+    let mut end = max_len;
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...", &line[..end])
+}
+
+/// Read one line ending in `sep` (already detected), keeping at most `max_len` bytes of it; the
+/// rest up to the terminator is skipped.
+fn read_line_bounded<R: BufRead>(
+    r: &mut R,
+    max_len: usize,
+    sep: LineSep,
+) -> io::Result<Option<String>> {
+    let terminator = sep.byte();
+    let mut buf = Vec::with_capacity(4096.min(max_len));
     let mut total = 0usize;
     loop {
         let (consume_amt, done, skip_until_newline) = {
@@ -59,11 +679,11 @@ fn read_line_bounded<R: BufRead>(r: &mut R) -> io::Result<Option<String>> {
             }
             let mut found = None;
             for (i, &b) in chunk.iter().enumerate() {
-                if b == b'\n' {
+                if b == terminator {
                     found = Some(i);
                     break;
                 }
-                if total + i >= MAX_LINE_LEN {
+                if total + i >= max_len {
                     break;
                 }
             }
@@ -72,8 +692,8 @@ fn read_line_bounded<R: BufRead>(r: &mut R) -> io::Result<Option<String>> {
                     buf.extend_from_slice(&chunk[..=i]);
                     (i + 1, true, false)
                 }
-                None if total + chunk.len() >= MAX_LINE_LEN => {
-                    let take = (MAX_LINE_LEN - total).min(chunk.len());
+                None if total + chunk.len() >= max_len => {
+                    let take = (max_len - total).min(chunk.len());
                     buf.extend_from_slice(&chunk[..take]);
                     (take, true, true)
                 }
@@ -92,7 +712,7 @@ fn read_line_bounded<R: BufRead>(r: &mut R) -> io::Result<Option<String>> {
                         if c.is_empty() {
                             (0, true)
                         } else {
-                            match c.iter().position(|&b| b == b'\n') {
+                            match c.iter().position(|&b| b == terminator) {
                                 Some(pos) => (pos + 1, true),
                                 None => (c.len(), false),
                             }
@@ -111,77 +731,431 @@ fn read_line_bounded<R: BufRead>(r: &mut R) -> io::Result<Option<String>> {
     if buf.is_empty() {
         return Ok(None);
     }
-    let s = String::from_utf8_lossy(&buf)
-        .trim_end_matches('\n')
-        .to_string();
-    Ok(Some(s))
+    Ok(Some(String::from_utf8_lossy(sep.trim(&buf)).into_owned()))
 }
 
-fn offset_after_n_newlines(path: &PathBuf, n: usize) -> io::Result<u64> {
-    if n == 0 {
-        return Ok(0);
+/// Reader adapter counting the bytes consumed, so line offsets come from the same pass.
+struct CountingReader<R> {
+    inner: R,
+    consumed: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.consumed += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
     }
-    let f = File::open(path)?;
-    let mut r = BufReader::new(f);
-    let mut offset: u64 = 0;
-    let mut newlines_seen: usize = 0;
-    let mut chunk = [0u8; 65536];
+
+    fn consume(&mut self, amt: usize) {
+        self.consumed += amt as u64;
+        self.inner.consume(amt);
+    }
+}
+
+/// Where the first whole line of a tail read starts in `content`: after its first terminator
+/// (the line before is likely partial), or 0 when there is none.
+fn tail_start(content: &[u8], sep: LineSep) -> usize {
+    let terminator = sep.detect(content).byte();
+    content
+        .iter()
+        .position(|&b| b == terminator)
+        .map_or(0, |i| i + 1)
+}
+
+/// Split the tail of a large file into lines ending in `sep`, dropping the first (likely
+/// partial) line (see [`tail_start`]). Lines over `max_len` bytes (MAX_LINE_LEN unless `--raw`)
+/// are cut on a char boundary and end with `...`. Blank lines are kept, so the lines stay
+/// numbered like the file's.
+pub(crate) fn parse_tail_lines(content: &[u8], max_len: usize, sep: LineSep) -> Vec<String> {
+    let sep = sep.detect(content);
+    let content = &content[tail_start(content, sep)..];
+    let mut lines = Vec::new();
+    for line in content.split_inclusive(|&b| b == sep.byte()) {
+        let line = sep.trim(line);
+        lines.push(cut_line(String::from_utf8_lossy(line).to_string(), max_len));
+    }
+    lines
+}
+
+/// Lines ending in `sep` in the first `end` bytes of the file at `path`: the 1-based number of
+/// the line starting at `end`, minus one.
+fn count_lines_before(path: &Path, end: u64, sep: LineSep) -> io::Result<usize> {
+    let terminator = sep.byte();
+    let mut reader = BufReader::with_capacity(1 << 20, File::open(path)?.take(end));
+    let mut count = 0;
     loop {
-        let nread = r.read(&mut chunk)?;
-        if nread == 0 {
-            break;
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            return Ok(count);
         }
-        for (i, &b) in chunk[..nread].iter().enumerate() {
-            if b == b'\n' {
-                newlines_seen += 1;
-                if newlines_seen == n {
-                    return Ok(offset + i as u64 + 1);
-                }
-            }
-        }
-        offset += nread as u64;
+        count += chunk.iter().filter(|&&b| b == terminator).count();
+        let len = chunk.len();
+        reader.consume(len);
     }
-    Ok(offset)
 }
 
-fn parse_tail_lines(mut content: &[u8]) -> Vec<String> {
-    if let Some(first_nl) = content.iter().position(|&b| b == b'\n') {
-        content = &content[first_nl + 1..];
+/// Whether [`load_logs_with`] numbers the lines of the file at `path` from 1 instead of with
+/// their line numbers in the file: a tail read (see TAIL_READ_SIZE) of a UTF-16 file, or of one
+/// where more than TAIL_COUNT_LIMIT bytes come before the tail and counting them would make
+/// opening slow.
+pub fn tail_numbers_relative(path: &Path, options: LoadOptions) -> bool {
+    let Ok(meta) = fs::metadata(path) else {
+        return false;
+    };
+    let file_size = meta.len();
+    if file_size <= TAIL_READ_SIZE || options.max_lines() > MAX_LINES {
+        return false;
     }
-    let mut lines = Vec::new();
-    for line in content.split(|&b| b == b'\n') {
-        let s = String::from_utf8_lossy(line).to_string();
-        let truncated = if s.len() > MAX_LINE_LEN {
-            format!("{}...", &s[..MAX_LINE_LEN])
+    matches!(utf16_bom(path), Ok(Some(_))) || file_size - TAIL_READ_SIZE > TAIL_COUNT_LIMIT
+}
+
+/// Stream `reader` keeping the last `max_lines` lines; returns them with the 1-based line
+/// number of the first kept line and the number of bytes read, where live mode picks up. Lines
+/// end in `sep` (`Auto` is detected from the first buffered bytes) and are cut after
+/// `max_line_len` bytes. A UTF-8 byte order mark before the first line is dropped (its bytes
+/// still count towards the offset).
+fn read_last_lines<R: BufRead>(
+    reader: R,
+    max_lines: usize,
+    max_line_len: usize,
+    sep: LineSep,
+) -> io::Result<(Vec<String>, usize, u64)> {
+    let mut reader = CountingReader {
+        inner: reader,
+        consumed: 0,
+    };
+    let sep = sep.detect(reader.fill_buf()?);
+    let mut deque: VecDeque<String> = VecDeque::with_capacity(max_lines.min(MAX_LINES) + 1);
+    let mut total_lines: usize = 0;
+    while let Some(mut line) = read_line_bounded(&mut reader, max_line_len, sep)? {
+        if total_lines == 0 && line.starts_with('\u{feff}') {
+            line.drain(..'\u{feff}'.len_utf8());
+        }
+        total_lines += 1;
+        deque.push_back(line);
+        if deque.len() > max_lines {
+            deque.pop_front();
+        }
+    }
+    let kept: Vec<String> = deque.into();
+    let file_line_start = total_lines.saturating_sub(kept.len()) + 1;
+    Ok((kept, file_line_start, reader.consumed))
+}
+
+/// UTF-16 byte order of a file starting with a UTF-16 BOM: `Some(true)` for big-endian,
+/// `Some(false)` for little-endian, `None` otherwise (read as UTF-8).
+pub fn utf16_bom(path: &Path) -> io::Result<Option<bool>> {
+    let mut bom = [0u8; 2];
+    let mut file = File::open(path)?;
+    let n = file.read(&mut bom)?;
+    Ok(match &bom[..n] {
+        [0xFE, 0xFF] => Some(true),
+        [0xFF, 0xFE] => Some(false),
+        _ => None,
+    })
+}
+
+/// Decode UTF-16 code units, replacing unpaired surrogates and a trailing odd byte with `�`.
+fn decode_utf16_bytes(bytes: &[u8], big_endian: bool) -> String {
+    let units = bytes.chunks_exact(2).map(|pair| {
+        if big_endian {
+            u16::from_be_bytes([pair[0], pair[1]])
         } else {
-            s
-        };
-        if !truncated.is_empty() {
-            lines.push(truncated);
+            u16::from_le_bytes([pair[0], pair[1]])
         }
+    });
+    let mut s: String = char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect();
+    if bytes.len() % 2 == 1 {
+        s.push(char::REPLACEMENT_CHARACTER);
     }
-    if lines.len() > MAX_LINES {
-        lines[lines.len() - MAX_LINES..].to_vec()
+    s
+}
+
+/// Load the last MAX_LINES of a UTF-16 file (BOM already detected), or as many as `options`
+/// ask for. Large files are otherwise read from their last TAIL_READ_SIZE bytes, and long lines
+/// cut, like UTF-8 ones. The returned offset is the file size, so live mode only picks up bytes appended later.
+fn load_utf16_logs(
+    path: PathBuf,
+    file_size: u64,
+    big_endian: bool,
+    options: LoadOptions,
+) -> io::Result<(Vec<String>, Option<PathBuf>, u64, usize)> {
+    let max_lines = options.max_lines();
+    let is_tail = max_lines <= MAX_LINES && file_size > TAIL_READ_SIZE + 2;
+    // Start on a code unit boundary; the BOM occupies the first two bytes.
+    let start = if is_tail {
+        (file_size - TAIL_READ_SIZE) & !1
     } else {
-        lines
+        2
+    };
+    let mut file = File::open(&path)?;
+    file.seek(SeekFrom::Start(start))?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    let text = decode_utf16_bytes(&buf, big_endian);
+    let sep = options.line_sep.detect(text.as_bytes());
+    let text = if is_tail {
+        text.split_once(sep.byte() as char)
+            .map(|(_, rest)| rest)
+            .unwrap_or("")
+    } else {
+        &text
+    };
+    let (kept, _, file_line_start) = parse_last_lines(text, max_lines, sep);
+    let kept = kept
+        .into_iter()
+        .map(|line| cut_line(line, options.max_line_len()))
+        .collect();
+    let file_line_start = if is_tail { 1 } else { file_line_start };
+    Ok((kept, Some(path), file_size, file_line_start))
+}
+
+/// Where log lines come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogSource {
+    /// Built-in sample lines.
+    Sample,
+    /// A log file (can be followed in live mode).
+    File(PathBuf),
+    /// Standard input, read to the end before the viewer starts (`ratlog -`).
+    Stdin,
+    /// An `http(s)://` or `ssh://` URL, read before the viewer starts and followed in live
+    /// mode (see [`RemoteSource::follow`]).
+    Remote(RemoteSource),
+    /// The newest file matching a `--glob` pattern (see [`newest_match`]).
+    Glob(String),
+}
+
+impl LogSource {
+    pub fn kind(&self) -> SourceKind {
+        match self {
+            LogSource::Sample => SourceKind::Sample,
+            LogSource::File(_) | LogSource::Glob(_) => SourceKind::File,
+            LogSource::Stdin => SourceKind::Stdin,
+            LogSource::Remote(_) => SourceKind::Remote,
+        }
+    }
+}
+
+/// Which kind of [`LogSource`] the viewer shows, for the status bar badge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SourceKind {
+    #[default]
+    Sample,
+    File,
+    Stdin,
+    Remote,
+}
+
+impl SourceKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            SourceKind::Sample => "sample",
+            SourceKind::File => "file",
+            SourceKind::Stdin => "stdin",
+            SourceKind::Remote => "remote",
+        }
+    }
+}
+
+/// How much of a source to load.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// `--all`: read every line instead of keeping the last MAX_LINES.
+    pub all: bool,
+    /// `--raw`: keep lines longer than MAX_LINE_LEN whole instead of cutting them.
+    pub raw: bool,
+    /// `-n N` (with `--cat`): keep the last N lines instead of MAX_LINES.
+    pub lines: Option<usize>,
+    /// `--line-sep`: what ends a line.
+    pub line_sep: LineSep,
+}
+
+impl LoadOptions {
+    fn max_lines(self) -> usize {
+        if self.all {
+            usize::MAX
+        } else {
+            self.lines.unwrap_or(MAX_LINES)
+        }
+    }
+
+    fn max_line_len(self) -> usize {
+        if self.raw { usize::MAX } else { MAX_LINE_LEN }
+    }
+}
+
+/// Load lines from any source; see [`load_logs`] for the returned tuple and [`LoadOptions`]
+/// for what `options` change.
+pub fn load_source(
+    source: LogSource,
+    options: LoadOptions,
+) -> io::Result<(Vec<String>, Option<PathBuf>, u64, usize)> {
+    match source {
+        LogSource::Sample => load_logs(None),
+        LogSource::File(path) => load_logs_with(Some(path), options),
+        LogSource::Glob(pattern) => match newest_match(&pattern)? {
+            Some(path) => load_logs_with(Some(path), options),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                fill(t().no_file_matches, &[&pattern]),
+            )),
+        },
+        LogSource::Stdin => {
+            let (kept, file_line_start, _) = read_last_lines(
+                io::stdin().lock(),
+                options.max_lines(),
+                options.max_line_len(),
+                options.line_sep,
+            )?;
+            Ok((kept, None, 0, file_line_start))
+        }
+        LogSource::Remote(remote) => {
+            let log = load_remote(&remote, options)?;
+            Ok((log.lines, None, log.size, log.file_line_start))
+        }
+    }
+}
+
+/// A remote log as [`load_remote`] loaded it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteLog {
+    pub lines: Vec<String>,
+    pub file_line_start: usize,
+    /// Line numbers count from the first line received, not the start of the log.
+    pub relative: bool,
+    /// Bytes in the log when it was read, where live mode picks up.
+    pub size: u64,
+}
+
+/// Fetch a remote log and keep its last lines, like [`load_logs_with`] for a file.
+pub fn load_remote(remote: &RemoteSource, options: LoadOptions) -> io::Result<RemoteLog> {
+    let max_lines = options.max_lines();
+    let fetched = remote.fetch(options.all || max_lines > MAX_LINES, max_lines)?;
+    remote_log(fetched, max_lines, options)
+}
+
+/// Lines of `fetched`, placed in the log by the line count the server gave. Bytes starting
+/// mid-log are numbered from 1 and marked relative.
+pub fn remote_log(
+    fetched: Fetched,
+    max_lines: usize,
+    options: LoadOptions,
+) -> io::Result<RemoteLog> {
+    if fetched.partial {
+        // Only the end of the log came back: skip its first, cut line like a tail read.
+        let mut lines = parse_tail_lines(&fetched.bytes, options.max_line_len(), options.line_sep);
+        lines.drain(..lines.len().saturating_sub(max_lines));
+        return Ok(RemoteLog {
+            lines,
+            file_line_start: 1,
+            relative: true,
+            size: fetched.size,
+        });
+    }
+    let (lines, file_line_start, _) = read_last_lines(
+        &fetched.bytes[..],
+        max_lines,
+        options.max_line_len(),
+        options.line_sep,
+    )?;
+    // `tail -n` sent only the last lines; the total places them in the log.
+    let file_line_start = fetched.total_lines.map_or(file_line_start, |total| {
+        total.saturating_sub(lines.len()) + 1
+    });
+    Ok(RemoteLog {
+        lines,
+        file_line_start,
+        relative: false,
+        size: fetched.size,
+    })
+}
+
+/// The most recently modified file whose name matches the last component of `pattern`, where
+/// `*` and `?` are wildcards; the directory part is taken as is (after `~` / `$VAR`
+/// expansion). Equal times go to the greater name, so `app.log.2024-06-02` beats `…-01`.
+pub fn newest_match(pattern: &str) -> io::Result<Option<PathBuf>> {
+    let expanded = expand_path(pattern)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, format!("{}: {}", pattern, e)))?;
+    let path = Path::new(&expanded);
+    let name_pattern = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty());
+    let mut newest: Option<(SystemTime, PathBuf)> = None;
+    for entry in fs::read_dir(dir.unwrap_or(Path::new(".")))?.flatten() {
+        let name = entry.file_name();
+        if !glob_match(&name_pattern, &name.to_string_lossy()) {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        let candidate = dir.map_or_else(|| PathBuf::from(&name), |d| d.join(&name));
+        if newest
+            .as_ref()
+            .is_none_or(|(time, best)| (modified, &candidate) > (*time, best))
+        {
+            newest = Some((modified, candidate));
+        }
     }
+    Ok(newest.map(|(_, path)| path))
 }
 
 /// Load last MAX_LINES from file. For large files, only reads the last TAIL_READ_SIZE bytes.
+/// `~` and `$VAR` in the path are expanded first. Files starting with a UTF-16 LE/BE byte order
+/// mark are decoded as UTF-16; anything else is read as UTF-8, with a UTF-8 BOM stripped.
+/// The returned offset is the end of what was read, so live mode only appends later bytes.
 pub fn load_logs(
     file_arg: Option<PathBuf>,
 ) -> io::Result<(Vec<String>, Option<PathBuf>, u64, usize)> {
-    if let Some(path) = file_arg {
+    load_logs_with(file_arg, LoadOptions::default())
+}
+
+/// [`load_logs`] with [`LoadOptions`]; with `all` every line of the file is read in one
+/// streaming pass.
+pub fn load_logs_with(
+    file_arg: Option<PathBuf>,
+    options: LoadOptions,
+) -> io::Result<(Vec<String>, Option<PathBuf>, u64, usize)> {
+    if let Some(arg) = file_arg {
+        let original = arg.to_string_lossy().to_string();
+        let expanded = expand_path(&original).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("{}: {}", original, e))
+        })?;
+        let path = PathBuf::from(&expanded);
         if !path.exists() {
-            return Err(io::Error::new(
-                io::ErrorKind::NotFound,
-                format!("Log file not found: {}", path.display()),
-            ));
+            let shown = path.display().to_string();
+            let msg = if expanded == original {
+                fill(t().log_file_not_found, &[&shown])
+            } else {
+                fill(t().log_file_not_found_expanded, &[&shown, &original])
+            };
+            return Err(io::Error::new(io::ErrorKind::NotFound, msg));
         }
         let meta = fs::metadata(&path)?;
         let file_size = meta.len();
 
-        if file_size > TAIL_READ_SIZE {
+        if let Some(big_endian) = utf16_bom(&path)? {
+            return load_utf16_logs(path, file_size, big_endian, options);
+        }
+
+        // The tail read holds at least MAX_LINES lines of normal length; more are streamed.
+        if file_size > TAIL_READ_SIZE && options.max_lines() <= MAX_LINES {
             let mut file = File::open(&path)?;
             let start = file_size.saturating_sub(TAIL_READ_SIZE);
             file.seek(SeekFrom::Start(start))?;
@@ -190,31 +1164,31 @@ pub fn load_logs(
             let mut limited = (&mut file).take(TAIL_READ_SIZE);
             let _ = limited.read_to_end(&mut buf);
             buf.truncate(buf.len().min(cap));
-            let kept = parse_tail_lines(&buf);
+            // The tail starts mid-file, so `Auto` looks at the file's first bytes instead.
+            let sep = options.line_sep.detect_file(&path);
+            let mut kept = parse_tail_lines(&buf, options.max_line_len(), sep);
+            let drop = kept.len().saturating_sub(options.max_lines());
+            kept.drain(..drop);
             let file_offset = file_size;
-            let file_line_start = 1;
+            // Count the lines before the tail to number the kept ones like the file does,
+            // unless that means reading too much (see `tail_numbers_relative`).
+            let first_line = if tail_numbers_relative(&path, options) {
+                1
+            } else {
+                let tail_at = start + tail_start(&buf, sep) as u64;
+                count_lines_before(&path, tail_at, sep)? + 1
+            };
+            let file_line_start = first_line + drop;
             return Ok((kept, Some(path), file_offset, file_line_start));
         }
 
         let file = File::open(&path)?;
-        let mut reader = BufReader::new(file);
-        let mut deque: VecDeque<String> = VecDeque::with_capacity(MAX_LINES + 1);
-        let mut total_lines: usize = 0;
-        while let Some(line) = read_line_bounded(&mut reader)? {
-            total_lines += 1;
-            deque.push_back(line);
-            if deque.len() > MAX_LINES {
-                deque.pop_front();
-            }
-        }
-        let kept: Vec<String> = deque.into_iter().collect();
-        let file_line_start = total_lines.saturating_sub(kept.len()) + 1;
-
-        let file_offset = if file_line_start <= 1 {
-            0
-        } else {
-            offset_after_n_newlines(&path, file_line_start - 1)?
-        };
+        let (kept, file_line_start, file_offset) = read_last_lines(
+            BufReader::new(file),
+            options.max_lines(),
+            options.max_line_len(),
+            options.line_sep,
+        )?;
 
         Ok((kept, Some(path), file_offset, file_line_start))
     } else {
@@ -1,163 +1,262 @@
 //! Log viewer with live text filtering — TUI application.
 //!
 //! Usage: `cargo run` (sample logs) or `cargo run -- <log-file>`
-//! Live mode: press L or F to toggle (only when loaded from a file).
-
-mod app;
-mod cli;
-mod constants;
-mod login;
-mod logs;
-mod settings;
-mod theme;
-mod util;
+//! Live mode: press L or F to toggle (only when loaded from a file or remote log).
 
 use std::env;
+use std::io::{self, IsTerminal, Write};
+
+use ratlog::constants::{ALL_WARN_BYTES, LOADING_NOTICE_BYTES};
+use ratlog::{app, cli, i18n, login, logs, prefix, redact, settings, theme, util};
+
+/// Write `lines` to stdout, one per line, for `--cat`, `--output-json` and `config export`.
+fn print_lines(lines: impl Iterator<Item = String>) -> io::Result<()> {
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    for line in lines {
+        writeln!(out, "{}", line)?;
+    }
+    out.flush()
+}
 
 #[tokio::main]
 async fn main() -> color_eyre::Result<()> {
     let args: Vec<String> = env::args().collect();
     let action = cli::parse_args(&args);
+    if let Some(name) = cli::profile_arg(&args) {
+        if !util::is_valid_profile_name(&name) {
+            eprintln!(
+                "ratlog: {}",
+                i18n::fill(i18n::t().invalid_profile, &[&name])
+            );
+            std::process::exit(1);
+        }
+        util::set_profile(&name);
+    }
+    i18n::set_language(settings::load_settings(None).language);
+    if let Some(warning) = util::config_dir_warning() {
+        eprintln!("ratlog: {}", warning);
+    }
 
     match action {
-        cli::CliAction::Login => {
+        cli::CliAction::Login { check } => {
+            color_eyre::install()?;
+            if check {
+                // Meant for scripts: a one-line reason and exit status 1, no report.
+                if let Err(e) = login::check().await {
+                    eprintln!("ratlog: {}", e);
+                    std::process::exit(1);
+                }
+                Ok(())
+            } else {
+                login::run().await
+            }
+        }
+        cli::CliAction::Shares { json } => {
             color_eyre::install()?;
-            login::run().await
+            login::shares(json).await
         }
-        cli::CliAction::Run(file_arg) => {
+        cli::CliAction::Unshare(target) => {
             color_eyre::install()?;
-            let terminal = ratatui::init();
-            let (logs, file_path, file_offset, file_line_start) = logs::load_logs(file_arg)?;
-            let result = app::App::new(logs, file_path, file_offset, file_line_start)
-                .run(terminal)
-                .await;
-            ratatui::restore();
+            login::unshare(target).await
+        }
+        cli::CliAction::Config(Some(cli::ConfigAction::Export)) => {
+            match print_lines(std::iter::once(settings::export_settings())) {
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+                result => Ok(result?),
+            }
+        }
+        cli::CliAction::Config(Some(cli::ConfigAction::Import(file))) => {
+            let json = if file == "-" {
+                io::read_to_string(io::stdin())
+            } else {
+                std::fs::read_to_string(&file)
+            };
+            match json.and_then(|json| settings::import_settings(&json)) {
+                Ok(path) => {
+                    let path = path.display().to_string();
+                    eprintln!(
+                        "ratlog: {}",
+                        i18n::fill(i18n::t().config_imported, &[&file, &path])
+                    );
+                    Ok(())
+                }
+                Err(e) => {
+                    eprintln!("ratlog: {}: {}", file, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        cli::CliAction::Config(None) => {
+            eprintln!("ratlog: {}", i18n::t().config_usage);
+            std::process::exit(1);
+        }
+        cli::CliAction::Run(source, options) => {
+            color_eyre::install()?;
+            if let Some(name) = &options.theme
+                && theme::ThemePreset::find(name).is_none()
+            {
+                eprintln!(
+                    "ratlog: {}",
+                    i18n::fill(
+                        i18n::t().unknown_theme,
+                        &[name, &theme::ThemePreset::names()]
+                    )
+                );
+                std::process::exit(1);
+            }
+            if let Some(name) = &options.line_sep
+                && logs::LineSep::from_name(name).is_none()
+            {
+                eprintln!(
+                    "ratlog: {}",
+                    i18n::fill(i18n::t().unknown_line_sep, &[name])
+                );
+                std::process::exit(1);
+            }
+            if let (true, logs::LogSource::File(path)) = (options.all, &source) {
+                let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+                if size > ALL_WARN_BYTES {
+                    eprintln!(
+                        "ratlog: {}",
+                        i18n::fill(i18n::t().all_large_file, &[&util::format_bytes(size)])
+                    );
+                }
+            }
+            // Reading a big file (or a remote log) takes a moment before the first frame; say
+            // so on stderr and clear the line once loading is done.
+            let notice = match &source {
+                logs::LogSource::File(path) => std::fs::metadata(path)
+                    .ok()
+                    .filter(|m| m.len() > LOADING_NOTICE_BYTES)
+                    .map(|m| format!("{} ({})", path.display(), util::format_bytes(m.len()))),
+                logs::LogSource::Remote(remote) => Some(remote.to_string()),
+                _ => None,
+            }
+            .filter(|_| io::stderr().is_terminal());
+            if let Some(what) = &notice {
+                eprint!("ratlog: {}", i18n::fill(i18n::t().loading, &[what]));
+            }
+            let headless = options.cat || options.output_json;
+            let load = logs::LoadOptions {
+                all: options.all,
+                raw: options.raw,
+                lines: options.lines.filter(|_| headless),
+                line_sep: options.line_sep(),
+            };
+            // Network and ssh failures are the user's to fix, like a missing file.
+            let kind = source.kind();
+            // A remote log is kept for live mode, with whether its lines are numbered from the
+            // first one received.
+            let mut relative = false;
+            let loaded = match &source {
+                logs::LogSource::Remote(remote) => logs::load_remote(remote, load).map(|log| {
+                    relative = log.relative;
+                    (log.lines, None, log.size, log.file_line_start)
+                }),
+                _ => logs::load_source(source.clone(), load),
+            };
+            let remote = match source {
+                logs::LogSource::Remote(remote) => Some(remote),
+                _ => None,
+            };
+            if notice.is_some() {
+                let _ = crossterm::execute!(
+                    io::stderr(),
+                    crossterm::cursor::MoveToColumn(0),
+                    crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine)
+                );
+            }
+            let (logs, file_path, file_offset, file_line_start) = match loaded {
+                Ok(loaded) => loaded,
+                Err(e)
+                    if remote.is_some()
+                        || matches!(
+                            e.kind(),
+                            io::ErrorKind::NotFound | io::ErrorKind::InvalidInput
+                        ) =>
+                {
+                    eprintln!("ratlog: {}", e);
+                    std::process::exit(1);
+                }
+                Err(e) => return Err(e.into()),
+            };
+            if headless {
+                let mut logs = logs;
+                if let Some(e) = settings::settings_error() {
+                    eprintln!("ratlog: {}", i18n::fill(i18n::t().settings_invalid, &[&e]));
+                }
+                let (redactor, errors) = redact::Redactor::new(&settings::load_redact_rules());
+                for error in errors {
+                    eprintln!(
+                        "ratlog: {}",
+                        i18n::fill(i18n::t().redact_rule_invalid, &[&error])
+                    );
+                }
+                redactor.apply_all(&mut logs);
+                let filter = options.filter.as_deref().unwrap_or("");
+                let prefix = prefix::LinePrefix::default();
+                let matches = logs::apply_filter(&logs, filter, logs.len());
+                let found = !matches.is_empty();
+                let lines = matches.into_iter().map(|(idx, text)| {
+                    let file_line = file_line_start + idx;
+                    if options.output_json {
+                        logs::json_record(file_line, &text)
+                    } else if options.line_numbers {
+                        format!("{}{}", prefix.render(file_line, &text, false), text)
+                    } else {
+                        text
+                    }
+                });
+                match print_lines(lines) {
+                    // The reader went away (`| head`): stop quietly like other filters.
+                    Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+                    result => result?,
+                }
+                // Like grep: exit status 1 when nothing matched, so scripts can branch on it.
+                if !found {
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+            let inline = options.inline;
+            let terminal = if inline {
+                // Draw below the shell prompt with the terminal's height, so the last frame
+                // stays in the scrollback after quitting.
+                let height = crossterm::terminal::size().map_or(24, |(_, rows)| rows);
+                ratatui::init_with_options(ratatui::TerminalOptions {
+                    viewport: ratatui::Viewport::Inline(height),
+                })
+            } else {
+                ratatui::init()
+            };
+            // Terminals without focus reporting ignore this; the app then stays "focused".
+            let _ = crossterm::execute!(io::stdout(), crossterm::event::EnableFocusChange);
+            // Mouse positions are absolute, the inline viewport is not at the top: no mouse.
+            let mouse = options.mouse && !inline;
+            if mouse {
+                let _ = crossterm::execute!(io::stdout(), crossterm::event::EnableMouseCapture);
+            }
+            let mut app =
+                app::App::new_with_options(logs, file_path, file_offset, file_line_start, options)
+                    .with_source(kind);
+            if let Some(remote) = remote {
+                app = app.with_remote(remote, relative);
+            }
+            let result = app.run(terminal).await;
+            if mouse {
+                let _ = crossterm::execute!(io::stdout(), crossterm::event::DisableMouseCapture);
+            }
+            let _ = crossterm::execute!(io::stdout(), crossterm::event::DisableFocusChange);
+            if inline {
+                // `ratatui::restore` also leaves the alternate screen, which would move the
+                // cursor; only undo raw mode and start the prompt below the last frame.
+                let _ = crossterm::terminal::disable_raw_mode();
+                let _ = crossterm::execute!(io::stdout(), crossterm::cursor::Show);
+                println!();
+            } else {
+                ratatui::restore();
+            }
             result
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::constants::MAX_LINES;
-    use crate::logs::{apply_filter, parse_log_content, sample_logs};
-    use crate::settings::SavedSettings;
-    use crate::util::{centered_rect, format_bytes};
-    use ratatui::layout::Rect;
-
-    #[test]
-    fn test_format_bytes() {
-        assert_eq!(format_bytes(0), "0 B");
-        assert_eq!(format_bytes(512), "512 B");
-        assert_eq!(format_bytes(1024), "1 KiB");
-        assert_eq!(format_bytes(1536), "1 KiB");
-        assert_eq!(format_bytes(1024 * 1024), "1.0 MiB");
-        assert_eq!(format_bytes(1024 * 1024 * 1024), "1.0 GiB");
-        assert_eq!(format_bytes(1536 * 1024 * 1024), "1.5 GiB");
-    }
-
-    #[test]
-    fn test_parse_log_content_empty() {
-        let (lines, offset, start) = parse_log_content("");
-        assert!(lines.is_empty());
-        assert_eq!(offset, 0);
-        assert_eq!(start, 1);
-    }
-
-    #[test]
-    fn test_parse_log_content_one_line() {
-        let (lines, offset, start) = parse_log_content("hello\n");
-        assert_eq!(lines.len(), 1);
-        assert_eq!(lines[0], "hello");
-        assert_eq!(offset, 0);
-        assert_eq!(start, 1);
-    }
-
-    #[test]
-    fn test_parse_log_content_last_max_lines() {
-        let n = MAX_LINES + 50;
-        let content = (0..n)
-            .map(|i| format!("line {}", i))
-            .collect::<Vec<_>>()
-            .join("\n");
-        let (lines, _offset, start) = parse_log_content(&content);
-        assert_eq!(lines.len(), MAX_LINES);
-        assert_eq!(start, 51);
-        assert_eq!(lines[0], "line 50");
-        assert_eq!(lines[MAX_LINES - 1], format!("line {}", n - 1));
-    }
-
-    #[test]
-    fn test_apply_filter_empty_query_returns_all() {
-        let lines = vec!["a".into(), "b".into(), "c".into()];
-        let out = apply_filter(&lines, "", 10);
-        assert_eq!(out.len(), 3);
-        assert_eq!(out[0], (0, "a".to_string()));
-        assert_eq!(out[1], (1, "b".to_string()));
-        assert_eq!(out[2], (2, "c".to_string()));
-    }
-
-    #[test]
-    fn test_apply_filter_matching_case_insensitive() {
-        let lines = vec!["INFO foo".into(), "ERROR bar".into(), "info baz".into()];
-        let out = apply_filter(&lines, "info", 10);
-        assert_eq!(out.len(), 2);
-        assert_eq!(out[0], (0, "INFO foo".to_string()));
-        assert_eq!(out[1], (2, "info baz".to_string()));
-    }
-
-    #[test]
-    fn test_apply_filter_cap_max_lines() {
-        let lines: Vec<String> = (0..20).map(|i| format!("x {}", i)).collect();
-        let out = apply_filter(&lines, "x", 5);
-        assert_eq!(out.len(), 5);
-        assert_eq!(out[0].1, "x 15");
-        assert_eq!(out[4].1, "x 19");
-    }
-
-    #[test]
-    fn test_sample_logs_non_empty() {
-        let logs = sample_logs();
-        assert!(!logs.is_empty());
-        assert!(logs.len() <= MAX_LINES);
-        assert!(
-            logs[0].contains("INFO")
-                || logs[0].contains("DEBUG")
-                || logs[0].contains("WARN")
-                || logs[0].contains("ERROR")
-        );
-    }
-
-    #[test]
-    fn test_centered_rect() {
-        let area = Rect {
-            x: 0,
-            y: 0,
-            width: 100,
-            height: 20,
-        };
-        let r = centered_rect(area, 50, 50);
-        assert_eq!(r.width, 50);
-        assert_eq!(r.height, 10);
-        assert_eq!(r.x, 25);
-        assert_eq!(r.y, 5);
-    }
-
-    #[test]
-    fn test_saved_settings_roundtrip() {
-        let saved = SavedSettings {
-            accent: "Cyan".to_string(),
-            text_color: "White".to_string(),
-            text_style: "Normal".to_string(),
-            border_color: "Gray".to_string(),
-            status_color: "Gray".to_string(),
-        };
-        let s = serde_json::to_string_pretty(&saved).unwrap();
-        let loaded: SavedSettings = serde_json::from_str(&s).unwrap();
-        assert_eq!(loaded.accent, saved.accent);
-        assert_eq!(loaded.text_color, saved.text_color);
-        assert_eq!(loaded.text_style, saved.text_style);
-        assert_eq!(loaded.border_color, saved.border_color);
-        assert_eq!(loaded.status_color, saved.status_color);
-    }
-}
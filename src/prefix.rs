@@ -0,0 +1,88 @@
+//! Line prefix: the gutter drawn before each log line, built from a small template.
+
+use crate::logs::{leading_timestamp, line_level};
+
+/// Template matching the built-in gutter, e.g. `   324 │ `.
+pub const DEFAULT_LINE_PREFIX: &str = "{lineno} {sep} ";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    /// File line number, right-aligned in 6 columns.
+    LineNo,
+    /// `│`, or `┆` on continuation rows of a multi-line entry.
+    Sep,
+    /// First upper-case level word of the line (`ERROR`, `WARN`, …), padded to 5 columns.
+    Level,
+    /// Leading timestamp of the line, or nothing.
+    Time,
+}
+
+/// Parsed line prefix template. Placeholders are `{lineno}`, `{sep}`, `{level}` and `{time}`;
+/// anything else, including unknown `{…}`, is copied as is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinePrefix {
+    parts: Vec<Part>,
+}
+
+impl LinePrefix {
+    pub fn parse(template: &str) -> LinePrefix {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            text.push_str(&rest[..open]);
+            rest = &rest[open..];
+            let part = rest.find('}').and_then(|close| {
+                let part = match &rest[1..close] {
+                    "lineno" => Part::LineNo,
+                    "sep" => Part::Sep,
+                    "level" => Part::Level,
+                    "time" => Part::Time,
+                    _ => return None,
+                };
+                Some((part, close + 1))
+            });
+            match part {
+                Some((part, len)) => {
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(part);
+                    rest = &rest[len..];
+                }
+                None => {
+                    text.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        text.push_str(rest);
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        LinePrefix { parts }
+    }
+
+    /// Prefix for `line`, shown as file line `file_line`; `continuation` marks rows inside a
+    /// multi-line entry.
+    pub fn render(&self, file_line: usize, line: &str, continuation: bool) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => out.push_str(text),
+                Part::LineNo => out.push_str(&format!("{:>6}", file_line)),
+                Part::Sep => out.push(if continuation { '┆' } else { '│' }),
+                Part::Level => out.push_str(&format!("{:<5}", line_level(line).unwrap_or(""))),
+                Part::Time => out.push_str(leading_timestamp(line).unwrap_or("")),
+            }
+        }
+        out
+    }
+}
+
+impl Default for LinePrefix {
+    fn default() -> Self {
+        LinePrefix::parse(DEFAULT_LINE_PREFIX)
+    }
+}
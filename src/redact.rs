@@ -0,0 +1,68 @@
+//! Redaction: regex rules from `settings.json` that mask secrets before lines are shown or shared.
+
+use std::borrow::Cow;
+
+use regex::Regex;
+
+fn default_replacement() -> String {
+    "***".to_string()
+}
+
+/// One rule of the `redact` list in `settings.json`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RedactRule {
+    /// Regular expression to mask.
+    pub pattern: String,
+    /// Text put in place of each match; `$1`, `${name}` refer to capture groups.
+    #[serde(default = "default_replacement")]
+    pub replacement: String,
+}
+
+/// Compiled redaction rules, applied in order.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    rules: Vec<(Regex, String)>,
+}
+
+impl Redactor {
+    /// Compile `rules`. Rules that don't compile are left out and described in the returned
+    /// list (`pattern: error`), so the caller can warn that they are not applied.
+    pub fn new(rules: &[RedactRule]) -> (Redactor, Vec<String>) {
+        let mut compiled = Vec::new();
+        let mut errors = Vec::new();
+        for rule in rules {
+            match Regex::new(&rule.pattern) {
+                Ok(re) => compiled.push((re, rule.replacement.clone())),
+                Err(e) => errors.push(format!("{}: {}", rule.pattern, e)),
+            }
+        }
+        (Redactor { rules: compiled }, errors)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// `line` with every rule applied; borrowed when nothing matched.
+    pub fn apply<'a>(&self, line: &'a str) -> Cow<'a, str> {
+        let mut line = Cow::Borrowed(line);
+        for (re, replacement) in &self.rules {
+            if let Cow::Owned(replaced) = re.replace_all(&line, replacement.as_str()) {
+                line = Cow::Owned(replaced);
+            }
+        }
+        line
+    }
+
+    /// Apply the rules to `lines` in place.
+    pub fn apply_all(&self, lines: &mut [String]) {
+        if self.is_empty() {
+            return;
+        }
+        for line in lines {
+            if let Cow::Owned(replaced) = self.apply(line) {
+                *line = replaced;
+            }
+        }
+    }
+}
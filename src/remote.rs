@@ -0,0 +1,443 @@
+//! Remote sources: logs fetched over HTTP(S) or read over SSH before the viewer starts.
+
+use std::io::{self, Read};
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+
+use crate::constants::{REMOTE_POLL_INTERVAL, TAIL_READ_SIZE};
+use crate::i18n::{fill, t};
+use crate::login::http_client;
+
+/// A log on another machine, named on the command line by URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteSource {
+    /// `http://…` or `https://…`, fetched with a GET request.
+    Http(String),
+    /// `ssh://[user@]host[:port]/path`, read by running `tail` (or `cat`) through `ssh`.
+    Ssh {
+        /// `host` or `user@host`, as passed to `ssh`.
+        host: String,
+        port: Option<u16>,
+        path: String,
+    },
+}
+
+impl RemoteSource {
+    /// Remote source named by `arg`, or `None` when it is not an `http(s)://` or `ssh://` URL.
+    pub fn parse(arg: &str) -> Option<RemoteSource> {
+        let scheme = arg.split_once("://")?.0.to_ascii_lowercase();
+        match scheme.as_str() {
+            "http" | "https" => Some(RemoteSource::Http(arg.to_string())),
+            "ssh" => {
+                let rest = &arg["ssh://".len()..];
+                let (authority, path) = rest.split_once('/')?;
+                let (host, port) = match authority.rsplit_once(':') {
+                    Some((host, port)) => (host, Some(port.parse().ok()?)),
+                    None => (authority, None),
+                };
+                if host.is_empty() || path.is_empty() {
+                    return None;
+                }
+                // `ssh://host/var/log/app.log` names the absolute path; `~/app.log` stays relative.
+                let path = match path.strip_prefix("~/") {
+                    Some(home) => home.to_string(),
+                    None => format!("/{}", path),
+                };
+                Some(RemoteSource::Ssh {
+                    host: host.to_string(),
+                    port,
+                    path,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// The `ssh` command printing the line and byte count of the log (`wc -lc`) on one line,
+    /// then its last `max_lines` lines (every line for `usize::MAX`).
+    pub fn ssh_command(host: &str, port: Option<u16>, path: &str, max_lines: usize) -> Command {
+        let mut command = ssh(port);
+        // The remote shell parses the command line again, so the path is quoted for it.
+        let path = shell_quote(path);
+        let read = if max_lines == usize::MAX {
+            format!("cat -- {}", path)
+        } else {
+            format!("tail -n {} -- {}", max_lines, path)
+        };
+        command
+            .arg(host)
+            .arg(format!("wc -lc < {} && {}", path, read));
+        command
+    }
+
+    /// The `ssh` command printing what is appended to the log after its first `offset` bytes,
+    /// until it is killed. It never prompts, since the terminal belongs to the viewer by then.
+    pub fn ssh_follow_command(host: &str, port: Option<u16>, path: &str, offset: u64) -> Command {
+        let mut command = ssh(port);
+        command
+            .arg("-o")
+            .arg("BatchMode=yes")
+            .arg(host)
+            .arg(format!(
+                "tail -c +{} -F -- {}",
+                offset + 1,
+                shell_quote(path)
+            ));
+        command
+    }
+
+    /// The log, or its end: at most the last TAIL_READ_SIZE bytes are requested over HTTP
+    /// unless `all`; over SSH, `tail` cuts to `max_lines`.
+    pub fn fetch(&self, all: bool, max_lines: usize) -> io::Result<Fetched> {
+        match self {
+            RemoteSource::Http(url) => block_on(fetch_http(url, all)),
+            RemoteSource::Ssh { host, port, path } => {
+                // stdin and stderr stay on the terminal for password prompts and ssh errors.
+                let output = Self::ssh_command(host, *port, path, max_lines)
+                    .stdin(Stdio::inherit())
+                    .stderr(Stdio::inherit())
+                    .output()?;
+                if !output.status.success() {
+                    return Err(io::Error::other(format!("ssh {}: {}", host, output.status)));
+                }
+                parse_ssh_output(output.stdout)
+                    .ok_or_else(|| io::Error::other(fill(t().ssh_unexpected_output, &[host])))
+            }
+        }
+    }
+
+    /// Start following the log from byte `offset` (see [`Fetched::size`]): over HTTP by
+    /// requesting the bytes after it every REMOTE_POLL_INTERVAL, over SSH with `tail -F`.
+    pub fn follow(&self, offset: u64) -> io::Result<RemoteFollow> {
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let child = match self {
+            RemoteSource::Http(url) => {
+                let (url, stop) = (url.clone(), stop.clone());
+                std::thread::spawn(move || poll_http(url, offset, tx, stop));
+                None
+            }
+            RemoteSource::Ssh { host, port, path } => {
+                let mut child = Self::ssh_follow_command(host, *port, path, offset)
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .spawn()?;
+                let (stdout, stderr) = (child.stdout.take(), child.stderr.take());
+                let host = host.clone();
+                std::thread::spawn(move || read_ssh(host, stdout, stderr, tx));
+                Some(child)
+            }
+        };
+        Ok(RemoteFollow {
+            rx,
+            stop,
+            child,
+            error: None,
+            finished: false,
+        })
+    }
+}
+
+/// A remote log as [`RemoteSource::fetch`] read it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fetched {
+    pub bytes: Vec<u8>,
+    /// The bytes start mid-log (an HTTP range starting past 0), so their first line is cut
+    /// and the lines before them are unknown.
+    pub partial: bool,
+    /// Lines in the whole log, when the server says (`wc -l` over SSH, counting a last line
+    /// without `\n`).
+    pub total_lines: Option<usize>,
+    /// Bytes in the log when it was read: where following it picks up.
+    pub size: u64,
+}
+
+/// [`Fetched`] from the output of [`RemoteSource::ssh_command`]: the `wc -lc` line, then the
+/// log. `None` when the first line is not two counts.
+pub fn parse_ssh_output(mut stdout: Vec<u8>) -> Option<Fetched> {
+    let newline = stdout.iter().position(|&b| b == b'\n')?;
+    let counts = std::str::from_utf8(&stdout[..newline]).ok()?;
+    let mut counts = counts.split_whitespace().map(|n| n.parse::<u64>().ok());
+    let (lines, size) = (counts.next()??, counts.next()??);
+    let bytes = stdout.split_off(newline + 1);
+    let unterminated = !bytes.is_empty() && !bytes.ends_with(b"\n");
+    Some(Fetched {
+        total_lines: Some(lines as usize + usize::from(unterminated)),
+        partial: false,
+        size,
+        bytes,
+    })
+}
+
+/// Bytes appended to a remote log, arriving from a background thread (see
+/// [`RemoteSource::follow`]). Dropping it stops following.
+pub struct RemoteFollow {
+    rx: Receiver<io::Result<Vec<u8>>>,
+    stop: Arc<AtomicBool>,
+    /// `ssh … tail -F`, killed on drop.
+    child: Option<Child>,
+    /// Error received after some bytes, returned by the next poll.
+    error: Option<io::Error>,
+    finished: bool,
+}
+
+impl RemoteFollow {
+    /// Bytes received since the last call (empty when none), or why fetching them failed.
+    /// Over HTTP, polling goes on after an error; see [`RemoteFollow::is_finished`].
+    pub fn poll(&mut self) -> io::Result<Vec<u8>> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        let mut bytes = Vec::new();
+        loop {
+            match self.rx.try_recv() {
+                Ok(Ok(chunk)) => bytes.extend(chunk),
+                Ok(Err(e)) if bytes.is_empty() => return Err(e),
+                Ok(Err(e)) => {
+                    self.error = Some(e);
+                    return Ok(bytes);
+                }
+                Err(TryRecvError::Empty) => return Ok(bytes),
+                Err(TryRecvError::Disconnected) => {
+                    self.finished = true;
+                    return Ok(bytes);
+                }
+            }
+        }
+    }
+
+    /// Nothing more will arrive (the `ssh` command ended).
+    pub fn is_finished(&self) -> bool {
+        self.finished && self.error.is_none()
+    }
+}
+
+impl Drop for RemoteFollow {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(child) = &mut self.child {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+fn ssh(port: Option<u16>) -> Command {
+    let mut command = Command::new("ssh");
+    if let Some(port) = port {
+        command.arg("-p").arg(port.to_string());
+    }
+    command
+}
+
+/// Forward the output of `ssh … tail -F` until it ends, then send why, with the last line ssh
+/// printed on stderr.
+fn read_ssh(
+    host: String,
+    stdout: Option<impl Read>,
+    stderr: Option<impl Read>,
+    tx: Sender<io::Result<Vec<u8>>>,
+) {
+    if let Some(mut stdout) = stdout {
+        let mut buf = vec![0; 64 * 1024];
+        loop {
+            match stdout.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if tx.send(Ok(buf[..n].to_vec())).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+    let mut message = String::new();
+    if let Some(mut stderr) = stderr {
+        let _ = stderr.read_to_string(&mut message);
+    }
+    let reason = message
+        .lines()
+        .last()
+        .unwrap_or("connection closed")
+        .to_string();
+    let _ = tx.send(Err(io::Error::other(format!("ssh {}: {}", host, reason))));
+}
+
+/// Every REMOTE_POLL_INTERVAL, send what was appended to `url` after `offset` until `stop`.
+fn poll_http(url: String, mut offset: u64, tx: Sender<io::Result<Vec<u8>>>, stop: Arc<AtomicBool>) {
+    let Ok(runtime) = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    else {
+        return;
+    };
+    let client = http_client();
+    loop {
+        std::thread::sleep(REMOTE_POLL_INTERVAL);
+        if stop.load(Ordering::Relaxed) {
+            return;
+        }
+        let sent = match runtime.block_on(fetch_http_from(&client, &url, offset)) {
+            Ok((bytes, next)) => {
+                offset = next;
+                bytes.is_empty() || tx.send(Ok(bytes)).is_ok()
+            }
+            Err(e) => tx.send(Err(e)).is_ok(),
+        };
+        if !sent {
+            return;
+        }
+    }
+}
+
+/// Bytes of `url` after `offset` and the offset after them. A log that shrank (rotated or
+/// truncated) is read again from its start on the next request.
+async fn fetch_http_from(
+    client: &reqwest::Client,
+    url: &str,
+    offset: u64,
+) -> io::Result<(Vec<u8>, u64)> {
+    let response = client
+        .get(url)
+        .header("Range", format!("bytes={}-", offset))
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    let status = response.status();
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value: &reqwest::header::HeaderValue| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let content_range = header(reqwest::header::CONTENT_RANGE);
+    match status {
+        // Nothing past `offset`; `bytes */N` gives the current length.
+        reqwest::StatusCode::RANGE_NOT_SATISFIABLE => {
+            let total = content_range
+                .as_deref()
+                .and_then(|v| v.trim().strip_prefix("bytes */"))
+                .and_then(|n| n.trim().parse::<u64>().ok());
+            Ok((
+                Vec::new(),
+                if total.is_some_and(|n| n < offset) {
+                    0
+                } else {
+                    offset
+                },
+            ))
+        }
+        reqwest::StatusCode::PARTIAL_CONTENT => {
+            let start = content_range
+                .as_deref()
+                .and_then(content_range_start)
+                .unwrap_or(offset);
+            let bytes = response.bytes().await.map_err(io::Error::other)?;
+            Ok((bytes.to_vec(), start + bytes.len() as u64))
+        }
+        // The whole log: keep what lies past `offset`, or all of it when it shrank.
+        _ if status.is_success() => {
+            let bytes = response.bytes().await.map_err(io::Error::other)?;
+            let len = bytes.len() as u64;
+            let from = if len >= offset { offset as usize } else { 0 };
+            Ok((bytes[from..].to_vec(), len))
+        }
+        _ => Err(io::Error::other(format!("{}: {}", url, status))),
+    }
+}
+
+fn content_range_start(value: &str) -> Option<u64> {
+    content_range(value).map(|(start, _, _)| start)
+}
+
+/// The URL [`RemoteSource::parse`] reads back to the same source.
+impl std::fmt::Display for RemoteSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RemoteSource::Http(url) => f.write_str(url),
+            RemoteSource::Ssh { host, port, path } => {
+                write!(f, "ssh://{}", host)?;
+                if let Some(port) = port {
+                    write!(f, ":{}", port)?;
+                }
+                match path.strip_prefix('/') {
+                    Some(_) => f.write_str(path),
+                    None => write!(f, "/~/{}", path),
+                }
+            }
+        }
+    }
+}
+
+/// Quote `s` for a POSIX shell.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// `start`, `end` (inclusive) and, unless it is `*`, the total length from a `Content-Range`
+/// header such as `bytes 900-999/1000`.
+pub fn content_range(value: &str) -> Option<(u64, u64, Option<u64>)> {
+    let (range, total) = value.trim().strip_prefix("bytes ")?.split_once('/')?;
+    let (start, end) = range.split_once('-')?;
+    Some((
+        start.trim().parse().ok()?,
+        end.trim().parse().ok()?,
+        total.trim().parse().ok(),
+    ))
+}
+
+/// GET `url`; without `all`, only its last TAIL_READ_SIZE bytes are asked for. The body starts
+/// mid-log only when the range the server sent starts past 0 (a log smaller than the range comes
+/// back whole, even as 206); a server that ignores the range sends the whole body.
+async fn fetch_http(url: &str, all: bool) -> io::Result<Fetched> {
+    let client = http_client();
+    let mut request = client.get(url);
+    if !all {
+        request = request.header("Range", format!("bytes=-{}", TAIL_READ_SIZE));
+    }
+    let response = request.send().await.map_err(io::Error::other)?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            fill(t().log_not_found, &[url]),
+        ));
+    }
+    if !status.is_success() {
+        return Err(io::Error::other(format!("{}: {}", url, status)));
+    }
+    let range = response
+        .headers()
+        .get(reqwest::header::CONTENT_RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(content_range);
+    let is_range = status == reqwest::StatusCode::PARTIAL_CONTENT;
+    // Without a readable Content-Range, a 206 is taken to start mid-log.
+    let partial = is_range && range.is_none_or(|(start, _, _)| start > 0);
+    let bytes = response.bytes().await.map_err(io::Error::other)?;
+    let size = match range {
+        Some((_, end, total)) if is_range => total.unwrap_or(end + 1),
+        _ => bytes.len() as u64,
+    };
+    Ok(Fetched {
+        bytes: bytes.to_vec(),
+        partial,
+        total_lines: None,
+        size,
+    })
+}
+
+/// Run `future` to completion from synchronous loading code, inside or outside a runtime.
+fn block_on<T>(future: impl std::future::Future<Output = io::Result<T>>) -> io::Result<T> {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+        Err(_) => tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(future),
+    }
+}
@@ -1,13 +1,99 @@
-//! Load/save user settings (colours, style) from config file.
+//! Load/save user settings (colours, style, display options) from config file.
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::theme::{AccentColor, BorderColor, StatusColor, TextColor, TextStyle};
+use crate::constants::{
+    DEFAULT_MAX_POLL_MS, DEFAULT_TAB_WIDTH, MAX_FILTER_LEN, POLL_INTERVALS_MS, TAB_WIDTHS,
+};
+use crate::i18n::Language;
+use crate::redact::RedactRule;
+use crate::status::StatusLayout;
+use crate::theme::{
+    AccentColor, BorderColor, GutterColor, StatusColor, TextColor, TextStyle, ThemeColors,
+};
+use crate::util::profile_file;
 
-fn settings_path() -> Option<PathBuf> {
-    dirs::config_dir().map(|d| d.join("ratlog").join("settings.json"))
+/// `settings.json`, or `profiles/NAME.json` with `--profile NAME`.
+fn settings_path() -> PathBuf {
+    profile_file("settings.json", ".json")
+}
+
+fn default_tab_width() -> usize {
+    DEFAULT_TAB_WIDTH
+}
+
+fn default_max_poll_ms() -> u64 {
+    DEFAULT_MAX_POLL_MS
+}
+
+fn default_status_fields() -> Vec<String> {
+    StatusLayout::FULL.names()
+}
+
+fn default_language() -> String {
+    Language::default().code().to_string()
+}
+
+fn default_gutter_color() -> String {
+    GutterColor::default().name().to_string()
+}
+
+fn default_max_filter_len() -> usize {
+    MAX_FILTER_LEN
+}
+
+fn default_per_file() -> String {
+    PerFileSettings::default().name().to_string()
+}
+
+/// How settings are remembered per log file: not at all, by full path, or by file name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PerFileSettings {
+    #[default]
+    Off,
+    Path,
+    Name,
+}
+
+impl PerFileSettings {
+    /// Name used in `settings.json`.
+    pub fn name(self) -> &'static str {
+        match self {
+            PerFileSettings::Off => "off",
+            PerFileSettings::Path => "path",
+            PerFileSettings::Name => "name",
+        }
+    }
+    pub fn all() -> &'static [PerFileSettings] {
+        &[
+            PerFileSettings::Off,
+            PerFileSettings::Path,
+            PerFileSettings::Name,
+        ]
+    }
+    fn from_name(name: &str) -> PerFileSettings {
+        PerFileSettings::all()
+            .iter()
+            .find(|m| m.name().eq_ignore_ascii_case(name))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Key under which settings for `path` are stored in `files`, or `None` when per-file settings
+/// are off. Paths are canonicalized when possible so `./app.log` and `app.log` share an entry.
+pub fn settings_key(path: &Path, mode: PerFileSettings) -> Option<String> {
+    match mode {
+        PerFileSettings::Off => None,
+        PerFileSettings::Path => {
+            let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+            Some(path.to_string_lossy().into_owned())
+        }
+        PerFileSettings::Name => path.file_name().map(|n| n.to_string_lossy().into_owned()),
+    }
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -17,118 +103,454 @@ pub struct SavedSettings {
     pub text_style: String,
     pub border_color: String,
     pub status_color: String,
+    #[serde(default = "default_gutter_color")]
+    pub gutter_color: String,
+    #[serde(default = "default_tab_width")]
+    pub tab_width: usize,
+    #[serde(default = "default_language")]
+    pub language: String,
+    #[serde(default)]
+    pub filter_select_last: bool,
+    #[serde(default)]
+    pub truncate_middle: bool,
+    #[serde(default = "default_max_poll_ms")]
+    pub max_poll_ms: u64,
+    #[serde(default)]
+    pub filter_tint: bool,
+    /// Status bar fields in display order: source, count, dropped, live, rate, uptime, memory,
+    /// filter, keys.
+    #[serde(default = "default_status_fields")]
+    pub status_fields: Vec<String>,
+    #[serde(default = "default_per_file")]
+    pub per_file: String,
+    #[serde(default)]
+    pub group_entries: bool,
+    #[serde(default)]
+    pub hide_hint_line: bool,
+    #[serde(default)]
+    pub highlight_tokens: bool,
+    #[serde(default)]
+    pub quit_requires_modifier: bool,
+    #[serde(default)]
+    pub skip_redact_warning: bool,
+    #[serde(default)]
+    pub hide_blank_lines: bool,
+    #[serde(default)]
+    pub filter_message_only: bool,
+    #[serde(default)]
+    pub split_view: bool,
+    #[serde(default)]
+    pub show_invisibles: bool,
+    #[serde(default)]
+    pub dense: bool,
+    #[serde(default)]
+    pub columns: bool,
+    /// Longest filter accepted, in characters (no panel row).
+    #[serde(default = "default_max_filter_len")]
+    pub max_filter_len: usize,
+    /// Text shown instead of the key hint line; global only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint_text: Option<String>,
+    /// Gutter template (see [`crate::prefix::LinePrefix`]); global only, not in `files` entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_prefix: Option<String>,
+    /// Ratlog Web URL for login and sharing, unless `RATLOG_WEB_URL` is set; global only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web_url: Option<String>,
+    /// Rules masking parts of each line before it is shown or shared; global only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redact: Vec<RedactRule>,
+    /// Filter restored when the file is opened; only kept in `files` entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// Per-file settings keyed by path or file name (see `per_file`); top-level fields are the
+    /// global default.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, SavedSettings>,
 }
 
-pub fn load_settings() -> (AccentColor, TextColor, TextStyle, BorderColor, StatusColor) {
-    let path = match settings_path() {
-        Some(p) => p,
-        None => {
-            return (
-                AccentColor::default(),
-                TextColor::default(),
-                TextStyle::default(),
-                BorderColor::default(),
-                StatusColor::default(),
-            );
+impl SavedSettings {
+    /// Entry for `key` when one is stored, otherwise the global settings.
+    fn entry(&self, key: Option<&str>) -> &SavedSettings {
+        key.and_then(|k| self.files.get(k)).unwrap_or(self)
+    }
+
+    /// Effective settings for `key`. The per-file mode itself is always the global one.
+    pub fn settings_for(&self, key: Option<&str>) -> Settings {
+        Settings {
+            per_file: PerFileSettings::from_name(&self.per_file),
+            ..Settings::from(self.entry(key))
         }
-    };
-    let s = match fs::read_to_string(&path) {
-        Ok(x) => x,
-        Err(_) => {
-            return (
-                AccentColor::default(),
-                TextColor::default(),
-                TextStyle::default(),
-                BorderColor::default(),
-                StatusColor::default(),
-            );
+    }
+
+    /// Remembered filter for `key`, if any.
+    pub fn filter_for(&self, key: Option<&str>) -> Option<&str> {
+        key.and_then(|k| self.files.get(k))
+            .and_then(|e| e.filter.as_deref())
+    }
+
+    /// Store `settings` (and a non-empty `filter`) under `key`, or as the global settings when
+    /// `key` is `None`. Other files' entries are kept.
+    pub fn store(&mut self, settings: &Settings, key: Option<&str>, filter: &str) {
+        match key {
+            Some(k) => {
+                let mut entry = SavedSettings::from(settings);
+                entry.filter = Some(filter.to_string()).filter(|f| !f.is_empty());
+                self.files.insert(k.to_string(), entry);
+                self.per_file = settings.per_file.name().to_string();
+            }
+            None => {
+                let files = std::mem::take(&mut self.files);
+                let line_prefix = self.line_prefix.take();
+                let hint_text = self.hint_text.take();
+                let web_url = self.web_url.take();
+                let redact = std::mem::take(&mut self.redact);
+                *self = SavedSettings {
+                    files,
+                    line_prefix,
+                    hint_text,
+                    web_url,
+                    redact,
+                    ..SavedSettings::from(settings)
+                };
+            }
         }
-    };
-    let saved: SavedSettings = match serde_json::from_str(&s) {
-        Ok(x) => x,
-        Err(_) => {
-            return (
-                AccentColor::default(),
-                TextColor::default(),
-                TextStyle::default(),
-                BorderColor::default(),
-                StatusColor::default(),
-            );
+    }
+}
+
+/// Effective settings used by the app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    pub accent_color: AccentColor,
+    pub text_color: TextColor,
+    pub text_style: TextStyle,
+    pub border_color: BorderColor,
+    pub status_color: StatusColor,
+    pub gutter_color: GutterColor,
+    /// Spaces per tab stop when displaying log lines.
+    pub tab_width: usize,
+    pub language: Language,
+    /// When the filter changes, select the last (most recent) match instead of keeping the row.
+    pub filter_select_last: bool,
+    /// Show lines wider than the pane as `start…end` instead of clipping the end.
+    pub truncate_middle: bool,
+    /// Longest live-mode poll interval reached while the file is idle, in ms.
+    pub max_poll_ms: u64,
+    /// Keep every line visible while filtering and tint the matching ones instead of hiding the rest.
+    pub filter_tint: bool,
+    /// Status bar fields shown, in order; trimmed by priority when the terminal is narrow.
+    pub status_layout: StatusLayout,
+    /// Whether opening a file restores settings and filter saved for it.
+    pub per_file: PerFileSettings,
+    /// Treat a timestamped line and the untimestamped lines after it as one entry when
+    /// filtering and moving with ↑/↓.
+    pub group_entries: bool,
+    /// Hide the key hint line under the status bar; prompts and messages still use it.
+    pub hide_hint_line: bool,
+    /// Colour IP addresses, URLs, UUIDs and durations in log lines.
+    pub highlight_tokens: bool,
+    /// Only Ctrl+C quits; bare `q` and Esc in the log list (or an empty filter) do nothing.
+    pub quit_requires_modifier: bool,
+    /// Share (`p`) straight away even when no `redact` rules are set, without the warning.
+    pub skip_redact_warning: bool,
+    /// Leave empty and whitespace-only lines out of the log list.
+    pub hide_blank_lines: bool,
+    /// Match the filter against the text after a line's leading timestamp only.
+    pub filter_message_only: bool,
+    /// Log pane split into the matches and the selected line in context (`v`).
+    pub split_view: bool,
+    /// Draw tabs, trailing spaces and control characters visibly (`i`).
+    pub show_invisibles: bool,
+    /// Dense mode (`d`): no line-number gutter and a one-column selection marker.
+    pub dense: bool,
+    /// Column view (`t`): timestamps and levels aligned, messages left-justified after them.
+    pub columns: bool,
+    /// Characters the filter may hold; typing or pasting beyond it is ignored.
+    pub max_filter_len: usize,
+}
+
+impl Settings {
+    pub fn colors(&self) -> ThemeColors {
+        ThemeColors {
+            accent_color: self.accent_color,
+            text_color: self.text_color,
+            text_style: self.text_style,
+            border_color: self.border_color,
+            status_color: self.status_color,
+            gutter_color: self.gutter_color,
         }
+    }
+
+    pub fn set_colors(&mut self, colors: ThemeColors) {
+        self.accent_color = colors.accent_color;
+        self.text_color = colors.text_color;
+        self.text_style = colors.text_style;
+        self.border_color = colors.border_color;
+        self.status_color = colors.status_color;
+        self.gutter_color = colors.gutter_color;
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            accent_color: AccentColor::default(),
+            text_color: TextColor::default(),
+            text_style: TextStyle::default(),
+            border_color: BorderColor::default(),
+            status_color: StatusColor::default(),
+            gutter_color: GutterColor::default(),
+            tab_width: DEFAULT_TAB_WIDTH,
+            language: Language::default(),
+            filter_select_last: false,
+            truncate_middle: false,
+            max_poll_ms: DEFAULT_MAX_POLL_MS,
+            filter_tint: false,
+            status_layout: StatusLayout::default(),
+            per_file: PerFileSettings::default(),
+            group_entries: false,
+            hide_hint_line: false,
+            highlight_tokens: false,
+            quit_requires_modifier: false,
+            skip_redact_warning: false,
+            hide_blank_lines: false,
+            filter_message_only: false,
+            split_view: false,
+            show_invisibles: false,
+            dense: false,
+            columns: false,
+            max_filter_len: MAX_FILTER_LEN,
+        }
+    }
+}
+
+impl From<&SavedSettings> for Settings {
+    fn from(saved: &SavedSettings) -> Self {
+        let parse_accent = |v: &str| {
+            AccentColor::all()
+                .iter()
+                .find(|c| c.name().eq_ignore_ascii_case(v))
+                .copied()
+                .unwrap_or_default()
+        };
+        let parse_text_color = |v: &str| {
+            TextColor::all()
+                .iter()
+                .find(|c| c.name().eq_ignore_ascii_case(v))
+                .copied()
+                .unwrap_or_default()
+        };
+        let parse_text_style = |v: &str| {
+            TextStyle::all()
+                .iter()
+                .find(|c| c.name().eq_ignore_ascii_case(v))
+                .copied()
+                .unwrap_or_default()
+        };
+        let parse_border = |v: &str| {
+            BorderColor::all()
+                .iter()
+                .find(|c| c.name().eq_ignore_ascii_case(v))
+                .copied()
+                .unwrap_or_default()
+        };
+        let parse_status = |v: &str| {
+            StatusColor::all()
+                .iter()
+                .find(|c| c.name().eq_ignore_ascii_case(v))
+                .copied()
+                .unwrap_or_default()
+        };
+        let parse_gutter = |v: &str| {
+            GutterColor::all()
+                .iter()
+                .find(|c| c.name().eq_ignore_ascii_case(v))
+                .copied()
+                .unwrap_or_default()
+        };
+        let tab_width = if TAB_WIDTHS.contains(&saved.tab_width) {
+            saved.tab_width
+        } else {
+            DEFAULT_TAB_WIDTH
+        };
+        let max_poll_ms = if POLL_INTERVALS_MS.contains(&saved.max_poll_ms) {
+            saved.max_poll_ms
+        } else {
+            DEFAULT_MAX_POLL_MS
+        };
+        Self {
+            accent_color: parse_accent(&saved.accent),
+            text_color: parse_text_color(&saved.text_color),
+            text_style: parse_text_style(&saved.text_style),
+            border_color: parse_border(&saved.border_color),
+            status_color: parse_status(&saved.status_color),
+            gutter_color: parse_gutter(&saved.gutter_color),
+            tab_width,
+            language: Language::from_code(&saved.language).unwrap_or_default(),
+            filter_select_last: saved.filter_select_last,
+            truncate_middle: saved.truncate_middle,
+            max_poll_ms,
+            filter_tint: saved.filter_tint,
+            status_layout: StatusLayout::from_names(&saved.status_fields),
+            per_file: PerFileSettings::from_name(&saved.per_file),
+            group_entries: saved.group_entries,
+            hide_hint_line: saved.hide_hint_line,
+            highlight_tokens: saved.highlight_tokens,
+            quit_requires_modifier: saved.quit_requires_modifier,
+            skip_redact_warning: saved.skip_redact_warning,
+            hide_blank_lines: saved.hide_blank_lines,
+            filter_message_only: saved.filter_message_only,
+            split_view: saved.split_view,
+            show_invisibles: saved.show_invisibles,
+            dense: saved.dense,
+            columns: saved.columns,
+            max_filter_len: if saved.max_filter_len > 0 {
+                saved.max_filter_len
+            } else {
+                MAX_FILTER_LEN
+            },
+        }
+    }
+}
+
+impl From<&Settings> for SavedSettings {
+    fn from(settings: &Settings) -> Self {
+        Self {
+            accent: settings.accent_color.name().to_string(),
+            text_color: settings.text_color.name().to_string(),
+            text_style: settings.text_style.name().to_string(),
+            border_color: settings.border_color.name().to_string(),
+            status_color: settings.status_color.name().to_string(),
+            gutter_color: settings.gutter_color.name().to_string(),
+            tab_width: settings.tab_width,
+            language: settings.language.code().to_string(),
+            filter_select_last: settings.filter_select_last,
+            truncate_middle: settings.truncate_middle,
+            max_poll_ms: settings.max_poll_ms,
+            filter_tint: settings.filter_tint,
+            status_fields: settings.status_layout.names(),
+            per_file: settings.per_file.name().to_string(),
+            group_entries: settings.group_entries,
+            hide_hint_line: settings.hide_hint_line,
+            highlight_tokens: settings.highlight_tokens,
+            quit_requires_modifier: settings.quit_requires_modifier,
+            skip_redact_warning: settings.skip_redact_warning,
+            hide_blank_lines: settings.hide_blank_lines,
+            filter_message_only: settings.filter_message_only,
+            split_view: settings.split_view,
+            show_invisibles: settings.show_invisibles,
+            dense: settings.dense,
+            columns: settings.columns,
+            max_filter_len: settings.max_filter_len,
+            hint_text: None,
+            line_prefix: None,
+            web_url: None,
+            redact: Vec::new(),
+            filter: None,
+            files: BTreeMap::new(),
+        }
+    }
+}
+
+fn read_saved() -> Option<SavedSettings> {
+    read_saved_from(&settings_path()).ok().flatten()
+}
+
+/// The settings saved at `path`; `None` when there is no file, an error when it cannot be read
+/// or parsed.
+pub fn read_saved_from(path: &Path) -> Result<Option<SavedSettings>, String> {
+    let s = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.to_string()),
     };
-    let parse_accent = |v: &str| {
-        AccentColor::all()
-            .iter()
-            .find(|c| c.name().eq_ignore_ascii_case(v))
-            .copied()
-            .unwrap_or_default()
-    };
-    let parse_text_color = |v: &str| {
-        TextColor::all()
-            .iter()
-            .find(|c| c.name().eq_ignore_ascii_case(v))
-            .copied()
-            .unwrap_or_default()
-    };
-    let parse_text_style = |v: &str| {
-        TextStyle::all()
-            .iter()
-            .find(|c| c.name().eq_ignore_ascii_case(v))
-            .copied()
-            .unwrap_or_default()
-    };
-    let parse_border = |v: &str| {
-        BorderColor::all()
-            .iter()
-            .find(|c| c.name().eq_ignore_ascii_case(v))
-            .copied()
-            .unwrap_or_default()
-    };
-    let parse_status = |v: &str| {
-        StatusColor::all()
-            .iter()
-            .find(|c| c.name().eq_ignore_ascii_case(v))
-            .copied()
-            .unwrap_or_default()
-    };
-    (
-        parse_accent(&saved.accent),
-        parse_text_color(&saved.text_color),
-        parse_text_style(&saved.text_style),
-        parse_border(&saved.border_color),
-        parse_status(&saved.status_color),
-    )
-}
-
-pub fn save_settings(
-    accent: AccentColor,
-    text_color: TextColor,
-    text_style: TextStyle,
-    border_color: BorderColor,
-    status_color: StatusColor,
-) {
-    let path = match settings_path() {
-        Some(p) => p,
-        None => return,
-    };
-    let saved = SavedSettings {
-        accent: accent.name().to_string(),
-        text_color: text_color.name().to_string(),
-        text_style: text_style.name().to_string(),
-        border_color: border_color.name().to_string(),
-        status_color: status_color.name().to_string(),
-    };
-    let s = match serde_json::to_string_pretty(&saved) {
-        Ok(x) => x,
-        Err(_) => return,
-    };
+    serde_json::from_str(&s)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+/// Why `settings.json` could not be read, if it exists but is unreadable or invalid: its
+/// settings and `redact` rules are then not applied.
+pub fn settings_error() -> Option<String> {
+    read_saved_from(&settings_path()).err()
+}
+
+/// Settings for the file stored under `key` (see [`settings_key`]), or the global settings.
+pub fn load_settings(key: Option<&str>) -> Settings {
+    match read_saved() {
+        Some(saved) => saved.settings_for(key),
+        None => Settings::default(),
+    }
+}
+
+/// Filter remembered for the file stored under `key`.
+pub fn load_filter(key: Option<&str>) -> Option<String> {
+    read_saved()?.filter_for(key).map(str::to_string)
+}
+
+/// Line prefix template set by hand in `settings.json`, if any.
+pub fn load_line_prefix() -> Option<String> {
+    read_saved()?.line_prefix
+}
+
+/// Custom hint line text set by hand in `settings.json`, if any.
+pub fn load_hint_text() -> Option<String> {
+    read_saved()?.hint_text
+}
+
+/// Ratlog Web URL set by hand in `settings.json`, if any.
+pub fn load_web_url() -> Option<String> {
+    read_saved()?.web_url
+}
+
+/// Redaction rules set by hand in `settings.json`.
+pub fn load_redact_rules() -> Vec<RedactRule> {
+    read_saved().map(|s| s.redact).unwrap_or_default()
+}
+
+fn write_saved(saved: &SavedSettings) -> std::io::Result<()> {
+    let path = settings_path();
+    let s = serde_json::to_string_pretty(saved)?;
     if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent);
+        fs::create_dir_all(parent)?;
     }
-    let _ = fs::OpenOptions::new()
+    fs::OpenOptions::new()
         .write(true)
         .create(true)
         .truncate(true)
         .open(&path)
-        .and_then(|mut f| f.write_all(s.as_bytes()));
+        .and_then(|mut f| f.write_all(s.as_bytes()))
+}
+
+/// Save `settings` for the file under `key` (with its current `filter`), or globally when `key`
+/// is `None`. A `settings.json` that cannot be read is left alone, so the parts written by hand
+/// (`redact` rules, per-file entries) are not replaced; the error says why nothing was saved.
+pub fn save_settings(settings: &Settings, key: Option<&str>, filter: &str) -> Result<(), String> {
+    let mut saved =
+        read_saved_from(&settings_path())?.unwrap_or_else(|| SavedSettings::from(settings));
+    saved.store(settings, key, filter);
+    write_saved(&saved).map_err(|e| e.to_string())
+}
+
+/// `saved` (or the defaults) as `ratlog config export` prints it: every top-level value filled
+/// in, with unknown names replaced by what the app uses; per-file entries and the global-only
+/// `hint_text`, `line_prefix`, `web_url` and `redact` are kept as they are.
+pub fn export_json(saved: Option<SavedSettings>) -> String {
+    let mut saved = saved.unwrap_or_else(|| SavedSettings::from(&Settings::default()));
+    let settings = saved.settings_for(None);
+    saved.store(&settings, None, "");
+    serde_json::to_string_pretty(&saved).unwrap_or_default()
+}
+
+/// The saved settings for `ratlog config export`.
+pub fn export_settings() -> String {
+    export_json(read_saved())
+}
+
+/// Replace `settings.json` with `json` (a `ratlog config export`), after checking that it
+/// parses. Returns the path written.
+pub fn import_settings(json: &str) -> std::io::Result<PathBuf> {
+    let saved: SavedSettings = serde_json::from_str(json)?;
+    write_saved(&saved)?;
+    Ok(settings_path())
 }
@@ -0,0 +1,211 @@
+//! Status bar: ordered field segments, layout presets and fitting them to the width.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusField {
+    Source,
+    Count,
+    Dropped,
+    Live,
+    Rate,
+    Uptime,
+    Memory,
+    Filter,
+    Keys,
+}
+
+impl StatusField {
+    /// Name used in `settings.json`.
+    pub fn name(self) -> &'static str {
+        match self {
+            StatusField::Source => "source",
+            StatusField::Count => "count",
+            StatusField::Dropped => "dropped",
+            StatusField::Live => "live",
+            StatusField::Rate => "rate",
+            StatusField::Uptime => "uptime",
+            StatusField::Memory => "memory",
+            StatusField::Filter => "filter",
+            StatusField::Keys => "keys",
+        }
+    }
+    pub fn from_name(name: &str) -> Option<StatusField> {
+        StatusField::all()
+            .iter()
+            .find(|f| f.name().eq_ignore_ascii_case(name.trim()))
+            .copied()
+    }
+    pub fn all() -> &'static [StatusField] {
+        &[
+            StatusField::Source,
+            StatusField::Count,
+            StatusField::Dropped,
+            StatusField::Live,
+            StatusField::Rate,
+            StatusField::Uptime,
+            StatusField::Memory,
+            StatusField::Filter,
+            StatusField::Keys,
+        ]
+    }
+    /// Lower is more important; the highest value is dropped first when space is tight.
+    pub fn priority(self) -> u8 {
+        match self {
+            StatusField::Count => 0,
+            StatusField::Live => 1,
+            StatusField::Filter => 2,
+            StatusField::Source => 3,
+            StatusField::Rate => 4,
+            StatusField::Dropped => 5,
+            StatusField::Uptime => 6,
+            StatusField::Memory => 7,
+            StatusField::Keys => 8,
+        }
+    }
+}
+
+const FIELD_COUNT: usize = 9;
+
+/// Which status fields are shown, in display order. Fixed-size so `Settings` stays `Copy`.
+#[derive(Debug, Clone, Copy)]
+pub struct StatusLayout {
+    fields: [StatusField; FIELD_COUNT],
+    len: usize,
+}
+
+impl PartialEq for StatusLayout {
+    fn eq(&self, other: &Self) -> bool {
+        self.fields() == other.fields()
+    }
+}
+
+impl Eq for StatusLayout {}
+
+impl StatusLayout {
+    pub const FULL: StatusLayout = StatusLayout::preset(&[
+        StatusField::Source,
+        StatusField::Count,
+        StatusField::Dropped,
+        StatusField::Live,
+        StatusField::Rate,
+        StatusField::Uptime,
+        StatusField::Memory,
+        StatusField::Filter,
+        StatusField::Keys,
+    ]);
+    pub const COMPACT: StatusLayout = StatusLayout::preset(&[
+        StatusField::Count,
+        StatusField::Live,
+        StatusField::Rate,
+        StatusField::Filter,
+    ]);
+    pub const MINIMAL: StatusLayout =
+        StatusLayout::preset(&[StatusField::Count, StatusField::Live]);
+
+    const fn preset(fields: &[StatusField]) -> StatusLayout {
+        let mut out = [StatusField::Count; FIELD_COUNT];
+        let mut len = 0;
+        while len < fields.len() && len < FIELD_COUNT {
+            out[len] = fields[len];
+            len += 1;
+        }
+        StatusLayout { fields: out, len }
+    }
+
+    /// Presets offered in the settings panel.
+    pub fn presets() -> &'static [StatusLayout] {
+        &[
+            StatusLayout::FULL,
+            StatusLayout::COMPACT,
+            StatusLayout::MINIMAL,
+        ]
+    }
+
+    /// Layout from fields in order, ignoring repeats; an empty list gives [`StatusLayout::FULL`].
+    pub fn new(fields: &[StatusField]) -> StatusLayout {
+        let mut layout = StatusLayout {
+            fields: [StatusField::Count; FIELD_COUNT],
+            len: 0,
+        };
+        for &field in fields {
+            if !layout.fields().contains(&field) && layout.len < FIELD_COUNT {
+                layout.fields[layout.len] = field;
+                layout.len += 1;
+            }
+        }
+        if layout.len == 0 {
+            StatusLayout::FULL
+        } else {
+            layout
+        }
+    }
+
+    /// Layout from `settings.json` names; unknown names are skipped.
+    pub fn from_names(names: &[String]) -> StatusLayout {
+        let fields: Vec<StatusField> = names
+            .iter()
+            .filter_map(|n| StatusField::from_name(n))
+            .collect();
+        StatusLayout::new(&fields)
+    }
+
+    pub fn fields(&self) -> &[StatusField] {
+        &self.fields[..self.len]
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.fields().iter().map(|f| f.name().to_string()).collect()
+    }
+
+    pub fn contains(&self, field: StatusField) -> bool {
+        self.fields().contains(&field)
+    }
+}
+
+impl Default for StatusLayout {
+    fn default() -> Self {
+        StatusLayout::FULL
+    }
+}
+
+const SEPARATOR: &str = "  |  ";
+
+/// Join `segments` into a status line no wider than `width` columns. Lowest-priority
+/// segments are dropped first; if one segment is still too wide it is cut at a word
+/// boundary and ends with `…`. Empty segments are skipped.
+pub fn fit_status(segments: &[(StatusField, String)], width: usize) -> String {
+    let mut kept: Vec<&(StatusField, String)> = segments
+        .iter()
+        .filter(|(_, text)| !text.is_empty())
+        .collect();
+    let render = |kept: &[&(StatusField, String)]| {
+        let parts: Vec<&str> = kept.iter().map(|(_, text)| text.as_str()).collect();
+        format!(" {} ", parts.join(SEPARATOR))
+    };
+    let mut line = render(&kept);
+    while line.chars().count() > width && kept.len() > 1 {
+        let drop = kept
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, (field, _))| field.priority())
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        kept.remove(drop);
+        line = render(&kept);
+    }
+    if line.chars().count() <= width {
+        return line;
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let cut: String = line.chars().take(width - 1).collect();
+    let mid_word = line
+        .chars()
+        .nth(width - 1)
+        .is_some_and(|next| !next.is_whitespace());
+    let cut = match cut.trim_end().rfind(char::is_whitespace) {
+        Some(pos) if mid_word && pos > 0 => cut[..pos].trim_end(),
+        _ => cut.trim_end(),
+    };
+    format!("{}…", cut)
+}
@@ -2,6 +2,8 @@
 
 use ratatui::style::{Color, Modifier, Style};
 
+use crate::highlight::Token;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Focus {
     Filter,
@@ -172,6 +174,139 @@ impl StatusColor {
     }
 }
 
+/// Colour of the line-number gutter in the log list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GutterColor {
+    #[default]
+    DarkGray,
+    Gray,
+    /// Same colour and style as the log text.
+    Text,
+}
+
+impl GutterColor {
+    pub fn name(self) -> &'static str {
+        match self {
+            GutterColor::DarkGray => "Dark",
+            GutterColor::Gray => "Gray",
+            GutterColor::Text => "Text",
+        }
+    }
+    pub fn all() -> &'static [GutterColor] {
+        &[GutterColor::DarkGray, GutterColor::Gray, GutterColor::Text]
+    }
+}
+
+/// The colour and text style settings a theme preset sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ThemeColors {
+    pub accent_color: AccentColor,
+    pub text_color: TextColor,
+    pub text_style: TextStyle,
+    pub border_color: BorderColor,
+    pub status_color: StatusColor,
+    pub gutter_color: GutterColor,
+}
+
+/// A named set of colours, applied for one session with `--theme NAME`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemePreset {
+    pub name: &'static str,
+    pub colors: ThemeColors,
+}
+
+const THEME_PRESETS: &[ThemePreset] = &[
+    ThemePreset {
+        name: "default",
+        colors: ThemeColors {
+            accent_color: AccentColor::Cyan,
+            text_color: TextColor::White,
+            text_style: TextStyle::Normal,
+            border_color: BorderColor::Gray,
+            status_color: StatusColor::Gray,
+            gutter_color: GutterColor::DarkGray,
+        },
+    },
+    ThemePreset {
+        name: "mono",
+        colors: ThemeColors {
+            accent_color: AccentColor::Blue,
+            text_color: TextColor::Gray,
+            text_style: TextStyle::Normal,
+            border_color: BorderColor::DarkGray,
+            status_color: StatusColor::DarkGray,
+            gutter_color: GutterColor::DarkGray,
+        },
+    },
+    ThemePreset {
+        name: "bright",
+        colors: ThemeColors {
+            accent_color: AccentColor::Yellow,
+            text_color: TextColor::White,
+            text_style: TextStyle::Bold,
+            border_color: BorderColor::White,
+            status_color: StatusColor::White,
+            gutter_color: GutterColor::Gray,
+        },
+    },
+    ThemePreset {
+        name: "forest",
+        colors: ThemeColors {
+            accent_color: AccentColor::Green,
+            text_color: TextColor::Green,
+            text_style: TextStyle::Normal,
+            border_color: BorderColor::DarkGray,
+            status_color: StatusColor::Gray,
+            gutter_color: GutterColor::DarkGray,
+        },
+    },
+    ThemePreset {
+        name: "ocean",
+        colors: ThemeColors {
+            accent_color: AccentColor::Blue,
+            text_color: TextColor::Cyan,
+            text_style: TextStyle::Normal,
+            border_color: BorderColor::Gray,
+            status_color: StatusColor::Gray,
+            gutter_color: GutterColor::Gray,
+        },
+    },
+    ThemePreset {
+        name: "neon",
+        colors: ThemeColors {
+            accent_color: AccentColor::Magenta,
+            text_color: TextColor::Yellow,
+            text_style: TextStyle::Normal,
+            border_color: BorderColor::White,
+            status_color: StatusColor::White,
+            gutter_color: GutterColor::Text,
+        },
+    },
+];
+
+impl ThemePreset {
+    pub fn all() -> &'static [ThemePreset] {
+        THEME_PRESETS
+    }
+
+    /// The preset called `name`, ignoring case.
+    pub fn find(name: &str) -> Option<ThemePreset> {
+        THEME_PRESETS
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    /// Every preset name, comma-separated, for error messages and `--help`.
+    pub fn names() -> String {
+        THEME_PRESETS
+            .iter()
+            .map(|p| p.name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 pub fn border_style(border_color: BorderColor) -> Style {
     Style::default().fg(border_color.to_ratatui())
 }
@@ -186,6 +321,43 @@ pub fn log_text_style(text_color: TextColor, text_style: TextStyle) -> Style {
         .add_modifier(text_style.modifier())
 }
 
+pub fn gutter_style(gutter: GutterColor, text_color: TextColor, text_style: TextStyle) -> Style {
+    match gutter {
+        GutterColor::DarkGray => Style::default().fg(Color::DarkGray),
+        GutterColor::Gray => Style::default().fg(Color::Gray),
+        GutterColor::Text => log_text_style(text_color, text_style),
+    }
+}
+
+/// Colour of a highlighted token; only the foreground is set so row styles still apply.
+pub fn token_style(token: Token) -> Style {
+    match token {
+        Token::Url => Style::default().fg(Color::Blue),
+        Token::Uuid => Style::default().fg(Color::Magenta),
+        Token::Ip => Style::default().fg(Color::Cyan),
+        Token::Duration => Style::default().fg(Color::Yellow),
+    }
+}
+
+/// Find matches in the shown lines.
+pub fn find_match_style() -> Style {
+    Style::default().fg(Color::Black).bg(Color::Yellow)
+}
+
+/// The current find match; bold and underlined so it still stands out on the selected row,
+/// whose highlight replaces the colours.
+pub fn find_current_style() -> Style {
+    Style::default()
+        .fg(Color::Black)
+        .bg(Color::Indexed(208))
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+}
+
+/// Subtle background for lines matching the filter in tint mode.
+pub fn match_tint_style() -> Style {
+    Style::default().bg(Color::Indexed(236))
+}
+
 pub fn status_style(status_color: StatusColor) -> Style {
     Style::default().fg(status_color.to_ratatui())
 }
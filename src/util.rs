@@ -1,6 +1,18 @@
-//! Helpers: format_bytes, centered_rect, current_process_memory.
+//! Helpers: format_bytes, centered_rect, current_process_memory, tab/control-char display,
+//! path expansion, clipboard, live poll timing and the config directory.
+
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
 use ratatui::layout::Rect;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::constants::{IDLE_POLLS_PER_STEP, POLL_INTERVALS_MS, RATE_WINDOW};
+use crate::i18n::{fill, t};
 
 pub fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
@@ -17,9 +29,86 @@ pub fn format_bytes(bytes: u64) -> String {
     }
 }
 
+/// Live-mode sleep after `idle_polls` consecutive empty polls, never above `max_ms`.
+pub fn poll_interval(idle_polls: u32, max_ms: u64) -> Duration {
+    let step = (idle_polls / IDLE_POLLS_PER_STEP) as usize;
+    let ms = POLL_INTERVALS_MS[step.min(POLL_INTERVALS_MS.len() - 1)];
+    Duration::from_millis(ms.min(max_ms).max(POLL_INTERVALS_MS[0]))
+}
+
+/// Moving average of lines appended per second over the last `RATE_WINDOW`.
+#[derive(Debug, Default)]
+pub struct RateMeter {
+    /// Poll times with the number of lines each added; entries older than the window are pruned.
+    samples: VecDeque<(Instant, usize)>,
+}
+
+impl RateMeter {
+    /// Upper bound on stored samples, so a very fast poll loop stays cheap.
+    const MAX_SAMPLES: usize = 64;
+
+    pub fn record(&mut self, now: Instant, lines: usize) {
+        self.prune(now);
+        if self.samples.len() == Self::MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((now, lines));
+    }
+
+    pub fn rate(&mut self, now: Instant) -> f64 {
+        self.prune(now);
+        let total: usize = self.samples.iter().map(|&(_, n)| n).sum();
+        total as f64 / RATE_WINDOW.as_secs_f64()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while let Some(&(at, _)) = self.samples.front() {
+            if now.saturating_duration_since(at) > RATE_WINDOW {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+/// Status bar label for a rate: `~42 l/s`, or one decimal below 10 (`~0.4 l/s`).
+pub fn format_rate(lines_per_sec: f64) -> String {
+    if lines_per_sec < 10.0 {
+        format!("~{:.1} l/s", lines_per_sec)
+    } else {
+        format!("~{:.0} l/s", lines_per_sec)
+    }
+}
+
+/// Compact label for how long something has run: `45s`, `3m12s`, `2h05m`, `3d04h`.
+pub fn format_uptime(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    match (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60) {
+        (0, 0, 0, s) => format!("{}s", s),
+        (0, 0, m, s) => format!("{}m{:02}s", m, s),
+        (0, h, m, _) => format!("{}h{:02}m", h, m),
+        (d, h, _, _) => format!("{}d{:02}h", d, h),
+    }
+}
+
+/// Short label for a poll interval: `400 ms`, `2 s`.
+pub fn format_millis(ms: u64) -> String {
+    if ms >= 1000 && ms.is_multiple_of(1000) {
+        format!("{} s", ms / 1000)
+    } else {
+        format!("{} ms", ms)
+    }
+}
+
 pub fn centered_rect(area: Rect, width_pct: u16, height_pct: u16) -> Rect {
-    let w = area.width * width_pct / 100;
-    let h = area.height * height_pct / 100;
+    // In u32 so a very wide terminal doesn't overflow the product.
+    let w = (u32::from(area.width) * u32::from(width_pct) / 100) as u16;
+    let h = (u32::from(area.height) * u32::from(height_pct) / 100) as u16;
     let x = area.x + (area.width.saturating_sub(w)) / 2;
     let y = area.y + (area.height.saturating_sub(h)) / 2;
     Rect {
@@ -30,6 +119,36 @@ pub fn centered_rect(area: Rect, width_pct: u16, height_pct: u16) -> Rect {
     }
 }
 
+/// Range `start..end` of at most `height` rows out of `len` that keeps `selected` in the
+/// middle, shifted to stay inside `0..len` near either end.
+pub fn context_window(selected: usize, len: usize, height: usize) -> (usize, usize) {
+    let height = height.min(len);
+    let start = selected
+        .saturating_sub(height / 2)
+        .min(len.saturating_sub(height));
+    (start, start + height)
+}
+
+/// Row index `percent`% of the way through `len` rows (like less's `50%`): 0 is the first
+/// row, 100 or more the last.
+pub fn percent_index(percent: usize, len: usize) -> usize {
+    percent.min(100) * len.saturating_sub(1) / 100
+}
+
+/// Row index under mouse row `row` on a scrollbar track starting at `track_top` with
+/// `track_height` cells, for a list of `len` rows: the top cell maps to the first row, the
+/// bottom cell to the last. Rows outside the track clamp to its ends.
+pub fn scrollbar_index(row: u16, track_top: u16, track_height: u16, len: usize) -> usize {
+    if len == 0 || track_height <= 1 {
+        return 0;
+    }
+    let cell = row.saturating_sub(track_top).min(track_height - 1) as usize;
+    (cell * (len - 1) + (track_height as usize - 1) / 2) / (track_height as usize - 1)
+}
+
+/// Shown in place of the RAM figure when the process can't be found.
+pub const MEMORY_UNAVAILABLE: &str = "—";
+
 pub fn current_process_memory() -> String {
     use sysinfo::System;
     let mut sys = System::new_all();
@@ -37,6 +156,304 @@ pub fn current_process_memory() -> String {
     if let Some(me) = sys.process(sysinfo::Pid::from(std::process::id() as usize)) {
         format_bytes(me.memory())
     } else {
-        "—".to_string()
+        MEMORY_UNAVAILABLE.to_string()
+    }
+}
+
+/// Expand tab characters to spaces, aligning to multiples of `tab_width` columns.
+pub fn expand_tabs(line: &str, tab_width: usize) -> String {
+    if !line.contains('\t') || tab_width == 0 {
+        return line.to_string();
+    }
+    let mut out = String::with_capacity(line.len() + tab_width);
+    let mut col = 0usize;
+    for c in line.chars() {
+        if c == '\t' {
+            let spaces = tab_width - col % tab_width;
+            out.extend(std::iter::repeat_n(' ', spaces));
+            col += spaces;
+        } else {
+            out.push(c);
+            col += 1;
+        }
+    }
+    out
+}
+
+/// Split a line into display segments, marking control characters as visible symbols.
+/// Returns `(text, is_invisible)` pairs; tabs become `→` padded to the next tab stop,
+/// other control chars use caret notation (`\r` → `^M`, DEL → `^?`).
+pub fn visible_segments(line: &str, tab_width: usize) -> Vec<(String, bool)> {
+    let mut segments: Vec<(String, bool)> = Vec::new();
+    let mut plain = String::new();
+    let mut col = 0usize;
+    for c in line.chars() {
+        let symbol = match c {
+            '\t' => {
+                let width = if tab_width == 0 {
+                    1
+                } else {
+                    tab_width - col % tab_width
+                };
+                let mut s = String::from('→');
+                s.extend(std::iter::repeat_n(' ', width - 1));
+                Some(s)
+            }
+            '\x7f' => Some("^?".to_string()),
+            c if (c as u32) < 0x20 => Some(format!("^{}", (b'@' + c as u8) as char)),
+            _ => None,
+        };
+        match symbol {
+            Some(s) => {
+                if !plain.is_empty() {
+                    segments.push((std::mem::take(&mut plain), false));
+                }
+                col += s.chars().count();
+                segments.push((s, true));
+            }
+            None => {
+                plain.push(c);
+                col += 1;
+            }
+        }
+    }
+    if !plain.is_empty() {
+        segments.push((plain, false));
+    }
+    segments
+}
+
+/// Whether `text` matches the shell-style `pattern`: `*` stands for any run of characters
+/// (including none), `?` for exactly one.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*` and the text position it is currently standing in for.
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p + 1, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                // Let the last `*` swallow one more character and retry from there.
+                Some((after, from)) => {
+                    p = after;
+                    t = from + 1;
+                    star = Some((after, from + 1));
+                }
+                None => return false,
+            },
+        }
     }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Expand a leading `~` to the home directory and `$VAR` / `${VAR}` to environment values.
+/// Returns an error naming the variable when one is not set.
+pub fn expand_path(input: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    if rest == "~" || rest.starts_with("~/") || rest.starts_with("~\\") {
+        let home = dirs::home_dir().ok_or(t().home_dir_not_found)?;
+        out.push_str(&home.to_string_lossy());
+        rest = &rest[1..];
+    }
+    let mut chars = rest.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+        let after = &rest[i + 1..];
+        let (name, consumed) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            }
+        } else {
+            let end = after
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], end)
+        };
+        if name.is_empty() {
+            out.push('$');
+            continue;
+        }
+        let value = std::env::var(name).map_err(|_| fill(t().env_var_not_set, &[name]))?;
+        out.push_str(&value);
+        while chars.next_if(|&(j, _)| j <= i + consumed).is_some() {}
+    }
+    Ok(out)
+}
+
+/// Cut `s` after its first `max_chars` characters; returns whether anything was removed.
+pub fn truncate_chars(s: &mut String, max_chars: usize) -> bool {
+    match s.char_indices().nth(max_chars) {
+        Some((end, _)) => {
+            s.truncate(end);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Shorten `line` to at most `max_chars` characters by replacing its middle with `…`.
+pub fn truncate_middle(line: &str, max_chars: usize) -> String {
+    let len = line.chars().count();
+    if len <= max_chars {
+        return line.to_string();
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+    let keep = max_chars - 1;
+    let head = keep.div_ceil(2);
+    let tail = keep - head;
+    let mut out: String = line.chars().take(head).collect();
+    out.push('…');
+    out.extend(line.chars().skip(len - tail));
+    out
+}
+
+/// Copy `text` to the system clipboard via the terminal (OSC 52), so it also works over SSH.
+pub fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    crossterm::execute!(
+        std::io::stdout(),
+        crossterm::clipboard::CopyToClipboard::to_clipboard_from(text)
+    )
+}
+
+/// Byte index of the grapheme before `cursor`, so Left and Backspace step over a whole
+/// `ğ`, `é` (e + combining accent) or emoji. `cursor` must be a char boundary.
+pub fn prev_grapheme(s: &str, cursor: usize) -> usize {
+    s[..cursor.min(s.len())]
+        .grapheme_indices(true)
+        .next_back()
+        .map_or(0, |(i, _)| i)
+}
+
+/// Byte index just past the grapheme at `cursor`. `cursor` must be a char boundary.
+pub fn next_grapheme(s: &str, cursor: usize) -> usize {
+    let cursor = cursor.min(s.len());
+    s[cursor..]
+        .graphemes(true)
+        .next()
+        .map_or(s.len(), |g| cursor + g.len())
+}
+
+/// Terminal column of byte offset `cursor` in `s` (wide characters count as two).
+pub fn display_column(s: &str, cursor: usize) -> usize {
+    s[..cursor.min(s.len())].width()
+}
+
+/// Byte index of the start of the word before `cursor` (readline Alt+B): skips
+/// whitespace, then the word. `cursor` must be a char boundary.
+pub fn prev_word_start(s: &str, cursor: usize) -> usize {
+    let before = &s[..cursor.min(s.len())];
+    let trimmed = before.trim_end();
+    match trimmed.rfind(char::is_whitespace) {
+        Some(pos) => pos + trimmed[pos..].chars().next().map_or(1, char::len_utf8),
+        None => 0,
+    }
+}
+
+/// Byte index of the end of the word after `cursor` (readline Alt+F): skips whitespace,
+/// then the word. `cursor` must be a char boundary.
+pub fn next_word_end(s: &str, cursor: usize) -> usize {
+    let cursor = cursor.min(s.len());
+    let after = &s[cursor..];
+    let word = after.trim_start();
+    let skipped = after.len() - word.len();
+    cursor + skipped + word.find(char::is_whitespace).unwrap_or(word.len())
+}
+
+/// Remove the word before `cursor` plus the whitespace after it (Ctrl+W); returns the new
+/// cursor. `cursor` must be a char boundary.
+pub fn delete_word_before(s: &mut String, cursor: usize) -> usize {
+    let cursor = cursor.min(s.len());
+    let start = prev_word_start(s, cursor);
+    s.drain(start..cursor);
+    start
+}
+
+/// Pick the ratlog config directory from the platform config dir or `$XDG_CONFIG_HOME`,
+/// falling back to `.ratlog` in the current directory. The flag is true for that fallback.
+pub fn resolve_config_dir(
+    platform: Option<PathBuf>,
+    xdg_config_home: Option<OsString>,
+) -> (PathBuf, bool) {
+    if let Some(dir) = platform {
+        return (dir.join("ratlog"), false);
+    }
+    match xdg_config_home.filter(|x| !x.is_empty()) {
+        Some(xdg) => (PathBuf::from(xdg).join("ratlog"), false),
+        None => (PathBuf::from(".ratlog"), true),
+    }
+}
+
+/// Directory holding `settings.json` and the login token (see [`config_dir_warning`]).
+pub fn config_dir() -> PathBuf {
+    resolve_config_dir(dirs::config_dir(), std::env::var_os("XDG_CONFIG_HOME")).0
+}
+
+/// The warning that no config directory exists and the working-directory fallback is used.
+/// `main` prints it on stderr before the viewer takes over the terminal.
+pub fn config_dir_warning() -> Option<String> {
+    let (dir, fallback) =
+        resolve_config_dir(dirs::config_dir(), std::env::var_os("XDG_CONFIG_HOME"));
+    fallback.then(|| {
+        let shown = std::env::current_dir()
+            .map(|cwd| cwd.join(&dir))
+            .unwrap_or(dir);
+        fill(t().config_dir_fallback, &[&shown.display().to_string()])
+    })
+}
+
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Whether `name` can be a `--profile`: letters, digits, `-`, `_` and `.`, not starting with
+/// `.`, so it stays a single file name under `profiles/`.
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Use the profile `name` (`--profile`) for settings, the login token and recent shares for
+/// the rest of the process. Only the first call has an effect.
+pub fn set_profile(name: &str) {
+    let _ = PROFILE.set(name.to_string());
+}
+
+/// The profile set by [`set_profile`], if any.
+pub fn profile() -> Option<&'static str> {
+    PROFILE.get().map(String::as_str)
+}
+
+/// `default_name` in `dir`, or for a profile, `profiles/{profile}{suffix}` in `dir`.
+pub fn profile_path(
+    dir: PathBuf,
+    profile: Option<&str>,
+    default_name: &str,
+    suffix: &str,
+) -> PathBuf {
+    match profile {
+        Some(name) => dir.join("profiles").join(format!("{}{}", name, suffix)),
+        None => dir.join(default_name),
+    }
+}
+
+/// A file in [`config_dir`] for the current profile (see [`profile_path`]).
+pub fn profile_file(default_name: &str, suffix: &str) -> PathBuf {
+    profile_path(config_dir(), profile(), default_name, suffix)
 }
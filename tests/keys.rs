@@ -0,0 +1,189 @@
+//! Drive `App` with key presses and live polls and check which file line is selected.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratlog::cli::RunOptions;
+use ratlog::{App, AppConfig, LoadOptions, LogSource, load_source};
+
+fn numbered(lines: std::ops::RangeInclusive<usize>) -> Vec<String> {
+    lines.map(|i| format!("line {i}")).collect()
+}
+
+/// A log file holding `lines`, unique to the test `name`.
+fn temp_log(name: &str, lines: &[String]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("ratlog-keys-{}-{name}", std::process::id()));
+    let content: String = lines.iter().map(|l| format!("{l}\n")).collect();
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn append(path: &PathBuf, lines: &[&str]) {
+    let mut file = std::fs::OpenOptions::new().append(true).open(path).unwrap();
+    for line in lines {
+        writeln!(file, "{line}").unwrap();
+    }
+}
+
+/// An `App` showing `lines` (no file behind them) with default settings.
+fn test_app(lines: Vec<String>) -> App {
+    App::new_with_config(
+        lines,
+        None,
+        0,
+        1,
+        RunOptions::default(),
+        AppConfig::default(),
+    )
+}
+
+/// An `App` viewing a temporary log file holding `lines`, loaded the way `main` loads one, with
+/// default settings. Also returns the file's path, for appending to it.
+fn file_app(name: &str, lines: &[String]) -> (App, PathBuf) {
+    let path = temp_log(name, lines);
+    let (logs, file_path, file_offset, file_line_start) =
+        load_source(LogSource::File(path.clone()), LoadOptions::default()).unwrap();
+    let app = App::new_with_config(
+        logs,
+        file_path,
+        file_offset,
+        file_line_start,
+        RunOptions::default(),
+        AppConfig::default(),
+    );
+    (app, path)
+}
+
+fn press(app: &mut App, code: KeyCode) {
+    app.on_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+}
+
+fn ctrl(app: &mut App, c: char) {
+    app.on_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+}
+
+fn type_text(app: &mut App, text: &str) {
+    for c in text.chars() {
+        press(app, KeyCode::Char(c));
+    }
+}
+
+#[test]
+fn toggling_live_keeps_the_selected_line() {
+    let (mut app, path) = file_app("toggle", &numbered(1..=5));
+    press(&mut app, KeyCode::Down);
+    press(&mut app, KeyCode::Down);
+    assert_eq!(app.selected_file_line(), Some(3));
+    press(&mut app, KeyCode::Char('L'));
+    append(&path, &["line 6", "line 7"]);
+    assert!(app.poll_live());
+    assert_eq!(app.selected_file_line(), Some(3));
+    press(&mut app, KeyCode::Char('L'));
+    assert_eq!(app.selected_file_line(), Some(3));
+    append(&path, &["line 8"]);
+    assert!(!app.poll_live(), "polled with live mode off");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn live_follows_the_tail_until_moving_away() {
+    let (mut app, path) = file_app("follow", &numbered(1..=5));
+    press(&mut app, KeyCode::Char('G'));
+    press(&mut app, KeyCode::Char('L'));
+    append(&path, &["line 6", "line 7"]);
+    assert!(app.poll_live());
+    assert_eq!(app.selected_file_line(), Some(7));
+    // Moving up stops following: the next appends leave the selection where it is.
+    press(&mut app, KeyCode::Up);
+    append(&path, &["line 8"]);
+    assert!(app.poll_live());
+    assert_eq!(app.selected_file_line(), Some(6));
+    // G follows again.
+    press(&mut app, KeyCode::Char('G'));
+    assert_eq!(app.selected_file_line(), Some(8));
+    append(&path, &["line 9"]);
+    assert!(app.poll_live());
+    assert_eq!(app.selected_file_line(), Some(9));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn live_after_loading_appends_only_new_lines() {
+    let (mut app, path) = file_app("loaded", &numbered(1..=5));
+    press(&mut app, KeyCode::Char('G'));
+    press(&mut app, KeyCode::Char('L'));
+    append(&path, &["line 6"]);
+    assert!(app.poll_live());
+    assert_eq!(app.selected_file_line(), Some(6));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn jump_list_goes_back_and_forward() {
+    let mut app = test_app(numbered(1..=100));
+    press(&mut app, KeyCode::Char(':'));
+    type_text(&mut app, "50");
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.selected_file_line(), Some(50));
+    press(&mut app, KeyCode::Char('G'));
+    assert_eq!(app.selected_file_line(), Some(100));
+    ctrl(&mut app, 'o');
+    assert_eq!(app.selected_file_line(), Some(50));
+    ctrl(&mut app, 'o');
+    assert_eq!(app.selected_file_line(), Some(1));
+    // Nothing further back: the selection stays.
+    ctrl(&mut app, 'o');
+    assert_eq!(app.selected_file_line(), Some(1));
+    ctrl(&mut app, 'i');
+    assert_eq!(app.selected_file_line(), Some(50));
+    ctrl(&mut app, 'i');
+    assert_eq!(app.selected_file_line(), Some(100));
+}
+
+#[test]
+fn typing_or_pasting_into_the_filter_stops_at_the_cap() {
+    let mut config = AppConfig::default();
+    config.settings.max_filter_len = 5;
+    let mut app = App::new_with_config(numbered(1..=3), None, 0, 1, RunOptions::default(), config);
+    press(&mut app, KeyCode::Char('/'));
+    type_text(&mut app, "error: disk full");
+    assert_eq!(app.filter(), "error");
+    // A paste arrives as key presses too; inserting at the start is refused the same way.
+    press(&mut app, KeyCode::Home);
+    type_text(&mut app, "warn");
+    assert_eq!(app.filter(), "error");
+    press(&mut app, KeyCode::End);
+    press(&mut app, KeyCode::Backspace);
+    press(&mut app, KeyCode::Char('!'));
+    press(&mut app, KeyCode::Char('?'));
+    assert_eq!(app.filter(), "erro!");
+}
+
+#[test]
+fn find_next_and_previous_wrap_around() {
+    let lines = ["ok", "err one", "ok", "err two", "ok"]
+        .map(String::from)
+        .to_vec();
+    let mut app = test_app(lines);
+    press(&mut app, KeyCode::Char('*'));
+    type_text(&mut app, "err");
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.selected_file_line(), Some(2));
+    press(&mut app, KeyCode::Char('n'));
+    assert_eq!(app.selected_file_line(), Some(4));
+    press(&mut app, KeyCode::Char('n'));
+    assert_eq!(
+        app.selected_file_line(),
+        Some(2),
+        "n wraps to the first match"
+    );
+    press(&mut app, KeyCode::Char('N'));
+    assert_eq!(
+        app.selected_file_line(),
+        Some(4),
+        "N wraps to the last match"
+    );
+    press(&mut app, KeyCode::Char('N'));
+    assert_eq!(app.selected_file_line(), Some(2));
+}
@@ -0,0 +1,330 @@
+//! Render `App` into a ratatui `TestBackend` and check the visible text.
+
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{Terminal, backend::TestBackend, buffer::Buffer};
+use ratlog::cli::RunOptions;
+use ratlog::i18n::{self, Language};
+use ratlog::{App, AppConfig, SourceKind};
+
+fn lines(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+/// An `App` with default settings: nothing is read from or saved to the developer's own
+/// `settings.json`.
+fn test_app(
+    logs: Vec<String>,
+    path: Option<PathBuf>,
+    file_line_start: usize,
+    options: RunOptions,
+) -> App {
+    App::new_with_config(
+        logs,
+        path,
+        0,
+        file_line_start,
+        options,
+        AppConfig::default(),
+    )
+}
+
+fn render(app: &mut App, width: u16, height: u16) -> String {
+    i18n::set_language(Language::En);
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    buffer_text(terminal.backend().buffer())
+}
+
+fn buffer_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut out = String::new();
+    for y in 0..area.height {
+        for x in 0..area.width {
+            out.push_str(buffer[(x, y)].symbol());
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[test]
+fn renders_line_numbers_and_content() {
+    let mut app = test_app(
+        lines(&["first line", "second line"]),
+        None,
+        41,
+        RunOptions::default(),
+    );
+    let screen = render(&mut app, 80, 12);
+    assert!(screen.contains("    41 │ first line"), "{screen}");
+    assert!(screen.contains("    42 │ second line"), "{screen}");
+}
+
+#[test]
+fn renders_filter_box_and_logs_block() {
+    let mut app = test_app(lines(&["a"]), None, 1, RunOptions::default());
+    let screen = render(&mut app, 80, 12);
+    assert!(screen.contains(" Filter "), "{screen}");
+    assert!(screen.contains(" Logs "), "{screen}");
+}
+
+#[test]
+fn renders_status_bar_counts() {
+    let mut app = test_app(
+        lines(&["one", "two", "three"]),
+        None,
+        1,
+        RunOptions::default(),
+    );
+    let screen = render(&mut app, 200, 12);
+    assert!(screen.contains("3 / 3 lines"), "{screen}");
+    assert!(screen.contains("Filter: \"(none)\""), "{screen}");
+}
+
+#[test]
+fn no_mem_omits_ram_from_status_bar() {
+    let mut app = test_app(lines(&["one"]), None, 1, RunOptions::default());
+    assert!(render(&mut app, 200, 12).contains("RAM:"));
+    let mut app = test_app(
+        lines(&["one"]),
+        None,
+        1,
+        RunOptions {
+            no_mem: true,
+            ..RunOptions::default()
+        },
+    );
+    let screen = render(&mut app, 200, 12);
+    assert!(!screen.contains("RAM:"), "{screen}");
+    assert!(screen.contains("1 / 1 lines"), "{screen}");
+}
+
+#[test]
+fn narrow_status_bar_drops_fields_instead_of_clipping() {
+    let mut app = test_app(
+        lines(&["one", "two"]),
+        None,
+        1,
+        RunOptions {
+            no_mem: true,
+            ..RunOptions::default()
+        },
+    );
+    let screen = render(&mut app, 40, 12);
+    let status = screen.lines().nth(10).unwrap();
+    assert!(status.contains("2 / 2 lines"), "{screen}");
+    assert!(status.contains("Filter: \"(none)\""), "{screen}");
+    assert!(!status.contains("quit"), "{screen}");
+}
+
+#[test]
+fn status_message_replaces_hint_line() {
+    let mut app = test_app(lines(&["one"]), None, 1, RunOptions::default());
+    assert!(render(&mut app, 100, 12).contains("?: help"));
+    app.set_message("Copied 1 lines to the clipboard");
+    let screen = render(&mut app, 100, 12);
+    let hint = screen.lines().nth(11).unwrap();
+    assert!(hint.contains("Copied 1 lines"), "{screen}");
+    assert!(!screen.contains("?: help"), "{screen}");
+}
+
+#[test]
+fn follow_starts_live_only_for_files() {
+    let follow = RunOptions {
+        follow: true,
+        ..RunOptions::default()
+    };
+    let mut app = test_app(
+        lines(&["one", "two"]),
+        Some("app.log".into()),
+        1,
+        follow.clone(),
+    );
+    let screen = render(&mut app, 200, 12);
+    assert!(screen.contains("LIVE"), "{screen}");
+    let mut app = test_app(lines(&["one", "two"]), None, 1, follow);
+    assert!(!render(&mut app, 200, 12).contains("LIVE"));
+}
+
+#[test]
+fn filter_option_opens_filtered() {
+    let options = RunOptions {
+        filter: Some("error".to_string()),
+        ..RunOptions::default()
+    };
+    let mut app = test_app(
+        lines(&["INFO ok", "ERROR bad", "INFO fine"]),
+        None,
+        1,
+        options,
+    );
+    let screen = render(&mut app, 200, 12);
+    assert!(screen.contains("ERROR bad"), "{screen}");
+    assert!(!screen.contains("INFO ok"), "{screen}");
+    assert!(screen.contains("1 / 3 lines"), "{screen}");
+}
+
+#[test]
+fn scrollbar_only_when_lines_overflow() {
+    let mut app = test_app(lines(&["one", "two"]), None, 1, RunOptions::default());
+    assert!(!render(&mut app, 80, 12).contains('█'));
+    let many: Vec<String> = (0..100).map(|i| format!("line {i}")).collect();
+    let mut app = test_app(many, None, 1, RunOptions::default());
+    let screen = render(&mut app, 80, 12);
+    let thumb_row = screen.lines().find(|l| l.contains('█')).unwrap_or("");
+    assert!(thumb_row.ends_with('█'), "{screen}");
+}
+
+#[test]
+fn log_pane_scrolls_to_keep_the_selection_in_view() {
+    let many: Vec<String> = (1..=100).map(|i| format!("line {i}")).collect();
+    let mut app = test_app(many, None, 1, RunOptions::default());
+    app.on_key_event(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::NONE));
+    let screen = render(&mut app, 80, 12);
+    assert!(screen.contains("▸    100 │ line 100"), "{screen}");
+    assert!(!screen.contains("│ line 95"), "{screen}");
+    for _ in 0..5 {
+        app.on_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+    }
+    let screen = render(&mut app, 80, 12);
+    assert!(screen.contains("▸     95 │ line 95"), "{screen}");
+    assert!(!screen.contains("│ line 100"), "{screen}");
+}
+
+#[test]
+fn all_option_keeps_more_than_max_lines() {
+    let many: Vec<String> = (1..=200).map(|i| format!("line {i}")).collect();
+    let mut app = test_app(many.clone(), None, 1, RunOptions::default());
+    assert!(render(&mut app, 200, 12).contains("150 / 150 lines"));
+    let all = RunOptions {
+        all: true,
+        no_mem: true,
+        ..RunOptions::default()
+    };
+    let mut app = test_app(many, None, 1, all);
+    let screen = render(&mut app, 200, 12);
+    assert!(screen.contains("200 / 200 lines"), "{screen}");
+    assert!(screen.contains("     1 │ line 1"), "{screen}");
+}
+
+#[test]
+fn gutter_is_styled_apart_from_content() {
+    i18n::set_language(Language::En);
+    let mut app = test_app(lines(&["first", "second"]), None, 1, RunOptions::default());
+    let mut terminal = Terminal::new(TestBackend::new(60, 12)).unwrap();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    let buffer = terminal.backend().buffer();
+    // Second row (the first one is selected): gutter digit and first content character.
+    let y = (0..12)
+        .find(|&y| buffer[(9, y)].symbol() == "2")
+        .expect("gutter for line 2");
+    assert_eq!(buffer[(13, y)].symbol(), "s");
+    assert_ne!(buffer[(9, y)].fg, buffer[(13, y)].fg);
+}
+
+#[test]
+fn status_bar_shows_source_badge() {
+    let options = RunOptions {
+        no_mem: true,
+        ..RunOptions::default()
+    };
+    let mut app = test_app(lines(&["one"]), None, 1, options.clone());
+    let screen = render(&mut app, 200, 12);
+    assert!(screen.contains("[sample]"), "{screen}");
+    assert!(!screen.contains("L: live"), "{screen}");
+    let mut app =
+        test_app(lines(&["one"]), None, 1, options.clone()).with_source(SourceKind::Stdin);
+    assert!(render(&mut app, 200, 12).contains("[stdin]"));
+    let mut app = test_app(lines(&["one"]), Some("app.log".into()), 1, options);
+    let screen = render(&mut app, 200, 12);
+    assert!(screen.contains("[file]"), "{screen}");
+    assert!(screen.contains("L: live"), "{screen}");
+}
+
+#[test]
+fn status_bar_shows_uptime() {
+    let options = RunOptions {
+        no_mem: true,
+        ..RunOptions::default()
+    };
+    let mut app = test_app(lines(&["one"]), None, 1, options);
+    let screen = render(&mut app, 200, 12);
+    assert!(screen.contains("up 0s"), "{screen}");
+}
+
+#[test]
+fn tiny_terminal_shows_too_small_message() {
+    let mut app = test_app(
+        lines(&["first line", "second line"]),
+        None,
+        1,
+        RunOptions::default(),
+    );
+    // Must not panic even at a single cell.
+    render(&mut app, 1, 1);
+    let screen = render(&mut app, 19, 12);
+    assert!(screen.contains("Terminal"), "{screen}");
+    assert!(!screen.contains("first line"), "{screen}");
+    let screen = render(&mut app, 80, 7);
+    assert!(
+        screen.contains("Terminal too small (needs 20×8)"),
+        "{screen}"
+    );
+    let screen = render(&mut app, 20, 8);
+    assert!(screen.contains("1 │ first"), "{screen}");
+}
+
+fn press(app: &mut App, code: KeyCode) {
+    app.on_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+}
+
+/// Start a find for `text` and return the file line it selects.
+fn find(app: &mut App, text: &str) -> Option<usize> {
+    press(app, KeyCode::Char('*'));
+    for c in text.chars() {
+        press(app, KeyCode::Char(c));
+    }
+    press(app, KeyCode::Enter);
+    app.selected_file_line()
+}
+
+#[test]
+fn find_skips_matches_past_the_right_edge() {
+    let long = format!("err {} err", "x".repeat(100));
+    let mut app = test_app(
+        vec![long, "ok".into(), "err two".into(), "ok".into()],
+        None,
+        1,
+        RunOptions::default(),
+    );
+    render(&mut app, 60, 12);
+    assert_eq!(find(&mut app, "err"), Some(1));
+    // The second match on line 1 is clipped off screen, so n goes on to line 3.
+    press(&mut app, KeyCode::Char('n'));
+    assert_eq!(app.selected_file_line(), Some(3));
+}
+
+#[test]
+fn find_counts_matches_on_the_truncated_text() {
+    let long = format!("err {} err {} err", "a".repeat(50), "b".repeat(50));
+    let mut config = AppConfig::default();
+    config.settings.truncate_middle = true;
+    let mut app = App::new_with_config(
+        vec![long, "ok".into(), "err two".into(), "ok".into()],
+        None,
+        0,
+        1,
+        RunOptions::default(),
+        config,
+    );
+    let screen = render(&mut app, 60, 12);
+    assert!(screen.contains('…'), "{screen}");
+    assert_eq!(find(&mut app, "err"), Some(1));
+    // The middle match is cut out: the one at the end is the second and last on line 1.
+    press(&mut app, KeyCode::Char('n'));
+    assert_eq!(app.selected_file_line(), Some(1));
+    press(&mut app, KeyCode::Char('n'));
+    assert_eq!(app.selected_file_line(), Some(3));
+}